@@ -0,0 +1,2 @@
+# Logging
+log-written-to = Das Protokoll wird in '{$file_name}' geschrieben
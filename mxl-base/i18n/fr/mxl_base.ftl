@@ -0,0 +1,2 @@
+# Logging
+log-written-to = Le journal est écrit dans '{$file_name}'
@@ -1,5 +1,7 @@
 use std::sync::OnceLock;
 
+use crate::build_info::BuildInfo;
+
 #[derive(Debug)]
 pub struct About {
     pub qualifier: &'static str,
@@ -7,6 +9,7 @@ pub struct About {
     pub app_name: &'static str,
     pub binary_name: &'static str,
     pub version: &'static str,
+    pub build_info: BuildInfo,
 }
 
 static ABOUT_REGISTER: OnceLock<About> = OnceLock::new();
@@ -17,6 +20,7 @@ pub(crate) fn about_init(
     app_name: &'static str,
     binary_name: &'static str,
     version: &'static str,
+    build_info: BuildInfo,
 ) {
     ABOUT_REGISTER
         .set(About {
@@ -25,6 +29,7 @@ pub(crate) fn about_init(
             app_name,
             binary_name,
             version,
+            build_info,
         })
         .expect("Already initialized");
 }
@@ -32,3 +37,8 @@ pub(crate) fn about_init(
 pub fn about() -> &'static About {
     ABOUT_REGISTER.get().expect("Initialize first")
 }
+
+// For code which can also run without initialization, e.g. in tests of other crates:
+pub fn try_about() -> Option<&'static About> {
+    ABOUT_REGISTER.get()
+}
@@ -0,0 +1,140 @@
+use std::{fmt, path::Path, process::Command};
+
+const ENV_GIT_COMMIT: &str = "MXL_BUILD_GIT_COMMIT";
+const ENV_GIT_DIRTY: &str = "MXL_BUILD_GIT_DIRTY";
+const ENV_PROFILE: &str = "MXL_BUILD_PROFILE";
+const ENV_TARGET: &str = "MXL_BUILD_TARGET";
+const ENV_TIMESTAMP: &str = "MXL_BUILD_TIMESTAMP";
+const ENV_FEATURES: &str = "MXL_BUILD_FEATURES";
+const ENV_RUSTC_VERSION: &str = "MXL_BUILD_RUSTC_VERSION";
+
+// Metadata of the application build, all values are missing if emit() was not called in the build script:
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub git_commit: Option<&'static str>,
+    // Uncommitted changes of tracked files in the working tree:
+    pub git_dirty: Option<bool>,
+    pub profile: Option<&'static str>,
+    pub target: Option<&'static str>,
+    // RFC 3339 time of the build, or of SOURCE_DATE_EPOCH for reproducible builds:
+    pub timestamp: Option<&'static str>,
+    pub features: Vec<&'static str>,
+    // Output of "rustc -V" of the compiler, which built the application:
+    pub rustc_version: Option<&'static str>,
+}
+
+// Read the metadata set by build_info::emit() in the build script of the calling crate:
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        $crate::build_info::BuildInfo {
+            git_commit: option_env!("MXL_BUILD_GIT_COMMIT").filter(|value| !value.is_empty()),
+            git_dirty: option_env!("MXL_BUILD_GIT_DIRTY")
+                .filter(|value| !value.is_empty())
+                .map(|value| value == "true"),
+            profile: option_env!("MXL_BUILD_PROFILE").filter(|value| !value.is_empty()),
+            target: option_env!("MXL_BUILD_TARGET").filter(|value| !value.is_empty()),
+            timestamp: option_env!("MXL_BUILD_TIMESTAMP").filter(|value| !value.is_empty()),
+            features: option_env!("MXL_BUILD_FEATURES")
+                .unwrap_or_default()
+                .split(',')
+                .filter(|feature| !feature.is_empty())
+                .collect(),
+            rustc_version: option_env!("MXL_BUILD_RUSTC_VERSION").filter(|value| !value.is_empty()),
+        }
+    };
+}
+
+impl BuildInfo {
+    // Git commit with a "-dirty" suffix for uncommitted changes, like "git describe --dirty":
+    pub fn git_version(&self) -> Option<String> {
+        self.git_commit.map(|commit| match self.git_dirty {
+            Some(true) => format!("{commit}-dirty"),
+            _ => commit.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unknown = "unknown";
+        write!(
+            f,
+            "Commit={} Profile={} Target={} Timestamp={} Features=[{}] Rustc={}",
+            self.git_version().as_deref().unwrap_or(unknown),
+            self.profile.unwrap_or(unknown),
+            self.target.unwrap_or(unknown),
+            self.timestamp.unwrap_or(unknown),
+            self.features.join(","),
+            self.rustc_version.unwrap_or(unknown)
+        )
+    }
+}
+
+// Capture the build metadata in the build script of the application, read it with mxl_base::build_info!():
+pub fn emit() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let manifest_dir = Path::new(&manifest_dir);
+
+    let git_commit = git(manifest_dir, &["rev-parse", "HEAD"]);
+    let git_dirty = git_commit
+        .as_ref()
+        .and_then(|_| git(manifest_dir, &["status", "--porcelain", "--untracked-files=no"]))
+        .map(|status| (!status.is_empty()).to_string());
+    // Rebuild when the commit or the git index changes, edits of unstaged files do not update the dirty flag:
+    if let Some(git_dir) = git(manifest_dir, &["rev-parse", "--absolute-git-dir"]) {
+        let git_dir = Path::new(&git_dir);
+        for file in ["HEAD", "index"] {
+            println!("cargo:rerun-if-changed={}", git_dir.join(file).to_string_lossy());
+        }
+    }
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    let timestamp = match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(epoch) => epoch
+            .trim()
+            .parse()
+            .ok()
+            .and_then(|epoch| chrono::DateTime::from_timestamp(epoch, 0))
+            .map(|timestamp| timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+        Err(_) => Some(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+    };
+    // Cargo sets CARGO_FEATURE_<NAME> for every enabled feature, with upper case name and "-" replaced by "_":
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(name, _)| {
+            name.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase())
+        })
+        .collect();
+    features.sort();
+    // Cargo sets RUSTC to the compiler used for the build:
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = command_output(&rustc, manifest_dir, &["-V"]);
+
+    let values = [
+        (ENV_GIT_COMMIT, git_commit),
+        (ENV_GIT_DIRTY, git_dirty),
+        (ENV_PROFILE, std::env::var("PROFILE").ok()),
+        (ENV_TARGET, std::env::var("TARGET").ok()),
+        (ENV_TIMESTAMP, timestamp),
+        (ENV_FEATURES, Some(features.join(","))),
+        (ENV_RUSTC_VERSION, rustc_version),
+    ];
+    for (name, value) in values {
+        println!("cargo:rustc-env={name}={}", value.unwrap_or_default());
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    command_output("git", dir, args)
+}
+
+fn command_output(program: &str, dir: &Path, args: &[&str]) -> Option<String> {
+    Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|output| output.trim().to_string())
+}
@@ -0,0 +1,169 @@
+use anyhow::{Context, Result};
+use i18n_embed::{DefaultLocalizer, DesktopLanguageRequester, I18nAssets, Localizer, fluent::FluentLanguageLoader};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::sync::{
+    Arc, Mutex, Once,
+    atomic::{AtomicU64, Ordering},
+};
+
+use crate::settings::{Settings, settings};
+
+pub use i18n_embed::unic_langid::LanguageIdentifier;
+
+// Overrides the language of the setting and the desktop, e.g. "de" or "fr-CH":
+pub const ENV_NAME_LANGUAGE: &str = "MXL_LANGUAGE";
+
+// Persisted language of the application, used if neither set_language() nor the environment variable select one:
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanguageSettings {
+    pub language: Option<String>,
+}
+
+impl Settings for LanguageSettings {
+    const NAME: &'static str = "language";
+    const VERSION: u32 = 1;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LanguageListenerId(u64);
+
+type Listener = Arc<dyn Fn(&[LanguageIdentifier]) + Send + Sync>;
+
+// The localizers of all crates are switched together, so that an application never shows mixed languages:
+static LOCALIZERS: Mutex<Vec<DefaultLocalizer<'static>>> = Mutex::new(Vec::new());
+static OVERRIDE: Mutex<Option<LanguageIdentifier>> = Mutex::new(None);
+static NEXT_LISTENER_ID: AtomicU64 = AtomicU64::new(0);
+static LISTENERS: Mutex<Vec<(LanguageListenerId, Listener)>> = Mutex::new(Vec::new());
+
+// Called by the localization init function of every crate, selects the currently requested languages:
+pub fn register_language_loader(
+    loader: &'static FluentLanguageLoader,
+    localizations: &'static (dyn I18nAssets + Send + Sync + 'static),
+) {
+    let localizer = DefaultLocalizer::new(loader, localizations);
+    if let Err(error) = localizer.select(&requested_languages()) {
+        error!("Error while loading language: {error}");
+    }
+    LOCALIZERS.lock().unwrap().push(localizer);
+}
+
+// Languages in the order of preference: set_language(), the environment variable, the setting and the desktop:
+pub fn requested_languages() -> Vec<LanguageIdentifier> {
+    if let Some(language) = OVERRIDE.lock().unwrap().clone() {
+        return vec![language];
+    }
+    if let Ok(language) = std::env::var(ENV_NAME_LANGUAGE) {
+        match parse_language(&language) {
+            Ok(language) => return vec![language],
+            Err(error) => warn!("Ignore {ENV_NAME_LANGUAGE}: {error:?}"),
+        }
+    }
+    if let Some(language) = language_setting() {
+        match parse_language(&language) {
+            Ok(language) => return vec![language],
+            Err(error) => warn!("Ignore language setting: {error:?}"),
+        }
+    }
+    DesktopLanguageRequester::requested_languages()
+}
+
+// Switch the language of all crates at runtime, None restores the language of the environment, setting or desktop:
+pub fn set_language(language: Option<&str>) -> Result<()> {
+    let language = language.map(parse_language).transpose()?;
+    *OVERRIDE.lock().unwrap() = language;
+    reload();
+    Ok(())
+}
+
+// Languages for which at least one crate has translations:
+pub fn available_languages() -> Vec<LanguageIdentifier> {
+    let mut languages: Vec<LanguageIdentifier> = Vec::new();
+    for localizer in LOCALIZERS.lock().unwrap().iter() {
+        match localizer.available_languages() {
+            Ok(available) => languages.extend(available),
+            Err(error) => warn!("Cannot determine available languages: {error}"),
+        }
+    }
+    languages.sort_by_key(|language| language.to_string());
+    languages.dedup();
+    languages
+}
+
+// Select the requested languages again in all crates and notify the listeners:
+pub fn reload() {
+    let requested_languages = requested_languages();
+    for localizer in LOCALIZERS.lock().unwrap().iter() {
+        if let Err(error) = localizer.select(&requested_languages) {
+            error!("Error while loading language: {error}");
+        }
+    }
+    info!(
+        "Switched language to {}",
+        requested_languages
+            .iter()
+            .map(|language| language.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+
+    // Listeners are called without lock, so that they can subscribe or unsubscribe:
+    let listeners: Vec<_> = LISTENERS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(_, listener)| listener.clone())
+        .collect();
+    for listener in listeners {
+        listener(&requested_languages);
+    }
+}
+
+// Called after the language was switched, e.g. to send a message to components which re-render their strings.
+// Strings which were already translated do not change, so long-lived components subscribe and translate their
+// labels again:
+pub fn subscribe(listener: impl Fn(&[LanguageIdentifier]) + Send + Sync + 'static) -> LanguageListenerId {
+    let id = LanguageListenerId(NEXT_LISTENER_ID.fetch_add(1, Ordering::Relaxed));
+    LISTENERS.lock().unwrap().push((id, Arc::new(listener)));
+    id
+}
+
+pub fn unsubscribe(id: LanguageListenerId) {
+    LISTENERS.lock().unwrap().retain(|(listener_id, _)| *listener_id != id);
+}
+
+// Like subscribe(), but the listener is unsubscribed when the subscription is dropped, e.g. with the model
+// of a component:
+#[must_use]
+#[derive(Debug)]
+pub struct LanguageSubscription(LanguageListenerId);
+
+impl Drop for LanguageSubscription {
+    fn drop(&mut self) {
+        unsubscribe(self.0);
+    }
+}
+
+pub fn subscription(listener: impl Fn(&[LanguageIdentifier]) + Send + Sync + 'static) -> LanguageSubscription {
+    LanguageSubscription(subscribe(listener))
+}
+
+fn parse_language(language: &str) -> Result<LanguageIdentifier> {
+    // Also accept POSIX locales like "de_DE.UTF-8":
+    let language = language.split(['.', '@']).next().unwrap_or_default().replace('_', "-");
+    language
+        .parse()
+        .with_context(|| format!("Invalid language '{language}'"))
+}
+
+// The setting can only be read after mxl_base::init(), because the config directory depends on the application:
+fn language_setting() -> Option<String> {
+    crate::about::try_about()?;
+    let store = settings::<LanguageSettings>();
+    static SUBSCRIBE: Once = Once::new();
+    SUBSCRIBE.call_once(|| {
+        store.subscribe(|_| reload());
+    });
+    store.get().language
+}
@@ -1,9 +1,16 @@
 mod about;
+pub mod build_info;
+pub mod language;
 mod localization;
 pub mod logging;
 pub mod misc;
+pub mod redaction;
+pub mod settings;
+pub mod single_instance;
+#[cfg(feature = "translation-check")]
+pub mod translations;
 
-pub use about::about;
-pub use misc::init;
+pub use about::{About, about, try_about};
+pub use misc::{init, init_with_build_info};
 
 pub use fern;
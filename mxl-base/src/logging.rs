@@ -1,10 +1,24 @@
-use crate::localization::helper::fl;
-use anyhow::Result;
+mod error;
+mod format;
+mod levels;
+mod rotation;
+
+pub use error::{LogFallback, LoggingError};
+pub use format::{LogFields, LogFormat};
+pub use levels::{
+    ENV_NAME_LOG, ModuleLogLevels, load_module_log_levels, log_levels_config_file, module_log_levels, parse_log_spec,
+    save_module_log_levels, set_console_module_log_level, set_module_log_level, set_module_log_levels,
+};
+pub use rotation::{LogRotation, RotatingLogFile};
+
+use crate::{localization::helper::fl, redaction::Redactor};
 use log::*;
 use std::{
+    borrow::Cow,
     collections::HashMap,
     path::{Path, PathBuf},
     sync::{LazyLock, OnceLock},
+    time::Duration,
 };
 
 const DEFAULT_LOG_FILE_LOG_LEVEL: log::LevelFilter = log::LevelFilter::Trace;
@@ -34,17 +48,31 @@ pub fn get_console_log_level() -> log::LevelFilter {
     *CONSOLE_LOG_LEVEL.read().unwrap()
 }
 
+static REDACTOR: OnceLock<Redactor> = OnceLock::new();
+// The redactor of the builder, e.g. for dispatches added with Builder::add_dispatch():
+pub fn redactor() -> Option<&'static Redactor> {
+    REDACTOR.get()
+}
+
 static CURRENT_LOG_FILE_HOLDER: OnceLock<PathBuf> = OnceLock::new();
 pub fn current_log_file() -> &'static PathBuf {
     CURRENT_LOG_FILE_HOLDER.get().expect("init() must be called first")
 }
+pub fn try_current_log_file() -> Option<&'static PathBuf> {
+    CURRENT_LOG_FILE_HOLDER.get()
+}
 
 #[derive(Default)]
 pub struct Builder {
     level_for: HashMap<&'static str, log::LevelFilter>,
     console_level_for: HashMap<&'static str, log::LevelFilter>,
     without_console: bool,
+    fallback: LogFallback,
+    redactor: Option<Redactor>,
     dispatches: Vec<fern::Dispatch>,
+    rotation: LogRotation,
+    file_format: Option<LogFormat>,
+    console_format: Option<LogFormat>,
 }
 
 impl Builder {
@@ -72,83 +100,184 @@ impl Builder {
         self
     }
 
-    fn add_log_level_for(
-        mut logger: fern::Dispatch,
-        levels_for: &HashMap<&'static str, log::LevelFilter>,
-    ) -> fern::Dispatch {
-        for (module, level) in levels_for {
-            logger = logger.level_for(*module, *level);
+    // Where to log when the log file cannot be created, defaults to a log file in the temporary directory:
+    pub fn fallback(mut self, fallback: LogFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    // Remove personal data from the messages of the log file and the console:
+    pub fn redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
+    // Format of the log file, defaults to text with all fields:
+    pub fn file_format(mut self, format: LogFormat) -> Self {
+        self.file_format = Some(format);
+        self
+    }
+
+    // Format of the console output, defaults to text without additional fields:
+    pub fn console_format(mut self, format: LogFormat) -> Self {
+        self.console_format = Some(format);
+        self
+    }
+
+    // Rotate the log file when it reaches the size, None disables size based rotation:
+    pub fn max_log_file_size(mut self, size: Option<u64>) -> Self {
+        self.rotation.max_size = size;
+        self
+    }
+
+    // Rotate the log file when it is older than the age, also across program runs:
+    pub fn max_log_file_age(mut self, age: Option<Duration>) -> Self {
+        self.rotation.max_age = age;
+        self
+    }
+
+    // Number of rotated log files to keep, older files are removed:
+    pub fn keep_log_files(mut self, count: usize) -> Self {
+        self.rotation.keep = count;
+        self
+    }
+
+    #[cfg(feature = "gzip")]
+    pub fn compress_rotated_log_files(mut self, compress: bool) -> Self {
+        self.rotation.compress = compress;
+        self
+    }
+
+    fn redacting_format(
+        format: LogFormat,
+        redactor: Option<Redactor>,
+    ) -> impl Fn(fern::FormatCallback, &std::fmt::Arguments, &log::Record) + Sync + Send + 'static {
+        move |out, message, record| {
+            let Some(redactor) = &redactor else {
+                return format.format(out, message, record);
+            };
+            // Messages without arguments are redacted without copying them:
+            let text = match message.as_str() {
+                Some(text) => Cow::Borrowed(text),
+                None => Cow::Owned(message.to_string()),
+            };
+            match redactor.redact(&text) {
+                Cow::Borrowed(_) => format.format(out, message, record),
+                Cow::Owned(redacted) => format.format(out, &format_args!("{redacted}"), record),
+            }
         }
-        logger
     }
 
-    fn build_with_panic_on_failure(&mut self, log_dir: &Path) {
-        // NOTE!!!
-        // Every error MUST be a panic here else the user will not be able to see the error!
+    fn open_log_file(&self, log_dir: &Path) -> Result<(PathBuf, RotatingLogFile), LoggingError> {
+        let log_file = log_dir.join(format!("{}.{}", super::about::about().binary_name, LOG_FILE_EXTENSION));
+        std::fs::create_dir_all(log_dir).map_err(|source| LoggingError::CreateDir {
+            path: log_dir.to_path_buf(),
+            source,
+        })?;
+        let writer =
+            RotatingLogFile::open(&log_file, self.rotation.clone()).map_err(|source| LoggingError::OpenFile {
+                path: log_file.clone(),
+                source,
+            })?;
+        Ok((log_file, writer))
+    }
 
+    fn apply(&mut self, log_file_writer: Option<RotatingLogFile>) -> Result<(), LoggingError> {
+        // The module levels are checked for every record, so that they can be changed at runtime:
         let mut basic_logger = fern::Dispatch::new();
-        basic_logger = Self::add_log_level_for(basic_logger, &self.level_for);
 
-        {
+        if let Some(log_file_writer) = log_file_writer {
             // log file logger
-            let log_file = CURRENT_LOG_FILE_HOLDER
-                .get_or_init(|| log_dir.join(format!("{}.{}", super::about::about().binary_name, LOG_FILE_EXTENSION)));
-            std::fs::create_dir_all(log_dir).unwrap_or_else(|error| {
-                panic!(
-                    "Cannot create logging directory '{}': {:?}",
-                    log_dir.to_string_lossy(),
-                    error
-                )
-            });
-            let mut file_logger = fern::Dispatch::new()
-                .filter(|metadata| metadata.level() <= get_log_file_log_level())
-                .format(|out, message, record| {
-                    out.finish(format_args!("{} [{}] {}", record.level(), record.target(), message))
-                })
-                .chain(fern::log_file(log_file).unwrap_or_else(|error| {
-                    panic!("Cannot open log file '{}': {:?}", log_file.to_string_lossy(), error)
-                }));
-            file_logger = Self::add_log_level_for(file_logger, &self.level_for);
+            let file_format = self.file_format.unwrap_or(LogFormat::Text(LogFields::ALL));
+            let file_logger = fern::Dispatch::new()
+                .filter(|metadata| metadata.level() <= levels::file_level_for(metadata.target()))
+                .format(Self::redacting_format(file_format, self.redactor.clone()))
+                .chain(fern::Output::from(
+                    Box::new(log_file_writer) as Box<dyn std::io::Write + Send>
+                ));
 
             basic_logger = basic_logger.chain(file_logger)
         }
 
         if !self.without_console {
             // console logger
-            let mut console_logger = fern::Dispatch::new()
-                .format(|out, message, record| {
-                    out.finish(format_args!("{} [{}] {}", record.level(), record.target(), message))
-                })
-                .filter(|metadata| metadata.level() <= get_console_log_level())
+            let console_format = self.console_format.unwrap_or(LogFormat::Text(LogFields::NONE));
+            let console_logger = fern::Dispatch::new()
+                .format(Self::redacting_format(console_format, self.redactor.clone()))
+                .filter(|metadata| metadata.level() <= levels::console_level_for(metadata.target()))
                 .chain(std::io::stderr());
-            console_logger = Self::add_log_level_for(console_logger, &self.level_for);
-            console_logger = Self::add_log_level_for(console_logger, &self.console_level_for);
 
             basic_logger = basic_logger.chain(console_logger);
         }
-        for dispatch in std::mem::take(&mut self.dispatches) {
-            basic_logger = basic_logger.chain(dispatch);
+        if !self.dispatches.is_empty() {
+            // Additional dispatches get the records of the log file:
+            let mut dispatches_logger =
+                fern::Dispatch::new().filter(|metadata| metadata.level() <= levels::file_level_for(metadata.target()));
+            for dispatch in std::mem::take(&mut self.dispatches) {
+                dispatches_logger = dispatches_logger.chain(dispatch);
+            }
+            basic_logger = basic_logger.chain(dispatches_logger);
         }
-        basic_logger.apply().expect("Cannot start logging");
+        basic_logger.apply().map_err(LoggingError::AlreadyInitialized)
     }
 
-    pub fn build(mut self, log_dir: &Path) -> Result<()> {
-        self.build_with_panic_on_failure(log_dir);
+    pub fn build(mut self, log_dir: &Path) -> Result<(), LoggingError> {
         let about = super::about::about();
+        let (log_file, fallback_error) = match self.open_log_file(log_dir) {
+            Ok(log_file) => (Some(log_file), None),
+            Err(error) => match self.fallback {
+                LogFallback::Disabled => return Err(error),
+                LogFallback::Console => (None, Some(error)),
+                LogFallback::TempDir => (
+                    self.open_log_file(&std::env::temp_dir().join(about.binary_name)).ok(),
+                    Some(error),
+                ),
+            },
+        };
+        let (log_file, log_file_writer) = log_file.unzip();
+        if log_file.is_none() {
+            // Without log file the console is the only place where the log can be seen:
+            self.without_console = false;
+        }
+        self.apply(log_file_writer)?;
+        // The levels of the builder are the defaults for the saved levels and the environment variable.
+        // They are set after the logger is applied, so that a failed build, e.g. of a second logger, does not
+        // change them:
+        levels::set_default_module_log_levels(&self.level_for, &self.console_level_for);
+        let environment_result = levels::apply_environment();
+        if let Some(redactor) = &self.redactor {
+            REDACTOR.get_or_init(|| redactor.clone());
+        }
+        // Loaded after the logger is applied, so that errors of the settings are logged:
+        load_module_log_levels();
 
-        let log_file = current_log_file();
-        if !self.without_console {
-            // Currently not use translation. The log file name is wrapped to: <2068>log-file-name<2069>
-            // Some terminals copy these possibly invisible special characters when selecting and copying,
-            // so that the log file cannot be opened.
-            if false {
-                println!("{}", fl!("log-written-to", file_name = log_file.to_string_lossy()));
-            } else {
-                println!("Log is written to '{}'", log_file.to_string_lossy());
+        if let Some(log_file) = log_file {
+            let log_file = CURRENT_LOG_FILE_HOLDER.get_or_init(|| log_file);
+            if !self.without_console {
+                // Currently not use translation. The log file name is wrapped to: <2068>log-file-name<2069>
+                // Some terminals copy these possibly invisible special characters when selecting and copying,
+                // so that the log file cannot be opened.
+                if false {
+                    println!("{}", fl!("log-written-to", file_name = log_file.to_string_lossy()));
+                } else {
+                    println!("Log is written to '{}'", log_file.to_string_lossy());
+                }
             }
         }
 
         info!("Application: {} Version: {}", about.app_name, about.version);
+        info!("Build: {}", about.build_info);
+
+        if let Some(error) = fallback_error {
+            match try_current_log_file() {
+                Some(log_file) => warn!("{error}, log is written to '{}' instead", log_file.to_string_lossy()),
+                None => warn!("{error}, log is written only to the console"),
+            }
+        }
+        // Invalid levels must not prevent the application from starting:
+        if let Err(error) = environment_result {
+            warn!("{error:?}");
+        }
 
         Ok(())
     }
@@ -0,0 +1,47 @@
+use std::{fmt, path::PathBuf};
+
+#[derive(Debug)]
+pub enum LoggingError {
+    CreateDir { path: PathBuf, source: std::io::Error },
+    OpenFile { path: PathBuf, source: std::io::Error },
+    AlreadyInitialized(log::SetLoggerError),
+}
+
+impl fmt::Display for LoggingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoggingError::CreateDir { path, source } => {
+                write!(
+                    f,
+                    "Cannot create logging directory '{}': {source}",
+                    path.to_string_lossy()
+                )
+            }
+            LoggingError::OpenFile { path, source } => {
+                write!(f, "Cannot open log file '{}': {source}", path.to_string_lossy())
+            }
+            LoggingError::AlreadyInitialized(_) => write!(f, "Logging is already initialized"),
+        }
+    }
+}
+
+impl std::error::Error for LoggingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoggingError::CreateDir { source, .. } | LoggingError::OpenFile { source, .. } => Some(source),
+            LoggingError::AlreadyInitialized(source) => Some(source),
+        }
+    }
+}
+
+// What to do when the log file cannot be created, e.g. on read-only home directories:
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFallback {
+    // Return the error:
+    Disabled,
+    // Log only to the console:
+    Console,
+    // Write the log file into the temporary directory, or only to the console if this fails too:
+    #[default]
+    TempDir,
+}
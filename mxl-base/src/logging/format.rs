@@ -0,0 +1,111 @@
+use serde::Serialize;
+use std::fmt::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogFields {
+    // RFC 3339 timestamp in local time:
+    pub timestamp: bool,
+    // Name and id of the logging thread:
+    pub thread: bool,
+    // Source file and line of the log statement:
+    pub location: bool,
+}
+
+impl LogFields {
+    pub const NONE: Self = Self {
+        timestamp: false,
+        thread: false,
+        location: false,
+    };
+    pub const ALL: Self = Self {
+        timestamp: true,
+        thread: true,
+        location: true,
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    // "{timestamp} {level} [{thread}] [{target}] {file}:{line} {message}" with the enabled fields:
+    Text(LogFields),
+    // One JSON object per line with all fields, so that the log can be parsed by tools:
+    JsonLines,
+}
+
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    timestamp: String,
+    level: &'a str,
+    target: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    module: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread: Option<&'a str>,
+    thread_id: String,
+    message: String,
+}
+
+impl LogFormat {
+    pub(super) fn format(&self, out: fern::FormatCallback, message: &fmt::Arguments, record: &log::Record) {
+        match self {
+            LogFormat::Text(fields) => {
+                let mut prefix = String::new();
+                if fields.timestamp {
+                    _ = write!(prefix, "{} ", timestamp());
+                }
+                _ = write!(prefix, "{} ", record.level());
+                if fields.thread {
+                    let thread = std::thread::current();
+                    _ = write!(
+                        prefix,
+                        "[{}:{}] ",
+                        thread.name().unwrap_or("<unnamed>"),
+                        thread_id(&thread)
+                    );
+                }
+                _ = write!(prefix, "[{}] ", record.target());
+                if fields.location
+                    && let Some(file) = record.file()
+                {
+                    _ = write!(prefix, "{file}:{} ", record.line().unwrap_or_default());
+                }
+                out.finish(format_args!("{prefix}{message}"))
+            }
+            LogFormat::JsonLines => {
+                let thread = std::thread::current();
+                let json_record = JsonRecord {
+                    timestamp: timestamp(),
+                    level: record.level().as_str(),
+                    target: record.target(),
+                    module: record.module_path(),
+                    file: record.file(),
+                    line: record.line(),
+                    thread: thread.name(),
+                    thread_id: thread_id(&thread),
+                    message: message.to_string(),
+                };
+                let json = serde_json::to_string(&json_record).unwrap_or_else(|error| {
+                    format!(
+                        "{{\"level\":\"ERROR\",\"message\":{:?}}}",
+                        format!("Cannot serialize log record: {error}")
+                    )
+                });
+                out.finish(format_args!("{json}"))
+            }
+        }
+    }
+}
+
+fn timestamp() -> String {
+    chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, false)
+}
+
+// The numeric value of a thread id is not stable API, so it is taken from the debug output "ThreadId(<id>)":
+fn thread_id(thread: &std::thread::Thread) -> String {
+    let id = format!("{:?}", thread.id());
+    id.trim_start_matches("ThreadId(").trim_end_matches(')').to_string()
+}
@@ -0,0 +1,213 @@
+use anyhow::{Context, Result, bail};
+use log::LevelFilter;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    str::FromStr,
+    sync::{LazyLock, RwLock},
+};
+
+use crate::settings::{Settings, settings, settings_file_path};
+
+pub const ENV_NAME_LOG: &str = "RUST_LOG";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModuleLogLevels {
+    // Levels for the log file and the console:
+    pub modules: BTreeMap<String, LevelFilter>,
+    // Levels only for the console, they take precedence over the modules:
+    pub console_modules: BTreeMap<String, LevelFilter>,
+}
+
+impl Settings for ModuleLogLevels {
+    const NAME: &'static str = "log_levels";
+    const VERSION: u32 = 1;
+}
+
+impl ModuleLogLevels {
+    fn set(levels: &mut BTreeMap<String, LevelFilter>, module: &str, level: Option<LevelFilter>) {
+        match level {
+            Some(level) => _ = levels.insert(module.to_string(), level),
+            None => _ = levels.remove(module),
+        }
+    }
+}
+
+// The levels of the builder are overridden by the configured levels, which are overridden by the environment:
+#[derive(Default)]
+struct State {
+    defaults: ModuleLogLevels,
+    configured: ModuleLogLevels,
+    environment: ModuleLogLevels,
+    // Merged levels sorted by the length of the module name, so that the most specific module is found first:
+    file: Vec<ModuleLevel>,
+    console: Vec<ModuleLevel>,
+}
+
+struct ModuleLevel {
+    module: String,
+    level: LevelFilter,
+    // Levels of the builder are limited by the global level, only overrides can raise it:
+    is_default: bool,
+}
+
+impl State {
+    fn update(&mut self) {
+        let overrides = [&self.configured, &self.environment];
+        self.file = merge(
+            [(&self.defaults.modules, true)]
+                .into_iter()
+                .chain(overrides.iter().map(|levels| (&levels.modules, false))),
+        );
+        self.console = merge(
+            [(&self.defaults.modules, true)]
+                .into_iter()
+                .chain(overrides.iter().map(|levels| (&levels.modules, false)))
+                .chain([(&self.defaults.console_modules, true)])
+                .chain(overrides.iter().map(|levels| (&levels.console_modules, false))),
+        );
+    }
+}
+
+static STATE: LazyLock<RwLock<State>> = LazyLock::new(|| RwLock::new(State::default()));
+
+fn merge<'a>(levels: impl Iterator<Item = (&'a BTreeMap<String, LevelFilter>, bool)>) -> Vec<ModuleLevel> {
+    let mut merged = BTreeMap::new();
+    for (levels, is_default) in levels {
+        merged.extend(
+            levels
+                .iter()
+                .map(|(module, level)| (module.clone(), (*level, is_default))),
+        );
+    }
+    let mut merged: Vec<_> = merged
+        .into_iter()
+        .map(|(module, (level, is_default))| ModuleLevel {
+            module,
+            level,
+            is_default,
+        })
+        .collect();
+    merged.sort_by_key(|module_level| std::cmp::Reverse(module_level.module.len()));
+    merged
+}
+
+fn level_for(levels: &[ModuleLevel], target: &str, global_level: LevelFilter) -> LevelFilter {
+    let module_level = levels.iter().find(|module_level| {
+        target
+            .strip_prefix(module_level.module.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    });
+    match module_level {
+        Some(module_level) if module_level.is_default => module_level.level.min(global_level),
+        Some(module_level) => module_level.level,
+        None => global_level,
+    }
+}
+
+pub(super) fn file_level_for(target: &str) -> LevelFilter {
+    level_for(&STATE.read().unwrap().file, target, super::get_log_file_log_level())
+}
+
+pub(super) fn console_level_for(target: &str) -> LevelFilter {
+    level_for(&STATE.read().unwrap().console, target, super::get_console_log_level())
+}
+
+pub(super) fn set_default_module_log_levels(
+    modules: &HashMap<&'static str, LevelFilter>,
+    console_modules: &HashMap<&'static str, LevelFilter>,
+) {
+    let mut state = STATE.write().unwrap();
+    state.defaults = ModuleLogLevels {
+        modules: modules
+            .iter()
+            .map(|(module, level)| (module.to_string(), *level))
+            .collect(),
+        console_modules: console_modules
+            .iter()
+            .map(|(module, level)| (module.to_string(), *level))
+            .collect(),
+    };
+    state.update();
+}
+
+// Change the level of a module for the log file and the console at runtime, None removes the override:
+pub fn set_module_log_level(module: &str, level: Option<LevelFilter>) {
+    let mut state = STATE.write().unwrap();
+    ModuleLogLevels::set(&mut state.configured.modules, module, level);
+    state.update();
+}
+
+pub fn set_console_module_log_level(module: &str, level: Option<LevelFilter>) {
+    let mut state = STATE.write().unwrap();
+    ModuleLogLevels::set(&mut state.configured.console_modules, module, level);
+    state.update();
+}
+
+// The levels set at runtime or loaded from the config file:
+pub fn module_log_levels() -> ModuleLogLevels {
+    STATE.read().unwrap().configured.clone()
+}
+
+pub fn set_module_log_levels(levels: ModuleLogLevels) {
+    let mut state = STATE.write().unwrap();
+    state.configured = levels;
+    state.update();
+}
+
+pub fn log_levels_config_file() -> PathBuf {
+    settings_file_path::<ModuleLogLevels>()
+}
+
+// Load the configured levels from the settings, a missing settings file results in no configured levels:
+pub fn load_module_log_levels() {
+    set_module_log_levels(settings::<ModuleLogLevels>().get());
+}
+
+pub fn save_module_log_levels() -> Result<()> {
+    settings::<ModuleLogLevels>().set(module_log_levels())
+}
+
+// Parse a list in RUST_LOG syntax like "info,mxl_base=debug,gst", a module without a level enables all levels:
+pub fn parse_log_spec(spec: &str) -> Result<(Option<LevelFilter>, BTreeMap<String, LevelFilter>)> {
+    let mut default_level = None;
+    let mut modules = BTreeMap::new();
+    // Regular expression filters after a '/' are not supported:
+    let spec = spec.split('/').next().unwrap_or_default();
+    for entry in spec.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        match entry.split_once('=') {
+            Some((module, level)) => {
+                let level = LevelFilter::from_str(level.trim())
+                    .with_context(|| format!("Invalid level in log entry '{entry}'"))?;
+                if module.trim().is_empty() {
+                    bail!("Missing module in log entry '{entry}'");
+                }
+                modules.insert(module.trim().to_string(), level);
+            }
+            None => match LevelFilter::from_str(entry) {
+                Ok(level) => default_level = Some(level),
+                Err(_) => _ = modules.insert(entry.to_string(), LevelFilter::Trace),
+            },
+        }
+    }
+    Ok((default_level, modules))
+}
+
+// Apply the levels of the environment variable, they are not saved with the configured levels:
+pub(super) fn apply_environment() -> Result<()> {
+    let Ok(spec) = std::env::var(ENV_NAME_LOG) else {
+        return Ok(());
+    };
+    let (default_level, modules) =
+        parse_log_spec(&spec).with_context(|| format!("Invalid {ENV_NAME_LOG} environment variable"))?;
+    if let Some(level) = default_level {
+        super::set_log_file_log_level(level);
+        super::set_console_log_level(level);
+    }
+    let mut state = STATE.write().unwrap();
+    state.environment.modules = modules;
+    state.update();
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+const DEFAULT_MAX_SIZE: u64 = 10 * 1024 * 1024;
+const DEFAULT_KEEP: usize = 5;
+const COMPRESSED_FILE_EXTENSION: &str = "gz";
+
+#[derive(Debug, Clone)]
+pub struct LogRotation {
+    pub max_size: Option<u64>,
+    pub max_age: Option<Duration>,
+    pub keep: usize,
+    #[cfg(feature = "gzip")]
+    pub compress: bool,
+}
+
+impl Default for LogRotation {
+    fn default() -> Self {
+        Self {
+            max_size: Some(DEFAULT_MAX_SIZE),
+            max_age: None,
+            keep: DEFAULT_KEEP,
+            #[cfg(feature = "gzip")]
+            compress: false,
+        }
+    }
+}
+
+// Log file that is renamed to <name>.1 ... <name>.<keep> when it gets too large or too old:
+pub struct RotatingLogFile {
+    path: PathBuf,
+    rotation: LogRotation,
+    writer: BufWriter<File>,
+    size: u64,
+    created: SystemTime,
+    // Compressing a large file takes a while, so it is done in the background while logging continues:
+    #[cfg(feature = "gzip")]
+    compression: Option<std::thread::JoinHandle<()>>,
+}
+
+impl RotatingLogFile {
+    pub fn open(path: &Path, rotation: LogRotation) -> std::io::Result<Self> {
+        let (writer, size, created) = open_log_file(path)?;
+        let mut log_file = Self {
+            path: path.to_path_buf(),
+            rotation,
+            writer,
+            size,
+            created,
+            #[cfg(feature = "gzip")]
+            compression: None,
+        };
+        // A log file of a previous run is rotated before it is continued:
+        if log_file.needs_rotation() {
+            log_file.rotate()?;
+        }
+        Ok(log_file)
+    }
+
+    fn needs_rotation(&self) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        let too_large = self.rotation.max_size.is_some_and(|max_size| self.size >= max_size);
+        let too_old = self.rotation.max_age.is_some_and(|max_age| {
+            SystemTime::now()
+                .duration_since(self.created)
+                .is_ok_and(|age| age >= max_age)
+        });
+        too_large || too_old
+    }
+
+    fn rotated_path(&self, index: usize, compressed: bool) -> PathBuf {
+        let mut path = self.path.as_os_str().to_owned();
+        path.push(format!(".{index}"));
+        if compressed {
+            path.push(format!(".{COMPRESSED_FILE_EXTENSION}"));
+        }
+        PathBuf::from(path)
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.writer.flush()?;
+        // The rotated files are renamed below, so the previous compression has to be finished:
+        #[cfg(feature = "gzip")]
+        self.wait_for_compression();
+
+        for compressed in [false, true] {
+            remove_if_exists(&self.rotated_path(self.rotation.keep, compressed))?;
+            for index in (1..self.rotation.keep).rev() {
+                let from = self.rotated_path(index, compressed);
+                if from.exists() {
+                    std::fs::rename(&from, self.rotated_path(index + 1, compressed))?;
+                }
+            }
+        }
+
+        if self.rotation.keep > 0 {
+            let rotated_path = self.rotated_path(1, false);
+            std::fs::rename(&self.path, &rotated_path)?;
+            #[cfg(feature = "gzip")]
+            if self.rotation.compress {
+                let compressed_path = self.rotated_path(1, true);
+                self.compression = Some(std::thread::spawn(move || {
+                    if let Err(error) = compress(&rotated_path, &compressed_path) {
+                        // The logger cannot be used to report its own errors:
+                        eprintln!(
+                            "Cannot compress log file '{}': {error:?}",
+                            rotated_path.to_string_lossy()
+                        );
+                    }
+                }));
+            }
+        } else {
+            std::fs::remove_file(&self.path)?;
+        }
+
+        (self.writer, self.size, self.created) = open_log_file(&self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl RotatingLogFile {
+    fn wait_for_compression(&mut self) {
+        if let Some(compression) = self.compression.take() {
+            _ = compression.join();
+        }
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl Drop for RotatingLogFile {
+    fn drop(&mut self) {
+        self.wait_for_compression();
+    }
+}
+
+impl Write for RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    // A log record is complete when it is flushed, so it is the place to rotate without splitting records:
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()?;
+        if self.needs_rotation()
+            && let Err(error) = self.rotate()
+        {
+            // The logger cannot be used to report its own errors:
+            eprintln!("Cannot rotate log file '{}': {error:?}", self.path.to_string_lossy());
+        }
+        Ok(())
+    }
+}
+
+fn open_log_file(path: &Path) -> std::io::Result<(BufWriter<File>, u64, SystemTime)> {
+    let file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    let metadata = file.metadata()?;
+    // Not all file systems provide the creation time:
+    let created = metadata
+        .created()
+        .or_else(|_| metadata.modified())
+        .unwrap_or_else(|_| SystemTime::now());
+    Ok((BufWriter::new(file), metadata.len(), created))
+}
+
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "gzip")]
+fn compress(source: &Path, destination: &Path) -> std::io::Result<()> {
+    let mut input = File::open(source)?;
+    let mut encoder = flate2::write::GzEncoder::new(File::create(destination)?, flate2::Compression::default());
+    std::io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?;
+    std::fs::remove_file(source)
+}
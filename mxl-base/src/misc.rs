@@ -7,7 +7,26 @@ pub fn init(
     binary_name: &'static str,
     version: &'static str,
 ) {
-    crate::about::about_init(qualifier, organization, app_name, binary_name, version);
+    init_with_build_info(
+        qualifier,
+        organization,
+        app_name,
+        binary_name,
+        version,
+        crate::build_info::BuildInfo::default(),
+    );
+}
+
+// Like init(), with the metadata of the build, e.g. init_with_build_info(..., mxl_base::build_info!()):
+pub fn init_with_build_info(
+    qualifier: &'static str,
+    organization: &'static str,
+    app_name: &'static str,
+    binary_name: &'static str,
+    version: &'static str,
+    build_info: crate::build_info::BuildInfo,
+) {
+    crate::about::about_init(qualifier, organization, app_name, binary_name, version, build_info);
     crate::localization::init();
 }
 
@@ -0,0 +1,254 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::{
+    borrow::Cow,
+    net::{Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+const HOME_DIR_PATTERN: &str = r#"(?i)(/home/|/Users/|[A-Z]:\\Users\\)[^/\\\s'"]+"#;
+const HOME_DIR_REPLACEMENT: &str = "${1}<user>";
+const HOST_NAME_REPLACEMENT: &str = "<host>";
+const EMAIL_PATTERN: &str = r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}";
+const EMAIL_REPLACEMENT: &str = "<email>";
+// Candidates are validated by the standard library, so that e.g. Rust paths like "add::face" are not redacted:
+const IPV4_ADDRESS_PATTERN: &str = r"\b[0-9]{1,3}(?:\.[0-9]{1,3}){3}\b";
+const IPV6_ADDRESS_PATTERN: &str = r"[0-9A-Fa-f]{0,4}:[0-9A-Fa-f:]*:[0-9A-Fa-f:.]*";
+const IP_ADDRESS_REPLACEMENT: &str = "<ip>";
+// Words before version numbers, which look like IPv4 addresses, e.g. "version 1.24.12.0":
+const VERSION_WORDS: [&str; 3] = ["version", "ver", "release"];
+
+enum Replacement {
+    Text(String),
+    Ipv4Address,
+    Ipv6Address,
+}
+
+struct Rule {
+    regex: Regex,
+    replacement: Replacement,
+}
+
+// Replaces personal data like user names, host names, e-mail and IP addresses in logs and reports:
+#[derive(Clone)]
+pub struct Redactor {
+    rules: std::sync::Arc<Vec<Rule>>,
+}
+
+impl std::fmt::Debug for Redactor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Redactor")
+            .field(
+                "rules",
+                &self.rules.iter().map(|rule| rule.regex.as_str()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Default for Redactor {
+    // All built-in rules:
+    fn default() -> Self {
+        RedactorBuilder::new().build()
+    }
+}
+
+impl Redactor {
+    pub fn builder() -> RedactorBuilder {
+        RedactorBuilder::new()
+    }
+
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for rule in self.rules.iter() {
+            let replaced = match &rule.replacement {
+                Replacement::Text(replacement) => rule.regex.replace_all(&text, replacement.as_str()),
+                Replacement::Ipv4Address => replace_addresses(&rule.regex, &text, ipv4_address_len),
+                Replacement::Ipv6Address => replace_addresses(&rule.regex, &text, ipv6_address_len),
+            };
+            if let Cow::Owned(replaced) = replaced {
+                text = Cow::Owned(replaced);
+            }
+        }
+        text
+    }
+}
+
+pub struct RedactorBuilder {
+    home_dirs: bool,
+    host_names: Vec<String>,
+    emails: bool,
+    ip_addresses: bool,
+    rules: Vec<Rule>,
+}
+
+impl RedactorBuilder {
+    // Starts with all built-in rules, they can be disabled individually:
+    pub fn new() -> Self {
+        Self {
+            home_dirs: true,
+            host_names: local_host_name().into_iter().collect(),
+            emails: true,
+            ip_addresses: true,
+            rules: Vec::new(),
+        }
+    }
+
+    // User names in home directories like "/home/<user>" or "C:\Users\<user>":
+    pub fn home_dirs(mut self, enabled: bool) -> Self {
+        self.home_dirs = enabled;
+        self
+    }
+
+    // The name of the local host, more names can be added with host_name():
+    pub fn local_host_name(mut self, enabled: bool) -> Self {
+        let local_host_name = local_host_name();
+        self.host_names.retain(|name| Some(name) != local_host_name.as_ref());
+        if enabled {
+            self.host_names.extend(local_host_name);
+        }
+        self
+    }
+
+    pub fn host_name(mut self, name: &str) -> Self {
+        if !name.is_empty() {
+            self.host_names.push(name.to_string());
+        }
+        self
+    }
+
+    pub fn emails(mut self, enabled: bool) -> Self {
+        self.emails = enabled;
+        self
+    }
+
+    pub fn ip_addresses(mut self, enabled: bool) -> Self {
+        self.ip_addresses = enabled;
+        self
+    }
+
+    // Additional rule, the replacement can refer to capture groups like "${1}":
+    pub fn rule(mut self, pattern: &str, replacement: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).with_context(|| format!("Invalid redaction pattern '{pattern}'"))?;
+        self.rules.push(Rule {
+            regex,
+            replacement: Replacement::Text(replacement.to_string()),
+        });
+        Ok(self)
+    }
+
+    pub fn build(self) -> Redactor {
+        // Configured rules first, so that they can match the original text:
+        let mut rules = self.rules;
+        let mut add = |pattern: &str, replacement: Replacement| {
+            rules.push(Rule {
+                regex: Regex::new(pattern).expect("Invalid built-in redaction pattern"),
+                replacement,
+            })
+        };
+        if self.home_dirs {
+            add(HOME_DIR_PATTERN, Replacement::Text(HOME_DIR_REPLACEMENT.to_string()));
+        }
+        // E-mail addresses before host names, because they contain host names:
+        if self.emails {
+            add(EMAIL_PATTERN, Replacement::Text(EMAIL_REPLACEMENT.to_string()));
+        }
+        for host_name in &self.host_names {
+            add(
+                &format!(r"(?i)\b{}\b", regex::escape(host_name)),
+                Replacement::Text(HOST_NAME_REPLACEMENT.to_string()),
+            );
+        }
+        if self.ip_addresses {
+            // IPv6 addresses first, because they can contain an IPv4 address:
+            add(IPV6_ADDRESS_PATTERN, Replacement::Ipv6Address);
+            add(IPV4_ADDRESS_PATTERN, Replacement::Ipv4Address);
+        }
+        Redactor {
+            rules: std::sync::Arc::new(rules),
+        }
+    }
+}
+
+impl Default for RedactorBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Replace the candidates, for which the validation returns the length of the address. The text is only copied
+// if an address is found, because most log messages contain none:
+fn replace_addresses<'a>(
+    regex: &Regex,
+    text: &'a str,
+    address_len: impl Fn(&str, regex::Match) -> Option<usize>,
+) -> Cow<'a, str> {
+    let mut replaced = None::<String>;
+    let mut last = 0;
+    for candidate in regex.find_iter(text) {
+        let Some(len) = address_len(text, candidate) else {
+            continue;
+        };
+        let replaced = replaced.get_or_insert_with(|| String::with_capacity(text.len()));
+        replaced.push_str(&text[last..candidate.start()]);
+        replaced.push_str(IP_ADDRESS_REPLACEMENT);
+        last = candidate.start() + len;
+    }
+    match replaced {
+        Some(mut replaced) => {
+            replaced.push_str(&text[last..]);
+            Cow::Owned(replaced)
+        }
+        None => Cow::Borrowed(text),
+    }
+}
+
+// Addresses must not be part of a longer word or number like "v1.2.3.4" or "1.2.3.4.5":
+fn is_separate(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let mut after = text[end..].chars();
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    !before.is_some_and(|c| is_word(c) || c == '.')
+        && !after
+            .next()
+            .is_some_and(|c| is_word(c) || (c == '.' && after.next().is_some_and(|c| c.is_ascii_digit())))
+}
+
+fn follows_version_word(text: &str) -> bool {
+    let word = text
+        .trim_end_matches([' ', ':', '='])
+        .rsplit(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default();
+    VERSION_WORDS.iter().any(|version| word.eq_ignore_ascii_case(version))
+}
+
+// Four part version numbers look like IPv4 addresses, so that they are recognized by the word before them.
+// Addresses ending with 0 are networks or versions like "1.24.12.0" and not the address of a host:
+fn ipv4_address_len(text: &str, candidate: regex::Match) -> Option<usize> {
+    let address = candidate.as_str();
+    (is_separate(text, candidate.start(), candidate.end())
+        && !follows_version_word(&text[..candidate.start()])
+        && !address.ends_with(".0")
+        && Ipv4Addr::from_str(address).is_ok())
+    .then_some(address.len())
+}
+
+// Punctuation at the end of a sentence is not part of the address. IPv6 addresses need a digit,
+// so that e.g. "add::face" in a Rust path is kept:
+fn ipv6_address_len(text: &str, candidate: regex::Match) -> Option<usize> {
+    let address = candidate.as_str().trim_end_matches(['.', ':']);
+    (is_separate(text, candidate.start(), candidate.start() + address.len())
+        && address.contains(|c: char| c.is_ascii_digit())
+        && Ipv6Addr::from_str(address).is_ok())
+    .then_some(address.len())
+}
+
+fn local_host_name() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .into_iter()
+        .find_map(|name| std::env::var(name).ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
@@ -0,0 +1,251 @@
+use anyhow::{Context, Result, bail};
+use log::*;
+use serde::{Serialize, de::DeserializeOwned};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, LazyLock, Mutex, RwLock,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+};
+
+use crate::misc::project_dirs;
+
+const SETTINGS_FILE_EXTENSION: &str = "toml";
+// Stored next to the settings, so that the name must not be used by a settings field:
+const VERSION_KEY: &str = "settings_version";
+
+pub trait Settings: Serialize + DeserializeOwned + Default + Clone + Send + Sync + 'static {
+    // Name of the settings file in the config directory without extension:
+    const NAME: &'static str;
+    // Increase on incompatible changes and convert older files in migrate():
+    const VERSION: u32;
+
+    // Convert the settings of a file from the given version to the next version:
+    fn migrate(from_version: u32, table: &mut toml::Table) -> Result<()> {
+        _ = (from_version, table);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(u64);
+
+type Listener<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
+pub struct SettingsStore<T: Settings> {
+    path: PathBuf,
+    value: RwLock<T>,
+    // Set if the file could not be loaded, e.g. because it was written by a newer version:
+    read_only: AtomicBool,
+    next_listener_id: AtomicU64,
+    listeners: Mutex<Vec<(ListenerId, Listener<T>)>>,
+}
+
+impl<T: Settings> std::fmt::Debug for SettingsStore<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SettingsStore").field("path", &self.path).finish()
+    }
+}
+
+impl<T: Settings> SettingsStore<T> {
+    // Load the settings from the file, a missing file results in the default settings:
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let value = load(&path)?;
+        Ok(Self::with_value(path, value))
+    }
+
+    // Like open(), but a file that cannot be loaded results in the default settings. The store is read-only then,
+    // so that the file is not overwritten:
+    pub fn open_or_default(path: PathBuf) -> Self {
+        Self::open(path.clone()).unwrap_or_else(|error| {
+            warn!("Use default settings without saving them: {error:?}");
+            let store = Self::with_value(path, T::default());
+            store.read_only.store(true, Ordering::Relaxed);
+            store
+        })
+    }
+
+    fn with_value(path: PathBuf, value: T) -> Self {
+        Self {
+            path,
+            value: RwLock::new(value),
+            read_only: AtomicBool::new(false),
+            next_listener_id: AtomicU64::new(0),
+            listeners: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self) -> T {
+        self.value.read().unwrap().clone()
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    // Change and save the settings, the listeners are only notified if the settings were saved.
+    // The settings are locked during the change, so that the change must not access the store:
+    pub fn update(&self, change: impl FnOnce(&mut T)) -> Result<()> {
+        let value = {
+            let mut current = self.value.write().unwrap();
+            let mut value = current.clone();
+            change(&mut value);
+            self.save(&value)?;
+            *current = value.clone();
+            value
+        };
+        self.notify(&value);
+        Ok(())
+    }
+
+    pub fn set(&self, value: T) -> Result<()> {
+        {
+            let mut current = self.value.write().unwrap();
+            self.save(&value)?;
+            *current = value.clone();
+        }
+        self.notify(&value);
+        Ok(())
+    }
+
+    // Load the settings again, e.g. after the file was changed by another process:
+    pub fn reload(&self) -> Result<()> {
+        let value: T = load(&self.path)?;
+        *self.value.write().unwrap() = value.clone();
+        self.read_only.store(false, Ordering::Relaxed);
+        self.notify(&value);
+        Ok(())
+    }
+
+    fn save(&self, value: &T) -> Result<()> {
+        if self.is_read_only() {
+            bail!(
+                "Settings {:?} are not saved, because the file could not be loaded",
+                self.path
+            );
+        }
+        save(&self.path, value)
+    }
+
+    // The listener is called with the new settings after every change, also from other components:
+    pub fn subscribe(&self, listener: impl Fn(&T) + Send + Sync + 'static) -> ListenerId {
+        let id = ListenerId(self.next_listener_id.fetch_add(1, Ordering::Relaxed));
+        self.listeners.lock().unwrap().push((id, Arc::new(listener)));
+        id
+    }
+
+    pub fn unsubscribe(&self, id: ListenerId) {
+        self.listeners
+            .lock()
+            .unwrap()
+            .retain(|(listener_id, _)| *listener_id != id);
+    }
+
+    fn notify(&self, value: &T) {
+        // Listeners are called without lock, so that they can subscribe or unsubscribe:
+        let listeners: Vec<_> = self
+            .listeners
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect();
+        for listener in listeners {
+            listener(value);
+        }
+    }
+}
+
+pub fn settings_file_path<T: Settings>() -> PathBuf {
+    project_dirs()
+        .config_dir()
+        .join(format!("{}.{SETTINGS_FILE_EXTENSION}", T::NAME))
+}
+
+// Shared store of the settings type, which is loaded from the config directory on first use:
+pub fn settings<T: Settings>() -> Arc<SettingsStore<T>> {
+    static STORES: LazyLock<Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>> =
+        LazyLock::new(|| Mutex::new(HashMap::new()));
+
+    let mut stores = STORES.lock().unwrap();
+    let store = stores
+        .entry(TypeId::of::<T>())
+        .or_insert_with(|| Arc::new(SettingsStore::<T>::open_or_default(settings_file_path::<T>())));
+    store
+        .clone()
+        .downcast::<SettingsStore<T>>()
+        .expect("Settings store has the wrong type")
+}
+
+fn load<T: Settings>(path: &Path) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let content = std::fs::read_to_string(path).with_context(|| format!("Cannot read settings {path:?}"))?;
+    let mut table: toml::Table = content
+        .parse()
+        .with_context(|| format!("Cannot parse settings {path:?}"))?;
+
+    // Files without version were not written by the settings store and are expected to be up to date:
+    let file_version = match table.remove(VERSION_KEY) {
+        Some(version) => version
+            .as_integer()
+            .and_then(|version| u32::try_from(version).ok())
+            .with_context(|| format!("Invalid {VERSION_KEY} in settings {path:?}"))?,
+        None => T::VERSION,
+    };
+    if file_version > T::VERSION {
+        bail!(
+            "Settings {path:?} have version {file_version}, but only version {} is supported",
+            T::VERSION
+        );
+    }
+    for version in file_version..T::VERSION {
+        T::migrate(version, &mut table)
+            .with_context(|| format!("Cannot migrate settings {path:?} from version {version}"))?;
+    }
+
+    let value = toml::Value::Table(table)
+        .try_into()
+        .with_context(|| format!("Cannot parse settings {path:?}"))?;
+    if file_version < T::VERSION {
+        info!(
+            "Migrated settings {path:?} from version {file_version} to {}",
+            T::VERSION
+        );
+        save(path, &value)?;
+    }
+    Ok(value)
+}
+
+// Write into a temporary file and rename it, so that the settings file is never written partially:
+fn save<T: Settings>(path: &Path, value: &T) -> Result<()> {
+    let mut table = match toml::Value::try_from(value).context("Cannot serialize settings")? {
+        toml::Value::Table(table) => table,
+        _ => bail!("Settings must be serialized as table"),
+    };
+    table.insert(VERSION_KEY.to_string(), toml::Value::Integer(T::VERSION.into()));
+    let content = toml::to_string_pretty(&table).context("Cannot serialize settings")?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("Cannot create directory {parent:?}"))?;
+    }
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+    let mut file =
+        std::fs::File::create(&temp_path).with_context(|| format!("Cannot create settings {temp_path:?}"))?;
+    file.write_all(content.as_bytes())
+        .and_then(|_| file.sync_all())
+        .with_context(|| format!("Cannot write settings {temp_path:?}"))?;
+    std::fs::rename(&temp_path, path).with_context(|| format!("Cannot replace settings {path:?}"))?;
+    Ok(())
+}
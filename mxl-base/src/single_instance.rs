@@ -0,0 +1,186 @@
+use anyhow::{Context, Result};
+use fs4::fs_std::FileExt;
+use log::*;
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use crate::misc::project_dirs;
+
+const LOCK_FILE_EXTENSION: &str = "lock";
+#[cfg(unix)]
+const SOCKET_FILE_EXTENSION: &str = "sock";
+// The primary instance may just have started and not yet listen on the socket:
+#[cfg(unix)]
+const CONNECT_ATTEMPTS: usize = 20;
+#[cfg(unix)]
+const CONNECT_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+// A secondary instance that does not finish sending must not block the arguments of others:
+#[cfg(unix)]
+const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+pub enum Instance {
+    // No other instance is running, this instance has to handle the forwarded arguments:
+    Primary(PrimaryInstance),
+    // Another instance is running, the arguments should be forwarded to it before this instance exits:
+    Secondary(SecondaryInstance),
+}
+
+// Determine whether another instance of the application is running, based on a lock file in the runtime directory:
+pub fn acquire() -> Result<Instance> {
+    acquire_in(&instance_dir())
+}
+
+// Like acquire(), but with the lock file and the socket in the given directory:
+pub fn acquire_in(dir: &Path) -> Result<Instance> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Cannot create directory {dir:?}"))?;
+    let lock_file_path = file_path(dir, LOCK_FILE_EXTENSION);
+    let lock_file = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_file_path)
+        .with_context(|| format!("Cannot open lock file {lock_file_path:?}"))?;
+    if lock_file
+        .try_lock_exclusive()
+        .with_context(|| format!("Cannot lock file {lock_file_path:?}"))?
+    {
+        debug!("Acquired single instance lock {lock_file_path:?}");
+        Ok(Instance::Primary(PrimaryInstance {
+            dir: dir.to_path_buf(),
+            _lock_file: lock_file,
+            #[cfg(unix)]
+            socket_path: None,
+        }))
+    } else {
+        debug!("Another instance holds the lock {lock_file_path:?}");
+        Ok(Instance::Secondary(SecondaryInstance { dir: dir.to_path_buf() }))
+    }
+}
+
+pub struct PrimaryInstance {
+    dir: PathBuf,
+    // The lock is released when the file is closed:
+    _lock_file: File,
+    #[cfg(unix)]
+    socket_path: Option<PathBuf>,
+}
+
+impl PrimaryInstance {
+    // Receive the arguments of secondary instances in a background thread, e.g. to add files to the playlist:
+    #[cfg(unix)]
+    pub fn listen(&mut self, handler: impl Fn(Vec<String>) + Send + 'static) -> Result<()> {
+        use std::{io::Read, os::unix::net::UnixListener};
+
+        let socket_path = file_path(&self.dir, SOCKET_FILE_EXTENSION);
+        // A socket file left by a crashed instance is not in use, because the lock is held:
+        match std::fs::remove_file(&socket_path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                return Err(error).with_context(|| format!("Cannot remove socket {socket_path:?}"));
+            }
+            _ => {}
+        }
+        let listener =
+            UnixListener::bind(&socket_path).with_context(|| format!("Cannot listen on socket {socket_path:?}"))?;
+        self.socket_path = Some(socket_path);
+
+        std::thread::Builder::new()
+            .name("single-instance".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    let mut content = String::new();
+                    let arguments = stream
+                        .and_then(|mut stream| {
+                            stream.set_read_timeout(Some(READ_TIMEOUT))?;
+                            stream.read_to_string(&mut content)
+                        })
+                        .map_err(anyhow::Error::from)
+                        .and_then(|_| Ok(serde_json::from_str::<Vec<String>>(&content)?));
+                    match arguments {
+                        Ok(arguments) => {
+                            debug!("Received arguments from another instance: {arguments:?}");
+                            handler(arguments)
+                        }
+                        Err(error) => warn!("Cannot receive arguments from another instance: {error:?}"),
+                    }
+                }
+            })
+            .context("Cannot start single instance thread")?;
+        Ok(())
+    }
+
+    // Forwarding is only supported on Unix, the lock still prevents a second instance:
+    #[cfg(not(unix))]
+    pub fn listen(&mut self, handler: impl Fn(Vec<String>) + Send + 'static) -> Result<()> {
+        _ = (&self.dir, handler);
+        Ok(())
+    }
+}
+
+impl Drop for PrimaryInstance {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(socket_path) = self.socket_path.take() {
+            _ = std::fs::remove_file(socket_path);
+        }
+    }
+}
+
+pub struct SecondaryInstance {
+    dir: PathBuf,
+}
+
+impl SecondaryInstance {
+    // Existing relative paths are made absolute, because the primary instance may run in another directory:
+    #[cfg(unix)]
+    pub fn forward(&self, arguments: &[String]) -> Result<()> {
+        use std::{io::Write, os::unix::net::UnixStream};
+
+        let arguments: Vec<String> = arguments
+            .iter()
+            .map(|argument| match std::path::Path::new(argument) {
+                path if path.is_relative() && path.exists() => std::path::absolute(path)
+                    .map(|path| path.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| argument.clone()),
+                _ => argument.clone(),
+            })
+            .collect();
+        let socket_path = file_path(&self.dir, SOCKET_FILE_EXTENSION);
+        let mut attempt = 1;
+        let mut stream = loop {
+            match UnixStream::connect(&socket_path) {
+                Err(_) if attempt < CONNECT_ATTEMPTS => {
+                    attempt += 1;
+                    std::thread::sleep(CONNECT_RETRY_INTERVAL);
+                }
+                result => {
+                    break result.with_context(|| format!("Cannot connect to the running instance {socket_path:?}"))?;
+                }
+            }
+        };
+        stream
+            .write_all(serde_json::to_string(&arguments)?.as_bytes())
+            .with_context(|| format!("Cannot forward arguments to the running instance {socket_path:?}"))?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    pub fn forward(&self, arguments: &[String]) -> Result<()> {
+        _ = (&self.dir, arguments);
+        anyhow::bail!("Forwarding arguments to the running instance is not supported on this platform")
+    }
+}
+
+// The runtime directory is removed on logout, the cache directory is used if it does not exist:
+fn instance_dir() -> PathBuf {
+    let project_dirs = project_dirs();
+    project_dirs
+        .runtime_dir()
+        .unwrap_or_else(|| project_dirs.cache_dir())
+        .to_path_buf()
+}
+
+fn file_path(dir: &Path, extension: &str) -> PathBuf {
+    dir.join(format!("{}.{extension}", crate::about::about().binary_name))
+}
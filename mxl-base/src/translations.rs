@@ -0,0 +1,140 @@
+use anyhow::{Context, Result, bail};
+use fluent_syntax::ast::{Entry, Expression, InlineExpression, Pattern, PatternElement};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+const I18N_CONFIG_FILE: &str = "i18n.toml";
+
+// Message ids like "dialog" or "dialog.attribute" with the names of their variables:
+type Messages = BTreeMap<String, BTreeSet<String>>;
+
+// Verify the translations of a crate in its tests, with the directory of its Cargo.toml. Every locale must have
+// the same messages and variables as the fallback language, the usages are checked by fl!() at compile time:
+pub fn check_translations(crate_dir: &Path) -> Result<()> {
+    let config_path = crate_dir.join(I18N_CONFIG_FILE);
+    let config: toml::Table = std::fs::read_to_string(&config_path)
+        .with_context(|| format!("Cannot read {config_path:?}"))?
+        .parse()
+        .with_context(|| format!("Cannot parse {config_path:?}"))?;
+    let fallback_language = config
+        .get("fallback_language")
+        .and_then(|value| value.as_str())
+        .with_context(|| format!("Missing fallback_language in {config_path:?}"))?;
+    let assets_dir = config
+        .get("fluent")
+        .and_then(|fluent| fluent.get("assets_dir"))
+        .and_then(|value| value.as_str())
+        .unwrap_or("i18n");
+
+    let mut locales = BTreeMap::new();
+    let assets_dir = crate_dir.join(assets_dir);
+    for entry in std::fs::read_dir(&assets_dir).with_context(|| format!("Cannot read directory {assets_dir:?}"))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            let locale = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            locales.insert(locale, read_locale(&path)?);
+        }
+    }
+    let fallback = locales
+        .get(fallback_language)
+        .with_context(|| format!("Missing fallback language '{fallback_language}' in {assets_dir:?}"))?;
+
+    let mut errors = Vec::new();
+    for (locale, messages) in &locales {
+        if locale == fallback_language {
+            continue;
+        }
+        for (id, variables) in fallback {
+            match messages.get(id) {
+                None => errors.push(format!("{locale}: missing message '{id}' of '{fallback_language}'")),
+                Some(locale_variables) if locale_variables != variables => errors.push(format!(
+                    "{locale}: message '{id}' has variables {locale_variables:?} instead of {variables:?}"
+                )),
+                _ => {}
+            }
+        }
+        for id in messages.keys().filter(|id| !fallback.contains_key(*id)) {
+            errors.push(format!(
+                "{locale}: message '{id}' does not exist in '{fallback_language}'"
+            ));
+        }
+    }
+    if !errors.is_empty() {
+        bail!("Invalid translations:\n{}", errors.join("\n"));
+    }
+    Ok(())
+}
+
+fn read_locale(dir: &Path) -> Result<Messages> {
+    let mut messages = Messages::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("Cannot read directory {dir:?}"))? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "ftl") {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path).with_context(|| format!("Cannot read {path:?}"))?;
+        let resource = match fluent_syntax::parser::parse(content.as_str()) {
+            Ok(resource) => resource,
+            Err((_, errors)) => bail!("Cannot parse {path:?}: {errors:?}"),
+        };
+        for entry in resource.body {
+            // Terms are prefixed with "-" like in the references:
+            let (id, value, attributes) = match entry {
+                Entry::Message(message) => (message.id.name.to_string(), message.value, message.attributes),
+                Entry::Term(term) => (format!("-{}", term.id.name), Some(term.value), term.attributes),
+                _ => continue,
+            };
+            if let Some(value) = value {
+                messages.insert(id.clone(), pattern_variables(&value));
+            }
+            for attribute in attributes {
+                messages.insert(
+                    format!("{id}.{}", attribute.id.name),
+                    pattern_variables(&attribute.value),
+                );
+            }
+        }
+    }
+    Ok(messages)
+}
+
+fn pattern_variables(pattern: &Pattern<&str>) -> BTreeSet<String> {
+    fn add_inline(expression: &InlineExpression<&str>, variables: &mut BTreeSet<String>) {
+        match expression {
+            InlineExpression::VariableReference { id } => {
+                variables.insert(id.name.to_string());
+            }
+            InlineExpression::FunctionReference { arguments, .. } => {
+                for argument in &arguments.positional {
+                    add_inline(argument, variables);
+                }
+                for argument in &arguments.named {
+                    add_inline(&argument.value, variables);
+                }
+            }
+            InlineExpression::Placeable { expression } => add_expression(expression, variables),
+            _ => {}
+        }
+    }
+    fn add_expression(expression: &Expression<&str>, variables: &mut BTreeSet<String>) {
+        match expression {
+            Expression::Select { selector, variants } => {
+                add_inline(selector, variables);
+                for variant in variants {
+                    variables.extend(pattern_variables(&variant.value));
+                }
+            }
+            Expression::Inline(expression) => add_inline(expression, variables),
+        }
+    }
+
+    let mut variables = BTreeSet::new();
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            add_expression(expression, &mut variables);
+        }
+    }
+    variables
+}
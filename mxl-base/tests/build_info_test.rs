@@ -0,0 +1,49 @@
+use mxl_base::build_info::BuildInfo;
+
+fn build_info() -> BuildInfo {
+    BuildInfo {
+        git_commit: Some("0123abcd"),
+        git_dirty: Some(false),
+        profile: Some("release"),
+        target: Some("x86_64-unknown-linux-gnu"),
+        timestamp: Some("2026-10-19T10:00:00Z"),
+        features: vec!["gzip", "log_viewer"],
+        rustc_version: Some("rustc 1.90.0 (1159e78c4 2025-09-14)"),
+    }
+}
+
+#[test]
+fn values_are_missing_without_emit() {
+    // The build script of mxl-base does not call build_info::emit():
+    assert_eq!(mxl_base::build_info!(), BuildInfo::default());
+}
+
+#[test]
+fn git_version() {
+    assert_eq!(build_info().git_version().as_deref(), Some("0123abcd"));
+    let dirty = BuildInfo {
+        git_dirty: Some(true),
+        ..build_info()
+    };
+    assert_eq!(dirty.git_version().as_deref(), Some("0123abcd-dirty"));
+    // Unknown changes are not reported as dirty:
+    let unknown = BuildInfo {
+        git_dirty: None,
+        ..build_info()
+    };
+    assert_eq!(unknown.git_version().as_deref(), Some("0123abcd"));
+    assert_eq!(BuildInfo::default().git_version(), None);
+}
+
+#[test]
+fn display() {
+    assert_eq!(
+        build_info().to_string(),
+        "Commit=0123abcd Profile=release Target=x86_64-unknown-linux-gnu Timestamp=2026-10-19T10:00:00Z \
+         Features=[gzip,log_viewer] Rustc=rustc 1.90.0 (1159e78c4 2025-09-14)"
+    );
+    assert_eq!(
+        BuildInfo::default().to_string(),
+        "Commit=unknown Profile=unknown Target=unknown Timestamp=unknown Features=[] Rustc=unknown"
+    );
+}
@@ -0,0 +1,71 @@
+use i18n_embed::LanguageLoader;
+use i18n_embed::fluent::{FluentLanguageLoader, fluent_language_loader};
+use mxl_base::language;
+use rust_embed::RustEmbed;
+use std::sync::{Mutex, OnceLock};
+
+#[derive(RustEmbed)]
+#[folder = "i18n/"]
+struct Localizations;
+
+static LANGUAGE_LOADER: OnceLock<FluentLanguageLoader> = OnceLock::new();
+
+// Registered like the language loader of a crate, but without mxl_base::init(), so that no setting is read:
+fn language_loader() -> &'static FluentLanguageLoader {
+    static INIT: std::sync::Once = std::sync::Once::new();
+    INIT.call_once(|| {
+        let loader = fluent_language_loader!();
+        loader
+            .load_fallback_language(&Localizations)
+            .expect("Error while loading fallback language");
+        _ = LANGUAGE_LOADER.set(loader);
+        language::register_language_loader(LANGUAGE_LOADER.get().unwrap(), &Localizations);
+    });
+    LANGUAGE_LOADER.get().unwrap()
+}
+
+fn log_written_to() -> String {
+    i18n_embed_fl::fl!(language_loader(), "log-written-to", file_name = "app.log")
+}
+
+#[test]
+fn switch_language_at_runtime() {
+    let switched = std::sync::Arc::new(Mutex::new(Vec::new()));
+    let id = language::subscribe({
+        let switched = switched.clone();
+        move |languages| switched.lock().unwrap().push(languages.to_vec())
+    });
+
+    language::set_language(Some("en")).unwrap();
+    assert!(log_written_to().starts_with("Log is written to"));
+    // The message changes without loading the language loader again:
+    language::set_language(Some("de_DE.UTF-8")).unwrap();
+    assert!(log_written_to().starts_with("Das Protokoll wird in"));
+    assert_eq!(
+        language::requested_languages(),
+        ["de-DE".parse::<language::LanguageIdentifier>().unwrap()]
+    );
+
+    language::unsubscribe(id);
+    // A subscription ends when it is dropped:
+    let subscription = language::subscription({
+        let switched = switched.clone();
+        move |languages| switched.lock().unwrap().push(languages.to_vec())
+    });
+    language::set_language(Some("fr")).unwrap();
+    drop(subscription);
+    language::set_language(Some("en")).unwrap();
+    assert!(log_written_to().starts_with("Log is written to"));
+    assert_eq!(
+        switched
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|languages| languages[0].to_string())
+            .collect::<Vec<_>>(),
+        ["en", "de-DE", "fr"]
+    );
+
+    assert!(language::available_languages().contains(&"fr".parse().unwrap()));
+    assert!(language::set_language(Some("not a language")).is_err());
+}
@@ -0,0 +1,59 @@
+use mxl_base::logging::{Builder, LogFallback, LogFormat};
+
+const BINARY_NAME: &str = "mxl-base-log-format-test";
+
+fn records(content: &str, target: &str) -> Vec<serde_json::Value> {
+    content
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .filter(|record| record["target"] == target)
+        .collect()
+}
+
+// The logger can be applied only once per process, so the JSON lines format has its own test binary:
+#[test]
+fn json_lines_log_file() {
+    mxl_base::init("com", "x-software", "MXL Base Log Format Test", BINARY_NAME, "1.0.0");
+    let dir = tempfile::tempdir().unwrap();
+    Builder::new()
+        .fallback(LogFallback::Disabled)
+        .without_console()
+        .file_format(LogFormat::JsonLines)
+        .build(dir.path())
+        .unwrap();
+
+    let line = line!() + 1;
+    log::info!(target: "format::test", "Message with \"quotes\"\nand a new line");
+    std::thread::Builder::new()
+        .name("json-writer".to_string())
+        .spawn(|| log::warn!(target: "format::test", "Message of another thread"))
+        .unwrap()
+        .join()
+        .unwrap();
+    log::logger().flush();
+
+    // Every line of the log file is a JSON object, also the ones of the builder:
+    let content = std::fs::read_to_string(dir.path().join(format!("{BINARY_NAME}.log"))).unwrap();
+    assert!(!records(&content, "mxl_base::logging").is_empty());
+    let records = records(&content, "format::test");
+    assert_eq!(records.len(), 2);
+
+    let record = &records[0];
+    assert_eq!(record["level"], "INFO");
+    assert_eq!(record["message"], "Message with \"quotes\"\nand a new line");
+    assert_eq!(record["module"], module_path!());
+    assert_eq!(record["file"], file!());
+    assert_eq!(record["line"], line);
+    assert_eq!(record["thread"].as_str(), std::thread::current().name());
+    chrono::DateTime::parse_from_rfc3339(record["timestamp"].as_str().unwrap()).unwrap();
+
+    let record = &records[1];
+    assert_eq!(record["level"], "WARN");
+    assert_eq!(record["thread"], "json-writer");
+    // The thread id is the number of the ThreadId:
+    let thread_ids: Vec<u64> = records
+        .iter()
+        .map(|record| record["thread_id"].as_str().unwrap().parse().unwrap())
+        .collect();
+    assert_ne!(thread_ids[0], thread_ids[1]);
+}
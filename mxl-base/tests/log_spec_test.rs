@@ -0,0 +1,112 @@
+use log::LevelFilter;
+use mxl_base::logging::{self, Builder, ENV_NAME_LOG, LogFallback};
+use std::{collections::BTreeMap, sync::Mutex};
+
+static RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn modules<const N: usize>(modules: [(&str, LevelFilter); N]) -> BTreeMap<String, LevelFilter> {
+    modules
+        .into_iter()
+        .map(|(module, level)| (module.to_string(), level))
+        .collect()
+}
+
+#[test]
+fn default_level() {
+    assert_eq!(
+        logging::parse_log_spec("debug").unwrap(),
+        (Some(LevelFilter::Debug), modules([]))
+    );
+    assert_eq!(
+        logging::parse_log_spec(" OFF ").unwrap(),
+        (Some(LevelFilter::Off), modules([]))
+    );
+    assert_eq!(logging::parse_log_spec("").unwrap(), (None, modules([])));
+}
+
+#[test]
+fn module_levels() {
+    assert_eq!(
+        logging::parse_log_spec("info, mxl_base=debug ,mxl_base::logging = trace").unwrap(),
+        (
+            Some(LevelFilter::Info),
+            modules([
+                ("mxl_base", LevelFilter::Debug),
+                ("mxl_base::logging", LevelFilter::Trace)
+            ])
+        )
+    );
+}
+
+#[test]
+fn bare_module_enables_all_levels() {
+    assert_eq!(
+        logging::parse_log_spec("gst,warn").unwrap(),
+        (Some(LevelFilter::Warn), modules([("gst", LevelFilter::Trace)]))
+    );
+}
+
+#[test]
+fn regex_filter_is_ignored() {
+    assert_eq!(
+        logging::parse_log_spec("mxl_base=debug/pipeline.*=error").unwrap(),
+        (None, modules([("mxl_base", LevelFilter::Debug)]))
+    );
+    assert_eq!(logging::parse_log_spec("/only a filter").unwrap(), (None, modules([])));
+}
+
+#[test]
+fn invalid_entries() {
+    assert!(logging::parse_log_spec("mxl_base=loud").is_err());
+    assert!(logging::parse_log_spec("mxl_base=").is_err());
+    assert!(logging::parse_log_spec("=debug").is_err());
+}
+
+// The logger can be applied only once per process, so the environment is tested with a single build:
+#[test]
+fn levels_of_the_environment() {
+    mxl_base::init(
+        "com",
+        "x-software",
+        "MXL Base Log Spec Test",
+        "mxl-base-log-spec-test",
+        "1.0.0",
+    );
+    unsafe { std::env::set_var(ENV_NAME_LOG, "warn,env_module=debug,env_enabled") };
+    let dir = tempfile::tempdir().unwrap();
+    let record_dispatch = mxl_base::fern::Dispatch::new().chain(mxl_base::fern::Output::call(|record| {
+        RECORDS
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", record.target(), record.args()))
+    }));
+    Builder::new()
+        .fallback(LogFallback::Disabled)
+        .without_console()
+        .add_dispatch(record_dispatch)
+        .build(dir.path())
+        .unwrap();
+    unsafe { std::env::remove_var(ENV_NAME_LOG) };
+
+    assert_eq!(logging::get_log_file_log_level(), LevelFilter::Warn);
+    assert_eq!(logging::get_console_log_level(), LevelFilter::Warn);
+    // The levels of the environment are not saved with the configured levels:
+    assert_eq!(logging::module_log_levels(), Default::default());
+
+    log::info!(target: "other", "below the default level");
+    log::debug!(target: "env_module::sub", "within the module level");
+    log::trace!(target: "env_module::sub", "below the module level");
+    log::trace!(target: "env_enabled", "all levels enabled");
+    let records = RECORDS.lock().unwrap();
+    let records: Vec<_> = records
+        .iter()
+        .filter(|record| !record.starts_with("mxl_base"))
+        .collect();
+    assert_eq!(
+        records,
+        [
+            "env_module::sub within the module level",
+            "env_enabled all levels enabled"
+        ]
+    );
+}
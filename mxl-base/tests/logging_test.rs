@@ -0,0 +1,171 @@
+use mxl_base::logging::{Builder, LogFallback, LoggingError};
+use std::{
+    path::PathBuf,
+    sync::{Mutex, Once},
+};
+
+const BINARY_NAME: &str = "mxl-base-logging-test";
+
+static RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| mxl_base::init("com", "x-software", "MXL Base Logging Test", BINARY_NAME, "1.0.0"));
+}
+
+// A directory below a regular file cannot be created, also not with the permissions of root:
+fn uncreatable_dir(dir: &tempfile::TempDir) -> PathBuf {
+    let file = dir.path().join("file");
+    std::fs::write(&file, "").unwrap();
+    file.join("log")
+}
+
+#[test]
+fn error_when_log_dir_cannot_be_created() {
+    init();
+    let dir = tempfile::tempdir().unwrap();
+    let log_dir = uncreatable_dir(&dir);
+    let error = Builder::new()
+        .fallback(LogFallback::Disabled)
+        .build(&log_dir)
+        .unwrap_err();
+    match &error {
+        LoggingError::CreateDir { path, .. } => assert_eq!(path, &log_dir),
+        error => panic!("Unexpected error {error:?}"),
+    }
+    assert!(error.to_string().contains("Cannot create logging directory"));
+    assert!(std::error::Error::source(&error).is_some());
+}
+
+#[test]
+fn error_when_log_file_cannot_be_opened() {
+    init();
+    let dir = tempfile::tempdir().unwrap();
+    // A directory with the name of the log file:
+    let log_file = dir.path().join(format!("{BINARY_NAME}.log"));
+    std::fs::create_dir(&log_file).unwrap();
+    let error = Builder::new()
+        .fallback(LogFallback::Disabled)
+        .build(dir.path())
+        .unwrap_err();
+    match &error {
+        LoggingError::OpenFile { path, .. } => assert_eq!(path, &log_file),
+        error => panic!("Unexpected error {error:?}"),
+    }
+    assert!(error.to_string().contains("Cannot open log file"));
+}
+
+#[cfg(unix)]
+#[test]
+fn error_in_read_only_dir() {
+    use std::os::unix::fs::PermissionsExt;
+
+    init();
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o555)).unwrap();
+    // The permissions do not apply to root:
+    if std::fs::write(dir.path().join("probe"), "").is_ok() {
+        return;
+    }
+    let log_dir = dir.path().join("log");
+    let result = Builder::new().fallback(LogFallback::Disabled).build(&log_dir);
+    std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+    match result {
+        Err(LoggingError::CreateDir { path, .. }) => assert_eq!(path, log_dir),
+        result => panic!("Unexpected result {result:?}"),
+    }
+}
+
+// The logger can be applied only once per process, so the fallback and the module levels are tested together:
+#[test]
+fn temp_dir_fallback_and_module_levels() {
+    init();
+    let dir = tempfile::tempdir().unwrap();
+    let record_dispatch = mxl_base::fern::Dispatch::new().chain(mxl_base::fern::Output::call(|record| {
+        RECORDS
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", record.target(), record.args()))
+    }));
+    Builder::new()
+        .fallback(LogFallback::TempDir)
+        .without_console()
+        .level_for("capped", log::LevelFilter::Debug)
+        .add_dispatch(record_dispatch)
+        .build(&uncreatable_dir(&dir))
+        .unwrap();
+
+    let log_file = std::env::temp_dir()
+        .join(BINARY_NAME)
+        .join(format!("{BINARY_NAME}.log"));
+    assert_eq!(mxl_base::logging::try_current_log_file(), Some(&log_file));
+    assert!(log_file.exists());
+
+    // The level of the builder cannot raise the global level:
+    mxl_base::logging::set_log_file_log_level(log::LevelFilter::Info);
+    log::debug!(target: "capped::module", "capped by the global level");
+    // A level set at runtime can raise the global level:
+    mxl_base::logging::set_module_log_level("capped", Some(log::LevelFilter::Trace));
+    log::debug!(target: "capped::module", "raised at runtime");
+    mxl_base::logging::set_module_log_level("capped", None);
+    mxl_base::logging::set_log_file_log_level(log::LevelFilter::Trace);
+    log::trace!(target: "capped::module", "above the builder level");
+    let line = line!() + 1;
+    log::debug!(target: "capped::module", "within the builder level");
+    log::logger().flush();
+
+    // The default format of the log file has all fields:
+    // "{timestamp} {level} [{thread}:{id}] [{target}] {file}:{line} {message}"
+    let content = std::fs::read_to_string(&log_file).unwrap();
+    let record = content
+        .lines()
+        .rfind(|record| record.ends_with("within the builder level"))
+        .unwrap();
+    let mut fields = record.splitn(5, ' ');
+    chrono::DateTime::parse_from_rfc3339(fields.next().unwrap()).unwrap();
+    assert_eq!(fields.next(), Some("DEBUG"));
+    let (thread, thread_id) = fields
+        .next()
+        .unwrap()
+        .trim_matches(['[', ']'])
+        .rsplit_once(':')
+        .unwrap();
+    assert_eq!(Some(thread), std::thread::current().name());
+    thread_id.parse::<u64>().unwrap();
+    assert_eq!(fields.next(), Some("[capped::module]"));
+    assert_eq!(
+        fields.next(),
+        Some(format!("{}:{line} within the builder level", file!()).as_str())
+    );
+
+    let capped_records = || -> Vec<String> {
+        RECORDS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|record| record.starts_with("capped::module"))
+            .cloned()
+            .collect()
+    };
+    assert_eq!(
+        capped_records(),
+        [
+            "capped::module raised at runtime",
+            "capped::module within the builder level"
+        ]
+    );
+
+    // Logging cannot be initialized twice and the failed build does not change the levels:
+    unsafe { std::env::set_var(mxl_base::logging::ENV_NAME_LOG, "error,capped=off") };
+    let result = Builder::new()
+        .level_for("capped", log::LevelFilter::Off)
+        .build(dir.path());
+    unsafe { std::env::remove_var(mxl_base::logging::ENV_NAME_LOG) };
+    assert!(matches!(result, Err(LoggingError::AlreadyInitialized(_))));
+    assert_eq!(mxl_base::logging::get_log_file_log_level(), log::LevelFilter::Trace);
+    log::debug!(target: "capped::module", "after the failed build");
+    assert_eq!(
+        capped_records().last().map(String::as_str),
+        Some("capped::module after the failed build")
+    );
+}
@@ -0,0 +1,123 @@
+use mxl_base::redaction::Redactor;
+use std::borrow::Cow;
+
+// Without the local host name, so that the results do not depend on the machine:
+fn redactor() -> Redactor {
+    Redactor::builder().local_host_name(false).build()
+}
+
+#[test]
+fn home_dirs() {
+    let redactor = redactor();
+    assert_eq!(
+        redactor.redact("Open '/home/alice/Videos/clip.mp4'"),
+        "Open '/home/<user>/Videos/clip.mp4'"
+    );
+    assert_eq!(redactor.redact("/Users/bob/Library"), "/Users/<user>/Library");
+    assert_eq!(
+        redactor.redact(r"C:\Users\carol\AppData\Local"),
+        r"C:\Users\<user>\AppData\Local"
+    );
+    assert_eq!(
+        Redactor::builder()
+            .local_host_name(false)
+            .home_dirs(false)
+            .build()
+            .redact("/home/alice"),
+        "/home/alice"
+    );
+}
+
+#[test]
+fn emails() {
+    assert_eq!(
+        redactor().redact("Report sent by jane.doe+mxl@example.com."),
+        "Report sent by <email>."
+    );
+}
+
+#[test]
+fn host_names() {
+    let redactor = Redactor::builder()
+        .local_host_name(false)
+        .host_name("build-server")
+        .build();
+    assert_eq!(
+        redactor.redact("Connected to BUILD-SERVER as build-server-2"),
+        "Connected to <host> as <host>-2"
+    );
+    // The host name of an e-mail address is part of the e-mail address:
+    assert_eq!(redactor.redact("admin@build-server.example.com"), "<email>");
+}
+
+#[test]
+fn ipv4_addresses() {
+    let redactor = redactor();
+    assert_eq!(
+        redactor.redact("Stream from 192.168.1.20:8554"),
+        "Stream from <ip>:8554"
+    );
+    assert_eq!(redactor.redact("rtsp://10.0.0.5/live"), "rtsp://<ip>/live");
+    assert_eq!(redactor.redact("address: 127.0.0.1."), "address: <ip>.");
+    // Invalid addresses and parts of longer numbers:
+    assert_eq!(redactor.redact("999.1.1.1"), "999.1.1.1");
+    assert_eq!(redactor.redact("1.2.3.4.5"), "1.2.3.4.5");
+}
+
+#[test]
+fn versions_are_kept() {
+    let redactor = redactor();
+    for text in [
+        "GStreamer 1.24.12.0",
+        "version 1.24.12.1",
+        "Version: 10.2.3.4",
+        "release=2.1.0.7",
+        "v1.24.12.1",
+        "libfoo.so.1.2.3.4",
+        "1.24.12",
+    ] {
+        assert_eq!(redactor.redact(text), text);
+    }
+}
+
+#[test]
+fn ipv6_addresses() {
+    let redactor = redactor();
+    assert_eq!(redactor.redact("Listen on [::1]:8080"), "Listen on [<ip>]:8080");
+    assert_eq!(
+        redactor.redact("peer 2001:db8::8a2e:370:7334, interface fe80::1%eth0"),
+        "peer <ip>, interface <ip>%eth0"
+    );
+    assert_eq!(redactor.redact("mapped ::ffff:192.168.1.1"), "mapped <ip>");
+}
+
+#[test]
+fn rust_paths_and_times_are_kept() {
+    let redactor = redactor();
+    for text in [
+        "add::face",
+        "mxl_base::logging::levels",
+        "std::fmt::Display",
+        "[2026-10-19T10:00:00.000] INFO started at 12:30:45",
+        "MAC 00:1a:2b:3c:4d:5e",
+    ] {
+        assert_eq!(redactor.redact(text), text);
+    }
+}
+
+#[test]
+fn text_without_personal_data_is_not_copied() {
+    let text = "Player state changed to playing with GStreamer 1.24.12.0 in mxl::player";
+    assert!(matches!(redactor().redact(text), Cow::Borrowed(_)));
+}
+
+#[test]
+fn additional_rules() {
+    let redactor = Redactor::builder()
+        .local_host_name(false)
+        .rule(r"serial=(\w+)", "serial=<serial>")
+        .unwrap()
+        .build();
+    assert_eq!(redactor.redact("camera serial=AB1234"), "camera serial=<serial>");
+    assert!(Redactor::builder().rule("(", "").is_err());
+}
@@ -0,0 +1,137 @@
+use mxl_base::logging::{LogRotation, RotatingLogFile};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const MAX_SIZE: u64 = 100;
+
+fn rotated_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(suffix);
+    PathBuf::from(rotated)
+}
+
+fn read(path: &Path, suffix: &str) -> String {
+    std::fs::read_to_string(rotated_path(path, suffix)).unwrap()
+}
+
+// A record that fills the log file, so that it is rotated when it is flushed:
+fn write_record(log_file: &mut RotatingLogFile, name: &str) {
+    writeln!(log_file, "{name:-<width$}", width = MAX_SIZE as usize).unwrap();
+    log_file.flush().unwrap();
+}
+
+fn size_rotation(keep: usize) -> LogRotation {
+    LogRotation {
+        max_size: Some(MAX_SIZE),
+        keep,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn rotate_by_size_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.log");
+    let mut log_file = RotatingLogFile::open(&path, size_rotation(3)).unwrap();
+
+    // A small record does not rotate the file:
+    writeln!(log_file, "small").unwrap();
+    log_file.flush().unwrap();
+    assert!(!rotated_path(&path, ".1").exists());
+
+    for name in ["first", "second", "third", "fourth"] {
+        write_record(&mut log_file, name);
+    }
+    assert!(std::fs::read_to_string(&path).unwrap().is_empty());
+    // The newest file has the lowest number and only three files are kept:
+    assert!(read(&path, ".1").starts_with("fourth"));
+    assert!(read(&path, ".2").starts_with("third"));
+    assert!(read(&path, ".3").starts_with("second"));
+    assert!(!rotated_path(&path, ".4").exists());
+
+    writeln!(log_file, "continued").unwrap();
+    log_file.flush().unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "continued\n");
+}
+
+#[test]
+fn keep_no_rotated_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.log");
+    let mut log_file = RotatingLogFile::open(&path, size_rotation(0)).unwrap();
+
+    write_record(&mut log_file, "first");
+    assert!(std::fs::read_to_string(&path).unwrap().is_empty());
+    assert!(!rotated_path(&path, ".1").exists());
+
+    writeln!(log_file, "after").unwrap();
+    log_file.flush().unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "after\n");
+}
+
+#[test]
+fn rotate_by_age_across_runs() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.log");
+    std::fs::write(&path, "previous run\n").unwrap();
+
+    // The file of the previous run is younger than an hour, so it is continued:
+    let rotation = LogRotation {
+        max_age: Some(Duration::from_secs(3600)),
+        keep: 2,
+        ..Default::default()
+    };
+    let mut log_file = RotatingLogFile::open(&path, rotation).unwrap();
+    writeln!(log_file, "second run").unwrap();
+    log_file.flush().unwrap();
+    drop(log_file);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "previous run\nsecond run\n");
+    assert!(!rotated_path(&path, ".1").exists());
+
+    // Every file is too old for a maximum age of zero, so the file is rotated when it is opened:
+    let rotation = LogRotation {
+        max_age: Some(Duration::ZERO),
+        keep: 2,
+        ..Default::default()
+    };
+    let log_file = RotatingLogFile::open(&path, rotation).unwrap();
+    drop(log_file);
+    assert!(std::fs::read_to_string(&path).unwrap().is_empty());
+    assert_eq!(read(&path, ".1"), "previous run\nsecond run\n");
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn compress_rotated_files() {
+    use std::io::Read;
+
+    fn decompress(path: &Path, suffix: &str) -> String {
+        let file = std::fs::File::open(rotated_path(path, suffix)).unwrap();
+        let mut content = String::new();
+        flate2::read::GzDecoder::new(file).read_to_string(&mut content).unwrap();
+        content
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.log");
+    let rotation = LogRotation {
+        compress: true,
+        ..size_rotation(2)
+    };
+    let mut log_file = RotatingLogFile::open(&path, rotation).unwrap();
+    for name in ["first", "second", "third"] {
+        write_record(&mut log_file, name);
+    }
+    // Dropping the log file waits for the compression in the background:
+    drop(log_file);
+
+    assert!(decompress(&path, ".1.gz").starts_with("third"));
+    assert!(decompress(&path, ".2.gz").starts_with("second"));
+    assert!(!rotated_path(&path, ".3.gz").exists());
+    for suffix in [".1", ".2"] {
+        assert!(!rotated_path(&path, suffix).exists());
+    }
+}
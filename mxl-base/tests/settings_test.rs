@@ -0,0 +1,82 @@
+use mxl_base::settings::{Settings, SettingsStore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct PlayerSettings {
+    volume: f64,
+    muted: bool,
+}
+
+// Version 1 stored the volume in percent:
+impl Settings for PlayerSettings {
+    const NAME: &'static str = "player";
+    const VERSION: u32 = 2;
+
+    fn migrate(from_version: u32, table: &mut toml::Table) -> anyhow::Result<()> {
+        if from_version == 1
+            && let Some(percent) = table.remove("volume_percent")
+        {
+            let percent = percent.as_integer().unwrap_or(100);
+            table.insert("volume".to_string(), toml::Value::Float(percent as f64 / 100.0));
+        }
+        Ok(())
+    }
+}
+
+fn read_table(path: &std::path::Path) -> toml::Table {
+    std::fs::read_to_string(path).unwrap().parse().unwrap()
+}
+
+#[test]
+fn migrate_from_version_1() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("player.toml");
+    std::fs::write(&path, "settings_version = 1\nvolume_percent = 50\nmuted = true\n").unwrap();
+
+    let store = SettingsStore::<PlayerSettings>::open(path.clone()).unwrap();
+    assert_eq!(
+        store.get(),
+        PlayerSettings {
+            volume: 0.5,
+            muted: true
+        }
+    );
+
+    // The migrated settings are saved with the current version:
+    let table = read_table(&path);
+    assert_eq!(table["settings_version"].as_integer(), Some(2));
+    assert_eq!(table["volume"].as_float(), Some(0.5));
+    assert!(!table.contains_key("volume_percent"));
+}
+
+#[test]
+fn update_saves_and_notifies() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("player.toml");
+    let store = SettingsStore::<PlayerSettings>::open(path.clone()).unwrap();
+    assert_eq!(store.get(), PlayerSettings::default());
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    store.subscribe(move |settings: &PlayerSettings| sender.send(settings.volume).unwrap());
+    store.update(|settings| settings.volume = 0.8).unwrap();
+    assert_eq!(receiver.try_recv(), Ok(0.8));
+    assert_eq!(read_table(&path)["volume"].as_float(), Some(0.8));
+}
+
+#[test]
+fn newer_version_is_not_overwritten() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("player.toml");
+    let content = "settings_version = 3\nvolume = 0.3\nequalizer = true\n";
+    std::fs::write(&path, content).unwrap();
+
+    assert!(SettingsStore::<PlayerSettings>::open(path.clone()).is_err());
+    let store = SettingsStore::<PlayerSettings>::open_or_default(path.clone());
+    assert!(store.is_read_only());
+    assert_eq!(store.get(), PlayerSettings::default());
+
+    assert!(store.set(PlayerSettings::default()).is_err());
+    assert!(store.update(|settings| settings.muted = true).is_err());
+    assert!(!store.get().muted);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+}
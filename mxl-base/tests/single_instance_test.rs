@@ -0,0 +1,70 @@
+use mxl_base::single_instance::{self, Instance};
+use std::sync::Once;
+
+fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        mxl_base::init(
+            "com",
+            "x-software",
+            "MXL Base Single Instance Test",
+            "mxl-base-single-instance-test",
+            "1.0.0",
+        )
+    });
+}
+
+#[test]
+fn first_instance_is_primary() {
+    init();
+    let dir = tempfile::tempdir().unwrap();
+    let primary = single_instance::acquire_in(dir.path()).unwrap();
+    assert!(matches!(primary, Instance::Primary(_)));
+    assert!(matches!(
+        single_instance::acquire_in(dir.path()).unwrap(),
+        Instance::Secondary(_)
+    ));
+
+    // The lock is released with the primary instance:
+    drop(primary);
+    assert!(matches!(
+        single_instance::acquire_in(dir.path()).unwrap(),
+        Instance::Primary(_)
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn forward_arguments_to_primary() {
+    init();
+    let dir = tempfile::tempdir().unwrap();
+    let Instance::Primary(mut primary) = single_instance::acquire_in(dir.path()).unwrap() else {
+        panic!("First instance is not primary");
+    };
+    let (sender, receiver) = std::sync::mpsc::channel();
+    primary
+        .listen(move |arguments| sender.send(arguments).unwrap())
+        .unwrap();
+
+    // A client that does not finish sending does not block the secondary instance:
+    let stalled = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|extension| extension == "sock"))
+        .map(|path| std::os::unix::net::UnixStream::connect(path).unwrap())
+        .unwrap();
+
+    let Instance::Secondary(secondary) = single_instance::acquire_in(dir.path()).unwrap() else {
+        panic!("Second instance is not secondary");
+    };
+    let arguments = [
+        "--fullscreen".to_string(),
+        "https://example.com/clip with spaces.mp4".to_string(),
+    ];
+    secondary.forward(&arguments).unwrap();
+    assert_eq!(
+        receiver.recv_timeout(std::time::Duration::from_secs(30)).unwrap(),
+        arguments
+    );
+    drop(stalled);
+}
@@ -0,0 +1,49 @@
+use mxl_base::translations::check_translations;
+use std::path::Path;
+
+const ENGLISH: &str = "greeting = Hello { $name }\ndialog =\n    .title = Settings\n";
+
+// A crate with an English and a German translation:
+fn create_crate(dir: &Path, german: &str) {
+    std::fs::write(
+        dir.join("i18n.toml"),
+        "fallback_language = \"en\"\n[fluent]\nassets_dir = \"i18n\"\n",
+    )
+    .unwrap();
+    for (locale, content) in [("en", ENGLISH), ("de", german)] {
+        let locale_dir = dir.join("i18n").join(locale);
+        std::fs::create_dir_all(&locale_dir).unwrap();
+        std::fs::write(locale_dir.join("app.ftl"), content).unwrap();
+    }
+}
+
+#[test]
+fn complete_translation() {
+    let dir = tempfile::tempdir().unwrap();
+    create_crate(
+        dir.path(),
+        "greeting = Hallo { $name }\ndialog =\n    .title = Einstellungen\n",
+    );
+    check_translations(dir.path()).unwrap();
+}
+
+#[test]
+fn missing_messages_and_variables() {
+    let dir = tempfile::tempdir().unwrap();
+    create_crate(dir.path(), "greeting = Hallo\nfarewell = Tschüss\n");
+    let error = check_translations(dir.path()).unwrap_err().to_string();
+    assert!(error.contains("de: missing message 'dialog.title'"), "{error}");
+    assert!(error.contains("de: message 'greeting' has variables {}"), "{error}");
+    assert!(
+        error.contains("de: message 'farewell' does not exist in 'en'"),
+        "{error}"
+    );
+}
+
+#[test]
+fn missing_fallback_language() {
+    let dir = tempfile::tempdir().unwrap();
+    create_crate(dir.path(), ENGLISH);
+    std::fs::remove_dir_all(dir.path().join("i18n").join("en")).unwrap();
+    assert!(check_translations(dir.path()).is_err());
+}
@@ -0,0 +1,34 @@
+-all-files = Alle Dateien
+-zip-archive = ZIP-Archiv
+-redact = Persönliche Daten entfernen
+-redact-description = Benutzernamen, Rechnernamen, E-Mail- und IP-Adressen werden im Bericht ersetzt
+
+problem-report-dialog = Problembericht
+    .file-description = Eine oder mehrere vorherige, nicht erfolgreich beendete Programmausführungen können exportiert und zur Untersuchung gesendet werden. Der Bericht enthält keine Video- oder Audiodaten. Wenn Sie diesen Dialog schließen, wird er beim nächsten Programmstart erneut angezeigt.
+    .progress-description = Wird erstellt...
+    .success-title = Bericht erfolgreich erstellt
+    .success-description = Die Berichtsdatei wurde unter '{$file_name}' gespeichert. Falls sich Ihre Anfrage auf codierte Video- oder Audiodaten bezieht, senden Sie diese bitte zusammen mit der erstellten Berichtsdatei an {$support_mail}
+    .error-create-title = Erstellen des Berichts fehlgeschlagen
+    .error-move-title = Verschieben in den Papierkorb fehlgeschlagen
+    .btn-choose-file = Berichtsdatei auswählen...
+    .btn-back = Zurück
+    .btn-move-to-trash = In den Papierkorb verschieben
+    .redact = { -redact }
+    .redact-description = { -redact-description }
+    .btn-open-directory = Verzeichnis öffnen
+    .all-files = { -all-files }
+    .zip-archive = { -zip-archive }
+
+create-report-dialog = Berichtsdatei erstellen
+    .file-description = Wenn Sie Unterstützung benötigen, können Sie eine Berichtsdatei der aktuellen Sitzung erstellen. Der Bericht enthält keine Video- oder Audiodaten.
+    .progress-description = Wird erstellt...
+    .success-title = Bericht erfolgreich erstellt
+    .success-description = Die Berichtsdatei wurde unter '{$file_name}' gespeichert. Falls sich Ihre Anfrage auf codierte Video- oder Audiodaten bezieht, senden Sie diese bitte zusammen mit der erstellten Berichtsdatei an {$support_mail}
+    .error-title = Erstellen des Berichts fehlgeschlagen
+    .btn-choose-file = Berichtsdatei auswählen...
+    .btn-choose-other-file = Andere Berichtsdatei auswählen...
+    .redact = { -redact }
+    .redact-description = { -redact-description }
+    .btn-open-directory = Verzeichnis öffnen
+    .all-files = { -all-files }
+    .zip-archive = { -zip-archive }
@@ -1,5 +1,7 @@
 -all-files = All files
 -zip-archive = ZIP archive
+-redact = Remove personal data
+-redact-description = User names, host names, e-mail and IP addresses are replaced in the report
 
 problem-report-dialog = Problem report
     .file-description = One or more previous application executions that were unsuccessful can be exported and sent for investigation. The report will not contain any video or audio data. This dialog will be displayed the next time you start the program if you close it.
@@ -11,6 +13,8 @@ problem-report-dialog = Problem report
     .btn-choose-file = Choose report file...
     .btn-back = Back
     .btn-move-to-trash = Move to trash
+    .redact = { -redact }
+    .redact-description = { -redact-description }
     .btn-open-directory = Open directory
     .all-files = { -all-files }
     .zip-archive = { -zip-archive }
@@ -23,6 +27,8 @@ create-report-dialog = Create report file
     .error-title = Report creation failed
     .btn-choose-file = Choose report file...
     .btn-choose-other-file = Choose other report file...
+    .redact = { -redact }
+    .redact-description = { -redact-description }
     .btn-open-directory = Open directory
     .all-files = { -all-files }
     .zip-archive = { -zip-archive }
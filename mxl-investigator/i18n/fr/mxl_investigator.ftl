@@ -0,0 +1,34 @@
+-all-files = Tous les fichiers
+-zip-archive = Archive ZIP
+-redact = Supprimer les données personnelles
+-redact-description = Les noms d'utilisateur, noms d'hôte, adresses e-mail et adresses IP sont remplacés dans le rapport
+
+problem-report-dialog = Rapport de problème
+    .file-description = Une ou plusieurs exécutions précédentes de l'application qui ont échoué peuvent être exportées et envoyées pour analyse. Le rapport ne contient aucune donnée vidéo ou audio. Si vous fermez cette fenêtre, elle s'affichera à nouveau au prochain démarrage du programme.
+    .progress-description = Création en cours...
+    .success-title = Rapport créé avec succès
+    .success-description = Le fichier de rapport a été enregistré dans '{$file_name}'. Si votre demande concerne des données vidéo ou audio encodées, veuillez les envoyer avec le fichier de rapport créé à {$support_mail}
+    .error-create-title = Échec de la création du rapport
+    .error-move-title = Échec du déplacement vers la corbeille
+    .btn-choose-file = Choisir le fichier de rapport...
+    .btn-back = Retour
+    .btn-move-to-trash = Déplacer vers la corbeille
+    .redact = { -redact }
+    .redact-description = { -redact-description }
+    .btn-open-directory = Ouvrir le répertoire
+    .all-files = { -all-files }
+    .zip-archive = { -zip-archive }
+
+create-report-dialog = Créer un fichier de rapport
+    .file-description = Si vous avez besoin d'assistance, vous pouvez générer un fichier de rapport de la session en cours. Le rapport ne contient aucune donnée vidéo ou audio.
+    .progress-description = Création en cours...
+    .success-title = Rapport créé avec succès
+    .success-description = Le fichier de rapport a été enregistré dans '{$file_name}'. Si votre demande concerne des données vidéo ou audio encodées, veuillez les envoyer avec le fichier de rapport créé à {$support_mail}
+    .error-title = Échec de la création du rapport
+    .btn-choose-file = Choisir le fichier de rapport...
+    .btn-choose-other-file = Choisir un autre fichier de rapport...
+    .redact = { -redact }
+    .redact-description = { -redact-description }
+    .btn-open-directory = Ouvrir le répertoire
+    .all-files = { -all-files }
+    .zip-archive = { -zip-archive }
@@ -14,6 +14,8 @@ pub(super) mod internal {
         OpenFileChooser,
         OpenDirectory,
         CreateReport(PathBuf),
+        RedactChanged(bool),
+        LanguageChanged,
     }
 }
 
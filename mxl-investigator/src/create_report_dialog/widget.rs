@@ -50,6 +50,7 @@ impl Component for CreateReportDialog {
 
     view! {
         adw::Window {
+            #[watch]
             set_title: Some(&fl!("create-report-dialog")),
             set_modal: true,
             set_hide_on_close: true,
@@ -77,7 +78,9 @@ impl Component for CreateReportDialog {
 
                         #[name(start_page)]
                         adw::StatusPage {
+                            #[watch]
                             set_title: &fl!("create-report-dialog"),
+                            #[watch]
                             set_description: Some(&fl!("create-report-dialog", "file-description")),
 
                             gtk::Box {
@@ -87,11 +90,30 @@ impl Component for CreateReportDialog {
 
                                 adw::PreferencesGroup {
                                     adw::ActionRow {
+                                        #[watch]
                                         set_title: &fl!("create-report-dialog", "btn-choose-file"),
                                         set_activatable: true,
                                         add_suffix = &gtk::Image::from_icon_name(icon_names::RIGHT_LARGE) {},
                                         connect_activated => CreateReportDialogInput::PrivateMessage(PrivateMsg::OpenFileChooser),
                                     },
+                                    adw::ActionRow {
+                                        #[watch]
+                                        set_title: &fl!("create-report-dialog", "redact"),
+                                        #[watch]
+                                        set_subtitle: &fl!("create-report-dialog", "redact-description"),
+                                        // Only shown if the application configured a redactor:
+                                        set_visible: crate::proc_dir::archive_redactor().is_some(),
+                                        set_activatable_widget: Some(&redact_switch),
+
+                                        #[name(redact_switch)]
+                                        add_suffix = &gtk::Switch {
+                                            set_valign: gtk::Align::Center,
+                                            set_active: model.redact,
+                                            connect_active_notify[sender] => move |switch| {
+                                                sender.input(CreateReportDialogInput::PrivateMessage(PrivateMsg::RedactChanged(switch.is_active())));
+                                            },
+                                        },
+                                    },
                                 },
                             },
                         },
@@ -109,12 +131,14 @@ impl Component for CreateReportDialog {
                             },
                             gtk::Label {
                                 add_css_class: adw_css::TITLE_2,
+                                #[watch]
                                 set_label: &&fl!("create-report-dialog", "progress-description"),
                             },
                         },
 
                         #[name(success_page)]
                         adw::StatusPage {
+                            #[watch]
                             set_title: &fl!("create-report-dialog", "success-title"),
                             add_css_class: adw_css::SUCCESS,
                             #[watch]
@@ -127,6 +151,7 @@ impl Component for CreateReportDialog {
 
                                 adw::PreferencesGroup {
                                     adw::ActionRow {
+                                        #[watch]
                                         set_title: &fl!("create-report-dialog", "btn-open-directory"),
                                         set_activatable: true,
                                         // add_suffix = &gtk::Image::from_icon_name(icon_names::FOLDER_OPEN) {},
@@ -138,6 +163,7 @@ impl Component for CreateReportDialog {
 
                         #[name(error_page)]
                         adw::StatusPage {
+                            #[watch]
                             set_title: &fl!("create-report-dialog", "error-title"),
                             add_css_class: adw_css::ERROR,
 
@@ -148,6 +174,7 @@ impl Component for CreateReportDialog {
 
                                 adw::PreferencesGroup {
                                     adw::ActionRow {
+                                        #[watch]
                                         set_title: &fl!("create-report-dialog", "btn-choose-other-file"),
                                         set_activatable: true,
                                         add_suffix = &gtk::Image::from_icon_name(icon_names::RIGHT_LARGE) {},
@@ -163,43 +190,47 @@ impl Component for CreateReportDialog {
     }
 
     fn init(init: Self::Init, root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let file_filters = vec![
+            {
+                let filter = gtk::FileFilter::new();
+                filter.add_suffix(crate::proc_dir::ARCHIVE_DEFAULT_FILE_EXTENSION);
+                filter
+            },
+            {
+                let filter = gtk::FileFilter::new();
+                filter.add_pattern("*");
+                filter
+            },
+        ];
+        // The input messages contain widgets, so the language listener cannot send them from another thread:
+        let (language_sender, language_receiver) = relm4::channel::<()>();
+        relm4::spawn_local(language_receiver.forward(sender.input_sender().clone(), |_| {
+            CreateReportDialogInput::PrivateMessage(PrivateMsg::LanguageChanged)
+        }));
+
         let model = CreateReportDialog {
             app_name: init.app_name,
             binary_name: init.binary_name,
             file_name: String::default(),
-            file_chooser: {
-                let builder = SaveDialog::builder();
-                let widget = builder.widget();
-                widget.set_title(&fl!("create-report-dialog"));
-                builder
-                    .launch(SaveDialogSettings {
-                        create_folders: true,
-                        is_modal: true,
-                        filters: vec![
-                            {
-                                let filter = gtk::FileFilter::new();
-                                filter.set_name(Some(&fl!("create-report-dialog", "zip-archive")));
-                                filter.add_suffix(crate::proc_dir::ARCHIVE_DEFAULT_FILE_EXTENSION);
-                                filter
-                            },
-                            {
-                                let filter = gtk::FileFilter::new();
-                                filter.set_name(Some(&fl!("create-report-dialog", "all-files")));
-                                filter.add_pattern("*");
-                                filter
-                            },
-                        ],
-                        ..Default::default()
-                    })
-                    .forward(sender.input_sender(), |response| match response {
-                        SaveDialogResponse::Accept(path) => {
-                            CreateReportDialogInput::PrivateMessage(PrivateMsg::CreateReport(path))
-                        }
-                        SaveDialogResponse::Cancel => CreateReportDialogInput::PrivateMessage(PrivateMsg::NoOperation),
-                    })
-            },
+            file_chooser: SaveDialog::builder()
+                .launch(SaveDialogSettings {
+                    create_folders: true,
+                    is_modal: true,
+                    filters: file_filters.clone(),
+                    ..Default::default()
+                })
+                .forward(sender.input_sender(), |response| match response {
+                    SaveDialogResponse::Accept(path) => {
+                        CreateReportDialogInput::PrivateMessage(PrivateMsg::CreateReport(path))
+                    }
+                    SaveDialogResponse::Cancel => CreateReportDialogInput::PrivateMessage(PrivateMsg::NoOperation),
+                }),
             processing: false,
+            redact: true,
+            file_filters,
+            _language_subscription: mxl_base::language::subscription(move |_| language_sender.emit(())),
         };
+        model.translate_file_chooser();
 
         let widgets = view_output!();
         mxl_relm4_components::gtk::do_closure_on_escape(&root, move || {
@@ -255,6 +286,12 @@ impl Component for CreateReportDialog {
                 PrivateMsg::OpenFileChooser => {
                     self.file_chooser.emit(SaveDialogMsg::SaveAs(self.file_name.clone()));
                 }
+                PrivateMsg::RedactChanged(redact) => self.redact = redact,
+                // The file chooser is translated again, the other labels are watched:
+                PrivateMsg::LanguageChanged => {
+                    self.translate_file_chooser();
+                    self.update_view(widgets, sender);
+                }
                 PrivateMsg::OpenDirectory => {
                     let mut dir = std::path::PathBuf::from(&self.file_name);
                     dir.set_file_name("");
@@ -267,7 +304,10 @@ impl Component for CreateReportDialog {
                     widgets.stack_view.set_transition_type(gtk::StackTransitionType::None);
                     widgets.stack_view.set_visible_child(&widgets.progress_page);
                     self.processing = true;
-                    sender.spawn_oneshot_command(move || crate::proc_dir::archive_and_remove_panics(&path));
+                    let redactor = crate::proc_dir::archive_redactor().filter(|_| self.redact);
+                    sender.spawn_oneshot_command(move || {
+                        crate::proc_dir::archive_and_remove_panics_with_redaction(&path, redactor.as_ref())
+                    });
                     self.update_view(widgets, sender);
                 }
             },
@@ -1,5 +1,5 @@
 use i18n_embed::{
-    DefaultLocalizer, DesktopLanguageRequester, LanguageLoader, Localizer,
+    LanguageLoader,
     fluent::{FluentLanguageLoader, fluent_language_loader},
 };
 use rust_embed::RustEmbed;
@@ -15,22 +15,19 @@ struct Localizations;
 pub static LANGUAGE_LOADER: OnceLock<FluentLanguageLoader> = OnceLock::new();
 
 pub(crate) fn init() {
-    LANGUAGE_LOADER.get_or_init(|| {
-        let loader = fluent_language_loader!();
-        loader
-            .load_fallback_language(&Localizations)
-            .expect("Error while loading fallback language");
-
-        let localizer = DefaultLocalizer::new(&loader, &Localizations);
-        let requested_languages = DesktopLanguageRequester::requested_languages();
-        if let Err(error) = localizer.select(&requested_languages) {
-            log::error!("Error while loading language: {error}");
-        }
-        loader
-    });
+    if LANGUAGE_LOADER.get().is_some() {
+        return;
+    }
+    let loader = fluent_language_loader!();
+    loader
+        .load_fallback_language(&Localizations)
+        .expect("Error while loading fallback language");
+    // The languages are selected and switched at runtime by the shared language module:
+    if LANGUAGE_LOADER.set(loader).is_ok() {
+        mxl_base::language::register_language_loader(language_loader(), &Localizations);
+    }
 }
 
-#[allow(dead_code)]
 pub(crate) fn language_loader() -> &'static FluentLanguageLoader {
     LANGUAGE_LOADER.get().expect("Localization is not initialized")
 }
@@ -56,22 +56,44 @@ pub fn log_sysinfo(level: log::Level) {
 }
 
 pub fn create_sysinfo_dump() {
-    #[cfg(feature = "sysinfo")]
-    {
-        fn create_sysinfo() -> Result<()> {
-            use sysinfo::{Components, Disks, Networks, System};
+    fn create_sysinfo() -> Result<()> {
+        let sysinfo_file_path = crate::proc_dir::proc_dir().join("sysinfo.txt");
+        let mut file = File::options()
+            .create(true)
+            .append(true)
+            .open(&sysinfo_file_path)
+            .with_context(|| format!("Cannot create file '{}'", sysinfo_file_path.to_string_lossy()))?;
+
+        let mut out = Vec::new();
+        // Identify the exact build of the application in reports:
+        if let Some(about) = mxl_base::try_about() {
+            let build_info = &about.build_info;
+            let unknown = "unknown";
+            writeln!(&mut out, "=> application:")?;
+            writeln!(&mut out, "Name:       {}", about.app_name)?;
+            writeln!(&mut out, "Version:    {}", about.version)?;
+            writeln!(&mut out, "Git commit: {}", build_info.git_commit.unwrap_or(unknown))?;
+            writeln!(
+                &mut out,
+                "Git dirty:  {}",
+                build_info
+                    .git_dirty
+                    .map_or(unknown.to_string(), |dirty| dirty.to_string())
+            )?;
+            writeln!(&mut out, "Profile:    {}", build_info.profile.unwrap_or(unknown))?;
+            writeln!(&mut out, "Target:     {}", build_info.target.unwrap_or(unknown))?;
+            writeln!(&mut out, "Timestamp:  {}", build_info.timestamp.unwrap_or(unknown))?;
+            writeln!(&mut out, "Features:   {}", build_info.features.join(","))?;
+            writeln!(&mut out, "Rustc:      {}", build_info.rustc_version.unwrap_or(unknown))?;
+        }
 
-            let sysinfo_file_path = crate::proc_dir::proc_dir().join("sysinfo.txt");
-            let mut file = File::options()
-                .create(true)
-                .append(true)
-                .open(&sysinfo_file_path)
-                .with_context(|| format!("Cannot create file '{}'", sysinfo_file_path.to_string_lossy()))?;
+        #[cfg(feature = "sysinfo")]
+        {
+            use sysinfo::{Components, Disks, Networks, System};
 
             let mut sys = sysinfo::System::new_all();
             sys.refresh_all();
 
-            let mut out = Vec::new();
             writeln!(&mut out, "=> system:")?;
             // RAM and swap information:
             writeln!(&mut out, "total memory: {} bytes", sys.total_memory())?;
@@ -123,14 +145,14 @@ pub fn create_sysinfo_dump() {
             for component in &components {
                 writeln!(&mut out, "{component:?}")?;
             }
-
-            file.write_all(out.as_slice())?;
-            Ok(())
         }
 
-        if let Err(err) = create_sysinfo() {
-            log::warn!("Cannot create system information: {err:?}");
-        }
+        file.write_all(out.as_slice())?;
+        Ok(())
+    }
+
+    if let Err(err) = create_sysinfo() {
+        log::warn!("Cannot create system information: {err:?}");
     }
 }
 
@@ -1,4 +1,8 @@
-use mxl_relm4_components::{relm4::Controller, relm4_components::save_dialog::SaveDialog};
+use crate::localization::helper::fl;
+use mxl_relm4_components::{
+    relm4::{ComponentController, Controller, gtk, gtk::prelude::*},
+    relm4_components::save_dialog::SaveDialog,
+};
 
 #[derive(Debug)]
 pub struct ProblemReportDialogInit {
@@ -13,6 +17,21 @@ pub struct ProblemReportDialog {
     pub(super) file_name: String,
     pub(super) file_chooser: Controller<SaveDialog>,
     pub(super) processing: bool,
+    pub(super) redact: bool,
+    // The filters of the file chooser, in the order of the names in translate_file_chooser():
+    pub(super) file_filters: Vec<gtk::FileFilter>,
+    pub(super) _language_subscription: mxl_base::language::LanguageSubscription,
 }
 
-impl ProblemReportDialog {}
+impl ProblemReportDialog {
+    pub(super) fn translate_file_chooser(&self) {
+        self.file_chooser.widget().set_title(&fl!("problem-report-dialog"));
+        let names = [
+            fl!("problem-report-dialog", "zip-archive"),
+            fl!("problem-report-dialog", "all-files"),
+        ];
+        for (filter, name) in self.file_filters.iter().zip(names) {
+            filter.set_name(Some(&name));
+        }
+    }
+}
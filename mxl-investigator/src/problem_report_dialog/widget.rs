@@ -50,6 +50,7 @@ impl Component for ProblemReportDialog {
 
     view! {
         adw::Window {
+            #[watch]
             set_title: Some(&fl!("problem-report-dialog")),
             set_modal: true,
             set_hide_on_close: true,
@@ -77,7 +78,9 @@ impl Component for ProblemReportDialog {
 
                         #[name(start_page)]
                         adw::StatusPage {
+                            #[watch]
                             set_title: &fl!("problem-report-dialog"),
+                            #[watch]
                             set_description: Some(&fl!("problem-report-dialog", "file-description")),
 
                             gtk::Box {
@@ -87,12 +90,32 @@ impl Component for ProblemReportDialog {
 
                                 adw::PreferencesGroup {
                                     adw::ActionRow {
+                                        #[watch]
                                         set_title: &fl!("problem-report-dialog", "btn-choose-file"),
                                         set_activatable: true,
                                         add_suffix = &gtk::Image::from_icon_name(icon_names::RIGHT_LARGE) {},
                                         connect_activated => ProblemReportDialogInput::PrivateMessage(PrivateMsg::OpenFileChooser),
                                     },
                                     adw::ActionRow {
+                                        #[watch]
+                                        set_title: &fl!("problem-report-dialog", "redact"),
+                                        #[watch]
+                                        set_subtitle: &fl!("problem-report-dialog", "redact-description"),
+                                        // Only shown if the application configured a redactor:
+                                        set_visible: crate::proc_dir::archive_redactor().is_some(),
+                                        set_activatable_widget: Some(&redact_switch),
+
+                                        #[name(redact_switch)]
+                                        add_suffix = &gtk::Switch {
+                                            set_valign: gtk::Align::Center,
+                                            set_active: model.redact,
+                                            connect_active_notify[sender] => move |switch| {
+                                                sender.input(ProblemReportDialogInput::PrivateMessage(PrivateMsg::RedactChanged(switch.is_active())));
+                                            },
+                                        },
+                                    },
+                                    adw::ActionRow {
+                                        #[watch]
                                         set_title: &fl!("problem-report-dialog", "btn-move-to-trash"),
                                         set_activatable: true,
                                         add_css_class: adw_css::ERROR,
@@ -116,12 +139,14 @@ impl Component for ProblemReportDialog {
                             },
                             gtk::Label {
                                 add_css_class: adw_css::TITLE_2,
+                                #[watch]
                                 set_label: &&fl!("problem-report-dialog", "progress-description"),
                             },
                         },
 
                         #[name(success_page)]
                         adw::StatusPage {
+                            #[watch]
                             set_title: &fl!("problem-report-dialog", "success-title"),
                             add_css_class: adw_css::SUCCESS,
                             #[watch]
@@ -134,6 +159,7 @@ impl Component for ProblemReportDialog {
 
                                 adw::PreferencesGroup {
                                     adw::ActionRow {
+                                        #[watch]
                                         set_title: &fl!("problem-report-dialog", "btn-open-directory"),
                                         set_activatable: true,
                                         // add_suffix = &gtk::Image::from_icon_name(icon_names::FOLDER_OPEN) {},
@@ -154,6 +180,7 @@ impl Component for ProblemReportDialog {
 
                                 adw::PreferencesGroup {
                                     adw::ActionRow {
+                                        #[watch]
                                         set_title: &fl!("problem-report-dialog", "btn-back"),
                                         set_activatable: true,
                                         add_prefix = &gtk::Image::from_icon_name(icon_names::LEFT_LARGE) {},
@@ -169,43 +196,47 @@ impl Component for ProblemReportDialog {
     }
 
     fn init(init: Self::Init, root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let file_filters = vec![
+            {
+                let filter = gtk::FileFilter::new();
+                filter.add_suffix(crate::proc_dir::ARCHIVE_DEFAULT_FILE_EXTENSION);
+                filter
+            },
+            {
+                let filter = gtk::FileFilter::new();
+                filter.add_pattern("*");
+                filter
+            },
+        ];
+        // The input messages contain widgets, so the language listener cannot send them from another thread:
+        let (language_sender, language_receiver) = relm4::channel::<()>();
+        relm4::spawn_local(language_receiver.forward(sender.input_sender().clone(), |_| {
+            ProblemReportDialogInput::PrivateMessage(PrivateMsg::LanguageChanged)
+        }));
+
         let model = ProblemReportDialog {
             app_name: init.app_name,
             binary_name: init.binary_name,
             file_name: String::default(),
-            file_chooser: {
-                let builder = SaveDialog::builder();
-                let widget = builder.widget();
-                widget.set_title(&fl!("problem-report-dialog"));
-                builder
-                    .launch(SaveDialogSettings {
-                        create_folders: true,
-                        is_modal: true,
-                        filters: vec![
-                            {
-                                let filter = gtk::FileFilter::new();
-                                filter.set_name(Some(&fl!("problem-report-dialog", "zip-archive")));
-                                filter.add_suffix(crate::proc_dir::ARCHIVE_DEFAULT_FILE_EXTENSION);
-                                filter
-                            },
-                            {
-                                let filter = gtk::FileFilter::new();
-                                filter.set_name(Some(&fl!("problem-report-dialog", "all-files")));
-                                filter.add_pattern("*");
-                                filter
-                            },
-                        ],
-                        ..Default::default()
-                    })
-                    .forward(sender.input_sender(), |response| match response {
-                        SaveDialogResponse::Accept(path) => {
-                            ProblemReportDialogInput::PrivateMessage(PrivateMsg::CreateReport(path))
-                        }
-                        SaveDialogResponse::Cancel => ProblemReportDialogInput::PrivateMessage(PrivateMsg::NoOperation),
-                    })
-            },
+            file_chooser: SaveDialog::builder()
+                .launch(SaveDialogSettings {
+                    create_folders: true,
+                    is_modal: true,
+                    filters: file_filters.clone(),
+                    ..Default::default()
+                })
+                .forward(sender.input_sender(), |response| match response {
+                    SaveDialogResponse::Accept(path) => {
+                        ProblemReportDialogInput::PrivateMessage(PrivateMsg::CreateReport(path))
+                    }
+                    SaveDialogResponse::Cancel => ProblemReportDialogInput::PrivateMessage(PrivateMsg::NoOperation),
+                }),
             processing: false,
+            redact: true,
+            file_filters,
+            _language_subscription: mxl_base::language::subscription(move |_| language_sender.emit(())),
         };
+        model.translate_file_chooser();
 
         root.upcast_ref::<gtk::Window>().connect_close_request(glib::clone!(
             #[strong]
@@ -270,6 +301,12 @@ impl Component for ProblemReportDialog {
                 PrivateMsg::OpenFileChooser => {
                     self.file_chooser.emit(SaveDialogMsg::SaveAs(self.file_name.clone()));
                 }
+                PrivateMsg::RedactChanged(redact) => self.redact = redact,
+                // The file chooser is translated again, the other labels are watched:
+                PrivateMsg::LanguageChanged => {
+                    self.translate_file_chooser();
+                    self.update_view(widgets, sender);
+                }
                 PrivateMsg::OpenDirectory => {
                     let mut dir = std::path::PathBuf::from(&self.file_name);
                     dir.set_file_name("");
@@ -282,7 +319,10 @@ impl Component for ProblemReportDialog {
                     widgets.stack_view.set_transition_type(gtk::StackTransitionType::None);
                     widgets.stack_view.set_visible_child(&widgets.progress_page);
                     self.processing = true;
-                    sender.spawn_oneshot_command(move || crate::proc_dir::archive_and_remove_panics(&path));
+                    let redactor = crate::proc_dir::archive_redactor().filter(|_| self.redact);
+                    sender.spawn_oneshot_command(move || {
+                        crate::proc_dir::archive_and_remove_panics_with_redaction(&path, redactor.as_ref())
+                    });
                     self.update_view(widgets, sender);
                 }
                 PrivateMsg::MoveToTrash => {
@@ -1,11 +1,12 @@
 use anyhow::{Context, Result};
 use fs4::fs_std::FileExt;
+use mxl_base::redaction::Redactor;
 use std::{
     fs::File,
     io::{Read, Write},
     panic,
     path::{Path, PathBuf},
-    sync::{Mutex, OnceLock},
+    sync::{Mutex, OnceLock, RwLock},
 };
 use walkdir::WalkDir;
 use zip::{ZipWriter, write::SimpleFileOptions};
@@ -24,6 +25,7 @@ const PANIC_FILE_EXTENSION: &str = "panic";
 static RUN_DIR_HOLDER: OnceLock<PathBuf> = OnceLock::new();
 pub type ProcDirArchiveCallback = fn();
 static PROC_DIR_ARCHIVE_CREATE_CALLBACK: OnceLock<ProcDirArchiveCallback> = OnceLock::new();
+static ARCHIVE_REDACTOR: RwLock<Option<Redactor>> = RwLock::new(None);
 
 pub fn set_proc_dir(path: PathBuf) {
     RUN_DIR_HOLDER.set(path).expect("Proc directory already set");
@@ -196,7 +198,7 @@ pub fn proc_dir() -> &'static PathBuf {
     })
 }
 
-fn create_archive(src_dirs: &[PathBuf], archive_file_path: &Path) -> Result<()> {
+fn create_archive(src_dirs: &[PathBuf], archive_file_path: &Path, redactor: Option<&Redactor>) -> Result<()> {
     if src_dirs.is_empty() {
         anyhow::bail!("Cannot archive empty list of directories");
     }
@@ -229,6 +231,19 @@ fn create_archive(src_dirs: &[PathBuf], archive_file_path: &Path) -> Result<()>
                 log::trace!("adding file {path:?} as {name:?} ...");
                 zip.start_file_from_path(name, options)
                     .with_context(|| format!("Cannot add file '{}' to archive", name.to_string_lossy()))?;
+                // Only text files are redacted, binary files like compressed logs are added unchanged:
+                if let Some(redactor) = redactor
+                    && let Ok(text) = std::fs::read_to_string(path)
+                {
+                    zip.write_all(redactor.redact(&text).as_bytes()).with_context(|| {
+                        format!(
+                            "Cannot write redacted file '{}' to the archive.",
+                            path.to_string_lossy()
+                        )
+                    })?;
+                    continue;
+                }
+
                 let mut f = File::open(path).with_context(|| {
                     format!(
                         "Cannot open file '{}' to add it to the archive.",
@@ -335,14 +350,27 @@ pub fn proc_dir_archive_set_callback(callback: ProcDirArchiveCallback) {
     PROC_DIR_ARCHIVE_CREATE_CALLBACK.set(callback).unwrap();
 }
 
+// Personal data is removed from the text files of reports, when a redactor is set:
+pub fn set_archive_redactor(redactor: Option<Redactor>) {
+    *ARCHIVE_REDACTOR.write().unwrap() = redactor;
+}
+
+pub fn archive_redactor() -> Option<Redactor> {
+    ARCHIVE_REDACTOR.read().unwrap().clone()
+}
+
 pub fn archive_and_remove_panics(archive_file_path: &Path) -> Result<()> {
+    archive_and_remove_panics_with_redaction(archive_file_path, archive_redactor().as_ref())
+}
+
+pub fn archive_and_remove_panics_with_redaction(archive_file_path: &Path, redactor: Option<&Redactor>) -> Result<()> {
     if let Some(callback) = PROC_DIR_ARCHIVE_CREATE_CALLBACK.get() {
         callback();
     }
     let directories = std::fs::read_dir(default_proc_dir())?
         .map(|entry| Ok(entry?.path()))
         .collect::<Result<Vec<_>>>()?;
-    create_archive(&directories, archive_file_path)?;
+    create_archive(&directories, archive_file_path, redactor)?;
     for dir in directories {
         if dir_has_panic(&dir)? {
             log::trace!("remove old directory: {dir:?}");
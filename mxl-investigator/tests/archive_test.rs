@@ -0,0 +1,39 @@
+use mxl_base::redaction::Redactor;
+use mxl_investigator::proc_dir;
+use std::{io::Read, path::Path};
+
+const LOG_TEXT: &str = "Open /home/alice/Videos/clip.mp4 from 192.168.1.20\n";
+const BINARY: [u8; 4] = [0xff, 0xfe, 0x00, 0x01];
+
+fn archived_file(archive: &Path, name: &str) -> Vec<u8> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(archive).unwrap()).unwrap();
+    let index = (0..archive.len())
+        .find(|index| archive.name_for_index(*index).is_some_and(|path| path.ends_with(name)))
+        .unwrap_or_else(|| panic!("{name} is not archived"));
+    let mut content = Vec::new();
+    archive.by_index(index).unwrap().read_to_end(&mut content).unwrap();
+    content
+}
+
+#[test]
+fn create_archive_with_redactor() {
+    let dir = tempfile::tempdir().unwrap();
+    mxl_investigator::init(dir.path().join("data"));
+    let run_dir = proc_dir::proc_dir();
+    std::fs::write(run_dir.join("app.log"), LOG_TEXT).unwrap();
+    std::fs::write(run_dir.join("data.bin"), BINARY).unwrap();
+
+    // Text files are redacted, binary files are archived unchanged:
+    let archive = dir.path().join("redacted.zip");
+    let redactor = Redactor::builder().local_host_name(false).build();
+    proc_dir::archive_and_remove_panics_with_redaction(&archive, Some(&redactor)).unwrap();
+    assert_eq!(
+        String::from_utf8(archived_file(&archive, "app.log")).unwrap(),
+        "Open /home/<user>/Videos/clip.mp4 from <ip>\n"
+    );
+    assert_eq!(archived_file(&archive, "data.bin"), BINARY);
+
+    let archive = dir.path().join("unchanged.zip");
+    proc_dir::archive_and_remove_panics_with_redaction(&archive, None).unwrap();
+    assert_eq!(archived_file(&archive, "app.log"), LOG_TEXT.as_bytes());
+}
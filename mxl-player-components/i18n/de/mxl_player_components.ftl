@@ -0,0 +1,65 @@
+# Message dialog
+create-report = Bericht erstellen
+quit = Beenden
+close = Schließen
+fatal-error-title = Schwerwiegender Fehler
+error-title = Fehler
+warning-title = Warnung
+
+# Player ui
+seeking = Springe...
+buffering = Puffere...
+
+# Playlist ui
+playlist = Wiedergabeliste
+add-file = Datei hinzufügen
+remove-file = Datei entfernen
+    .desc = Datei aus der Wiedergabeliste entfernen
+repeat = Wiederholen
+    .all = Alle wiederholen
+    .none = Nicht wiederholen
+sort-by = Sortieren nach
+    .start-time = Startzeit
+    .file-name = Dateiname
+playlist-empty = Die Wiedergabeliste ist leer
+    .desc = Fügen Sie Dateien hinzu, um die Wiedergabe zu starten
+invalid-uri = Ungültige URI {$uri}
+file-discovery-timeout = Zeitüberschreitung beim Ermitteln der Dateiinformationen
+retry-fetch-metadata = Metadaten erneut abrufen
+
+# Video offsets dialog ui
+video-offsets = Video-Versatz
+video-offsets-audio = Audio-Versatz
+    .description = Versatz zwischen der Audio- und der Videospur in Millisekunden festlegen
+video-offsets-subtitle = Untertitel-Versatz
+    .description = Versatz zwischen der Untertitel- und der Videospur in Millisekunden festlegen
+
+# Decoder settings ui
+decoder = Decoder
+
+# Markers ui
+markers = Markierungen
+markers-empty = Keine Markierungen
+add-marker = Markierung an der aktuellen Position hinzufügen
+remove-marker = Markierung entfernen
+marker-default-name = Markierung {$number}
+
+# Clip export dialog ui
+clip-export = Clip exportieren
+clip-export-start = Beginn in Sekunden
+clip-export-end = Ende in Sekunden
+clip-export-mode = Exportmodus
+    .auto = Automatisch
+    .stream-copy = Stream kopieren
+    .re-encode = Neu codieren
+clip-export-output = Ausgabedatei
+clip-export-choose-output = Auswählen...
+clip-export-cancel = Abbrechen
+
+# GStreamer debug preferences ui
+gst-debug = GStreamer-Debugprotokoll
+    .description = Diagnosemeldungen des Medien-Frameworks für Problemberichte
+gst-debug-enable = Debugprotokoll aktivieren
+    .log-file = Wird in {$path} geschrieben
+gst-debug-spec = Debug-Kategorien
+    .description = Kommagetrennte Kategorien mit Stufen, z. B. play:6,decodebin*:5
@@ -36,3 +36,30 @@ video-offsets-subtitle = Subtitle offset
 
 # Decoder settings ui
 decoder = Decoder
+
+# Markers ui
+markers = Markers
+markers-empty = No markers
+add-marker = Add marker at the current position
+remove-marker = Remove marker
+marker-default-name = Marker {$number}
+
+# Clip export dialog ui
+clip-export = Export clip
+clip-export-start = Start in seconds
+clip-export-end = End in seconds
+clip-export-mode = Export mode
+    .auto = Automatic
+    .stream-copy = Stream copy
+    .re-encode = Re-encode
+clip-export-output = Output file
+clip-export-choose-output = Choose...
+clip-export-cancel = Cancel
+
+# GStreamer debug preferences ui
+gst-debug = GStreamer debug log
+    .description = Diagnostic messages of the media framework for problem reports
+gst-debug-enable = Enable debug log
+    .log-file = Written to {$path}
+gst-debug-spec = Debug categories
+    .description = Comma separated categories with levels, e.g. play:6,decodebin*:5
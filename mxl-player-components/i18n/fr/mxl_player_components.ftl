@@ -0,0 +1,65 @@
+# Message dialog
+create-report = Créer un rapport
+quit = Quitter
+close = Fermer
+fatal-error-title = Erreur fatale
+error-title = Erreur
+warning-title = Avertissement
+
+# Player ui
+seeking = Recherche de position...
+buffering = Mise en mémoire tampon...
+
+# Playlist ui
+playlist = Liste de lecture
+add-file = Ajouter un fichier
+remove-file = Supprimer le fichier
+    .desc = Supprimer le fichier de la liste de lecture
+repeat = Répéter
+    .all = Tout répéter
+    .none = Ne pas répéter
+sort-by = Trier par
+    .start-time = Heure de début
+    .file-name = Nom de fichier
+playlist-empty = La liste de lecture est vide
+    .desc = Ajoutez des fichiers pour démarrer la lecture
+invalid-uri = URI non valide {$uri}
+file-discovery-timeout = Délai dépassé lors de la récupération des informations du fichier
+retry-fetch-metadata = Réessayer de récupérer les métadonnées
+
+# Video offsets dialog ui
+video-offsets = Décalages vidéo
+video-offsets-audio = Décalage audio
+    .description = Définir le décalage entre la piste audio et la piste vidéo en millisecondes
+video-offsets-subtitle = Décalage des sous-titres
+    .description = Définir le décalage entre la piste de sous-titres et la piste vidéo en millisecondes
+
+# Decoder settings ui
+decoder = Décodeur
+
+# Markers ui
+markers = Marqueurs
+markers-empty = Aucun marqueur
+add-marker = Ajouter un marqueur à la position actuelle
+remove-marker = Supprimer le marqueur
+marker-default-name = Marqueur {$number}
+
+# Clip export dialog ui
+clip-export = Exporter un extrait
+clip-export-start = Début en secondes
+clip-export-end = Fin en secondes
+clip-export-mode = Mode d'exportation
+    .auto = Automatique
+    .stream-copy = Copie du flux
+    .re-encode = Réencodage
+clip-export-output = Fichier de sortie
+clip-export-choose-output = Choisir...
+clip-export-cancel = Annuler
+
+# GStreamer debug preferences ui
+gst-debug = Journal de débogage GStreamer
+    .description = Messages de diagnostic du framework multimédia pour les rapports de problème
+gst-debug-enable = Activer le journal de débogage
+    .log-file = Écrit dans {$path}
+gst-debug-spec = Catégories de débogage
+    .description = Catégories séparées par des virgules avec niveaux, p. ex. play:6,decodebin*:5
@@ -18,6 +18,9 @@ pub enum Accelerators {
     OpenProcDir,
     Preferences,
     VideoOffsets,
+    AddMarker,
+    NextMarker,
+    PreviousMarker,
 }
 
 #[macro_export]
@@ -72,5 +75,9 @@ pub fn accelerators(accel: Accelerators) -> Vec<&'static str> {
         Accelerators::OpenProcDir => vec![action_accelerator_with_os_modifier!("D")],
         Accelerators::Preferences => vec![action_accelerator_with_os_modifier!("comma")],
         Accelerators::VideoOffsets => vec![action_accelerator_with_os_modifier!("T")],
+        // Without <Shift> it would be the minimize shortcut of macOS:
+        Accelerators::AddMarker => vec![action_accelerator_with_os_modifier!("<Shift>M")],
+        Accelerators::NextMarker => vec![action_accelerator_with_os_modifier!("<Shift>Right")],
+        Accelerators::PreviousMarker => vec![action_accelerator_with_os_modifier!("<Shift>Left")],
     }
 }
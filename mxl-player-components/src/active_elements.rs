@@ -0,0 +1,303 @@
+use gst::prelude::*;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamMediaType {
+    Video,
+    Audio,
+    Subtitle,
+}
+
+impl StreamMediaType {
+    fn from_klass(klass: &str) -> Option<Self> {
+        if klass.contains("Video") || klass.contains("Image") {
+            Some(StreamMediaType::Video)
+        } else if klass.contains("Audio") {
+            Some(StreamMediaType::Audio)
+        } else if klass.contains("Subtitle") || klass.contains("Text") {
+            Some(StreamMediaType::Subtitle)
+        } else {
+            None
+        }
+    }
+
+    fn from_caps(caps: &gst::Caps) -> Option<Self> {
+        let name = caps.structure(0)?.name();
+        if name.starts_with("video/") || name.starts_with("image/") {
+            Some(StreamMediaType::Video)
+        } else if name.starts_with("audio/") {
+            Some(StreamMediaType::Audio)
+        } else if name.starts_with("text/") || name.starts_with("subpicture/") || name.starts_with("application/x-ssa")
+        {
+            Some(StreamMediaType::Subtitle)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveElement {
+    pub name: String,
+    pub factory: String,
+    pub klass: String,
+    pub input_caps: Option<String>,
+    pub output_caps: Option<String>,
+}
+
+impl ActiveElement {
+    fn from_element(element: &gst::Element, klass: &str) -> Option<Self> {
+        let factory = element.factory()?;
+        let pad_caps = |name: &str| {
+            element
+                .static_pad(name)
+                .and_then(|pad| pad.current_caps())
+                .map(|caps| caps.to_string())
+        };
+        Some(Self {
+            name: element.name().to_string(),
+            factory: factory.name().to_string(),
+            klass: klass.to_string(),
+            input_caps: pad_caps("sink"),
+            output_caps: pad_caps("src"),
+        })
+    }
+
+    // GStreamer marks elements that use dedicated hardware in the klass:
+    pub fn is_hardware(&self) -> bool {
+        self.klass.contains("Hardware")
+    }
+}
+
+impl fmt::Display for ActiveElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}, {})", self.factory, self.name, self.klass)?;
+        if let Some(caps) = &self.input_caps {
+            write!(f, "\n    input caps: {caps}")?;
+        }
+        if let Some(caps) = &self.output_caps {
+            write!(f, "\n    output caps: {caps}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveStream {
+    pub media_type: StreamMediaType,
+    pub parser: Option<ActiveElement>,
+    pub decoder: Option<ActiveElement>,
+    pub sink: Option<ActiveElement>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Parser,
+    Decoder,
+    Sink,
+}
+
+impl Role {
+    fn from_klass(klass: &str) -> Option<Self> {
+        if klass.contains("Decoder") {
+            Some(Role::Decoder)
+        } else if klass.contains("Parser") {
+            Some(Role::Parser)
+        } else if klass.contains("Sink") {
+            Some(Role::Sink)
+        } else {
+            None
+        }
+    }
+}
+
+impl ActiveStream {
+    fn new(media_type: StreamMediaType) -> Self {
+        Self {
+            media_type,
+            parser: None,
+            decoder: None,
+            sink: None,
+        }
+    }
+
+    // Keep the first element of each role, bins like glsinkbin only wrap the element that does the work:
+    fn add(&mut self, element: &gst::Element) {
+        if element.is::<gst::Bin>() {
+            return;
+        }
+        let Some(klass) = element.factory().map(|factory| factory.klass().to_string()) else {
+            return;
+        };
+        let slot = match Role::from_klass(&klass) {
+            Some(Role::Parser) => &mut self.parser,
+            Some(Role::Decoder) => &mut self.decoder,
+            Some(Role::Sink) => &mut self.sink,
+            None => return,
+        };
+        if slot.is_none() {
+            *slot = ActiveElement::from_element(element, &klass);
+        }
+    }
+}
+
+impl fmt::Display for ActiveStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:?} stream:", self.media_type)?;
+        for (role, element) in [
+            ("parser", &self.parser),
+            ("decoder", &self.decoder),
+            ("sink", &self.sink),
+        ] {
+            match element {
+                Some(element) => writeln!(f, "  {role}: {element}")?,
+                None => writeln!(f, "  {role}: -")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Limit for walking along the links of a stream, in case of unexpected loops:
+const MAX_WALK_DEPTH: usize = 64;
+
+// Collect the parser, decoder and sink that were selected for each stream. The streams of a playbin are followed
+// along their links, other pipelines are searched by the klass of their elements:
+pub fn collect(pipeline: &gst::Element) -> Vec<ActiveStream> {
+    if pipeline.find_property("n-video").is_some() {
+        collect_playbin_streams(pipeline)
+    } else {
+        collect_by_klass(pipeline)
+    }
+}
+
+fn collect_playbin_streams(playbin: &gst::Element) -> Vec<ActiveStream> {
+    let mut streams = Vec::new();
+    for (media_type, name) in [
+        (StreamMediaType::Video, "video"),
+        (StreamMediaType::Audio, "audio"),
+        (StreamMediaType::Subtitle, "text"),
+    ] {
+        let count = playbin.property::<i32>(&format!("n-{name}"));
+        let current = playbin.property::<i32>(&format!("current-{name}"));
+        for index in 0..count {
+            // The pad of the stream at the stream combiner, between the decoder and the sink:
+            let Some(pad) = playbin.emit_by_name::<Option<gst::Pad>>(&format!("get-{name}-pad"), &[&index]) else {
+                continue;
+            };
+            let mut stream = ActiveStream::new(media_type);
+            walk(&pad, &mut stream);
+            // Only the current stream of a type is rendered by the sink:
+            if index == current
+                && let Some(combiner) = pad.parent_element()
+            {
+                for src_pad in combiner.src_pads() {
+                    walk(&src_pad, &mut stream);
+                }
+            }
+            streams.push(stream);
+        }
+    }
+    streams
+}
+
+// Follow the links from the pad in its direction, through bins and internally linked pads like those of queues:
+fn walk(pad: &gst::Pad, stream: &mut ActiveStream) {
+    let mut pad = pad.clone();
+    for _ in 0..MAX_WALK_DEPTH {
+        let Some(peer) = linked_pad(&pad) else {
+            return;
+        };
+        let Some(element) = peer.parent_element() else {
+            return;
+        };
+        stream.add(&element);
+        let next = peer.iterate_internal_links().into_iter().flatten().next().or_else(|| {
+            let pads = if peer.direction() == gst::PadDirection::Sink {
+                element.src_pads()
+            } else {
+                element.sink_pads()
+            };
+            // Without internal links the path is only clear for a single pad:
+            match pads.as_slice() {
+                [pad] => Some(pad.clone()),
+                _ => None,
+            }
+        });
+        let Some(next) = next else {
+            return;
+        };
+        pad = next;
+    }
+}
+
+// The pad of the element that is linked to the pad, ghost pads of bins are passed through:
+fn linked_pad(pad: &gst::Pad) -> Option<gst::Pad> {
+    let mut pad = pad.peer()?;
+    for _ in 0..MAX_WALK_DEPTH {
+        if let Some(ghost_pad) = pad.downcast_ref::<gst::GhostPad>() {
+            // Into the bin:
+            pad = ghost_pad.target()?;
+        } else if let Some(ghost_pad) = pad
+            .downcast_ref::<gst::ProxyPad>()
+            .and_then(|proxy_pad| proxy_pad.internal())
+        {
+            // Out of the bin:
+            pad = ghost_pad.peer()?;
+        } else {
+            return Some(pad);
+        }
+    }
+    None
+}
+
+fn collect_by_klass(pipeline: &gst::Element) -> Vec<ActiveStream> {
+    let mut streams: Vec<ActiveStream> = Vec::new();
+    let Some(bin) = pipeline.downcast_ref::<gst::Bin>() else {
+        return streams;
+    };
+
+    for element in bin.iterate_recurse().into_iter().flatten() {
+        // Bins like glsinkbin only wrap the element that does the work:
+        if element.is::<gst::Bin>() {
+            continue;
+        }
+        let Some(klass) = element.factory().map(|factory| factory.klass().to_string()) else {
+            continue;
+        };
+        if Role::from_klass(&klass).is_none() {
+            continue;
+        }
+        let media_type = StreamMediaType::from_klass(&klass).or_else(|| {
+            element
+                .sink_pads()
+                .iter()
+                .find_map(|pad| pad.current_caps())
+                .and_then(|caps| StreamMediaType::from_caps(&caps))
+        });
+        let Some(media_type) = media_type else {
+            continue;
+        };
+
+        let index = match streams.iter().position(|stream| stream.media_type == media_type) {
+            Some(index) => index,
+            None => {
+                streams.push(ActiveStream::new(media_type));
+                streams.len() - 1
+            }
+        };
+        streams[index].add(&element);
+    }
+    streams
+}
+
+pub fn report(streams: &[ActiveStream]) -> String {
+    if streams.is_empty() {
+        return "No active streams\n".to_string();
+    }
+    streams
+        .iter()
+        .map(|stream| stream.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,528 @@
+use anyhow::{Context, Result, bail};
+use log::*;
+use mxl_relm4_components::relm4::gtk::{self, cairo, glib, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, path::Path, rc::Rc};
+
+use glib::clone;
+
+use crate::{misc, ui::player::model::VideoViewData, uri_helpers};
+
+const ANNOTATIONS_FILE_EXTENSION: &str = "annotations.json";
+const ANNOTATIONS_FILE_VERSION: u32 = 1;
+const DEFAULT_DURATION: f64 = 5.0;
+const DEFAULT_LINE_WIDTH: f64 = 3.0;
+const DEFAULT_TEXT_SIZE: f64 = 24.0;
+const ARROW_HEAD_LENGTH: f64 = 16.0;
+// Tolerance in video pixels to hit a shape with the eraser:
+const HIT_TOLERANCE: f64 = 10.0;
+
+pub type SharedAnnotationLayer = Rc<RefCell<AnnotationLayer>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Color {
+    pub red: f64,
+    pub green: f64,
+    pub blue: f64,
+    pub alpha: f64,
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self {
+            red: 1.0,
+            green: 0.0,
+            blue: 0.0,
+            alpha: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Shape {
+    Rectangle { from: Point, to: Point },
+    Arrow { from: Point, to: Point },
+    Freehand { points: Vec<Point> },
+    Text { position: Point, text: String, size: f64 },
+}
+
+impl Shape {
+    // Rectangle around the shape in video coordinates, the text size is approximated by the font size:
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        let points = match self {
+            Shape::Rectangle { from, to } | Shape::Arrow { from, to } => vec![*from, *to],
+            Shape::Freehand { points } => points.clone(),
+            Shape::Text { position, text, size } => vec![
+                Point {
+                    x: position.x,
+                    y: position.y - size,
+                },
+                Point {
+                    x: position.x + size * 0.6 * text.chars().count() as f64,
+                    y: position.y,
+                },
+            ],
+        };
+        let first = points.first()?;
+        Some(points.iter().fold((*first, *first), |(min, max), p| {
+            (
+                Point {
+                    x: min.x.min(p.x),
+                    y: min.y.min(p.y),
+                },
+                Point {
+                    x: max.x.max(p.x),
+                    y: max.y.max(p.y),
+                },
+            )
+        }))
+    }
+
+    // Hit test with a tolerance, e.g. for the eraser:
+    pub fn contains(&self, point: &Point) -> bool {
+        self.bounding_box().is_some_and(|(min, max)| {
+            point.x >= min.x - HIT_TOLERANCE
+                && point.x <= max.x + HIT_TOLERANCE
+                && point.y >= min.y - HIT_TOLERANCE
+                && point.y <= max.y + HIT_TOLERANCE
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub shape: Shape,
+    // Time range in seconds, in which the annotation is visible:
+    pub start: f64,
+    pub end: f64,
+    pub color: Color,
+    pub line_width: f64,
+}
+
+impl Annotation {
+    pub fn is_visible_at(&self, position: f64) -> bool {
+        position >= self.start && position <= self.end
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnnotationTool {
+    #[default]
+    None,
+    Rectangle,
+    Arrow,
+    Freehand,
+    Text,
+    Eraser,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AnnotationFile {
+    version: u32,
+    annotations: Vec<Annotation>,
+}
+
+#[derive(Debug)]
+pub struct AnnotationLayer {
+    annotations: Vec<Annotation>,
+    tool: AnnotationTool,
+    color: Color,
+    line_width: f64,
+    text: String,
+    text_size: f64,
+    duration: f64,
+    position: f64,
+    current: Option<Annotation>,
+    drag_start: Option<(f64, f64)>,
+    hovered: Option<usize>,
+    view: Option<VideoViewData>,
+}
+
+impl Default for AnnotationLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnnotationLayer {
+    pub fn new() -> Self {
+        Self {
+            annotations: Vec::new(),
+            tool: AnnotationTool::None,
+            color: Color::default(),
+            line_width: DEFAULT_LINE_WIDTH,
+            text: String::new(),
+            text_size: DEFAULT_TEXT_SIZE,
+            duration: DEFAULT_DURATION,
+            position: 0.0,
+            current: None,
+            drag_start: None,
+            hovered: None,
+            view: None,
+        }
+    }
+
+    pub fn new_shared() -> SharedAnnotationLayer {
+        Rc::new(RefCell::new(Self::new()))
+    }
+
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    pub fn set_annotations(&mut self, annotations: Vec<Annotation>) {
+        self.annotations = annotations;
+        self.hovered = None;
+    }
+
+    pub fn add(&mut self, annotation: Annotation) {
+        self.annotations.push(annotation);
+    }
+
+    pub fn undo(&mut self) -> Option<Annotation> {
+        self.hovered = None;
+        self.annotations.pop()
+    }
+
+    pub fn clear(&mut self) {
+        self.annotations.clear();
+        self.hovered = None;
+    }
+
+    pub fn tool(&self) -> AnnotationTool {
+        self.tool
+    }
+
+    pub fn set_tool(&mut self, tool: AnnotationTool) {
+        self.tool = tool;
+        self.current = None;
+        self.drag_start = None;
+        self.hovered = None;
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    pub fn set_line_width(&mut self, line_width: f64) {
+        self.line_width = line_width;
+    }
+
+    // The size is in pixels at the current zoom, the text is scaled with the video afterwards:
+    pub fn set_text(&mut self, text: &str, size: f64) {
+        self.text = text.to_string();
+        self.text_size = size;
+    }
+
+    // Duration in seconds, in which new annotations are visible:
+    pub fn set_duration(&mut self, duration: f64) {
+        self.duration = duration;
+    }
+
+    pub fn set_position(&mut self, position: f64) {
+        self.position = position;
+    }
+
+    pub fn visible_annotations(&self) -> impl Iterator<Item = &Annotation> {
+        self.annotations.iter().filter(|a| a.is_visible_at(self.position))
+    }
+
+    pub fn load(&mut self, path: &Path) -> Result<()> {
+        let file = std::fs::File::open(path).with_context(|| format!("Cannot open annotations file {path:?}"))?;
+        let content: AnnotationFile = serde_json::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Cannot parse annotations file {path:?}"))?;
+        // Newer versions may contain shapes, which would be lost when the file is saved again:
+        if content.version > ANNOTATIONS_FILE_VERSION {
+            bail!(
+                "Annotations file {path:?} has the unsupported version {}",
+                content.version
+            );
+        }
+        debug!("Loaded {} annotations from {path:?}", content.annotations.len());
+        self.set_annotations(content.annotations);
+        Ok(())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = AnnotationFile {
+            version: ANNOTATIONS_FILE_VERSION,
+            annotations: self.annotations.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&content).context("Cannot serialize annotations")?;
+        misc::write_file_replacing(path, &json).with_context(|| format!("Cannot write annotations file {path:?}"))?;
+        debug!("Saved {} annotations to {path:?}", self.annotations.len());
+        Ok(())
+    }
+
+    // Load the annotations stored next to the media file, no annotations are loaded if no file exists:
+    pub fn load_for_uri(&mut self, uri: &str) -> Result<()> {
+        let path = uri_helpers::sidecar_path_from_uri(uri, ANNOTATIONS_FILE_EXTENSION)?;
+        if path.exists() {
+            self.load(&path)
+        } else {
+            self.clear();
+            Ok(())
+        }
+    }
+
+    pub fn save_for_uri(&self, uri: &str) -> Result<()> {
+        let path = uri_helpers::sidecar_path_from_uri(uri, ANNOTATIONS_FILE_EXTENSION)?;
+        self.save(&path)
+    }
+
+    // Draw all visible annotations, must be called from the draw callback of the player component:
+    pub fn draw(&mut self, context: &cairo::Context, view: &VideoViewData) {
+        self.view = Some(view.coordinate_mapping());
+
+        context.set_line_cap(cairo::LineCap::Round);
+        context.set_line_join(cairo::LineJoin::Round);
+
+        for (index, annotation) in self.annotations.iter().enumerate() {
+            if annotation.is_visible_at(self.position) {
+                let highlight = self.hovered == Some(index);
+                if let Err(error) = Self::draw_annotation(context, view, annotation, highlight) {
+                    warn!("Cannot draw annotation - {error:?}");
+                }
+            }
+        }
+        if let Some(current) = &self.current
+            && let Err(error) = Self::draw_annotation(context, view, current, false)
+        {
+            warn!("Cannot draw annotation - {error:?}");
+        }
+    }
+
+    fn draw_annotation(
+        context: &cairo::Context,
+        view: &VideoViewData,
+        annotation: &Annotation,
+        highlight: bool,
+    ) -> Result<()> {
+        let to_widget = |p: &Point| view.video_to_widget(p.x, p.y);
+        let color = &annotation.color;
+        context.set_source_rgba(color.red, color.green, color.blue, color.alpha);
+        context.set_line_width(if highlight {
+            annotation.line_width * 2.0
+        } else {
+            annotation.line_width
+        });
+        context.new_path();
+
+        match &annotation.shape {
+            Shape::Rectangle { from, to } => {
+                if let (Some((x1, y1)), Some((x2, y2))) = (to_widget(from), to_widget(to)) {
+                    context.rectangle(x1.min(x2), y1.min(y2), (x2 - x1).abs(), (y2 - y1).abs());
+                    context.stroke()?;
+                }
+            }
+            Shape::Arrow { from, to } => {
+                if let (Some((x1, y1)), Some((x2, y2))) = (to_widget(from), to_widget(to)) {
+                    let angle = (y2 - y1).atan2(x2 - x1);
+                    let spread = std::f64::consts::PI / 7.0;
+                    context.move_to(x1, y1);
+                    context.line_to(x2, y2);
+                    context.move_to(
+                        x2 - ARROW_HEAD_LENGTH * (angle - spread).cos(),
+                        y2 - ARROW_HEAD_LENGTH * (angle - spread).sin(),
+                    );
+                    context.line_to(x2, y2);
+                    context.line_to(
+                        x2 - ARROW_HEAD_LENGTH * (angle + spread).cos(),
+                        y2 - ARROW_HEAD_LENGTH * (angle + spread).sin(),
+                    );
+                    context.stroke()?;
+                }
+            }
+            Shape::Freehand { points } => {
+                let mut points = points.iter().filter_map(to_widget);
+                if let Some((x, y)) = points.next() {
+                    context.move_to(x, y);
+                    points.for_each(|(x, y)| context.line_to(x, y));
+                    context.stroke()?;
+                }
+            }
+            Shape::Text { position, text, size } => {
+                // The size is in video pixels, so that the text is zoomed like the video:
+                if let (Some((x, y)), Some(scale)) = (to_widget(position), view.video_scale()) {
+                    context.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Bold);
+                    context.set_font_size(*size * scale);
+                    context.move_to(x, y);
+                    context.show_text(text)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn to_video(&self, x: f64, y: f64) -> Option<Point> {
+        let (x, y) = self.view.as_ref()?.widget_to_video(x, y)?;
+        Some(Point { x, y })
+    }
+
+    fn new_annotation(&self, shape: Shape) -> Annotation {
+        Annotation {
+            shape,
+            start: self.position,
+            end: self.position + self.duration,
+            color: self.color,
+            line_width: self.line_width,
+        }
+    }
+
+    fn annotation_at(&self, point: &Point) -> Option<usize> {
+        self.annotations
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, a)| a.is_visible_at(self.position) && a.shape.contains(point))
+            .map(|(index, _)| index)
+    }
+
+    // Handle the start of a drag on the drawing overlay in widget coordinates, returns true if a redraw is required:
+    pub fn drag_begin(&mut self, x: f64, y: f64) -> bool {
+        let Some(point) = self.to_video(x, y) else {
+            return false;
+        };
+        self.drag_start = Some((x, y));
+        let shape = match self.tool {
+            AnnotationTool::None => return false,
+            AnnotationTool::Rectangle => Shape::Rectangle { from: point, to: point },
+            AnnotationTool::Arrow => Shape::Arrow { from: point, to: point },
+            AnnotationTool::Freehand => Shape::Freehand { points: vec![point] },
+            AnnotationTool::Text => {
+                // The text size is chosen for the current zoom and stored in video pixels:
+                let Some(scale) = self.view.as_ref().and_then(|view| view.video_scale()) else {
+                    return false;
+                };
+                if self.text.is_empty() || scale <= 0.0 {
+                    return false;
+                }
+                Shape::Text {
+                    position: point,
+                    text: self.text.clone(),
+                    size: self.text_size / scale,
+                }
+            }
+            AnnotationTool::Eraser => {
+                if let Some(index) = self.annotation_at(&point) {
+                    let annotation = self.annotations.remove(index);
+                    trace!("Erased annotation {annotation:?}");
+                    self.hovered = None;
+                    return true;
+                }
+                return false;
+            }
+        };
+        self.current = Some(self.new_annotation(shape));
+        true
+    }
+
+    // Handle a drag update with the offset relative to the start point, returns true if a redraw is required:
+    pub fn drag_update(&mut self, offset_x: f64, offset_y: f64) -> bool {
+        let Some((start_x, start_y)) = self.drag_start else {
+            return false;
+        };
+        let Some(point) = self.to_video(start_x + offset_x, start_y + offset_y) else {
+            return false;
+        };
+        if let Some(current) = &mut self.current {
+            match &mut current.shape {
+                Shape::Rectangle { to, .. } | Shape::Arrow { to, .. } => *to = point,
+                Shape::Freehand { points } => points.push(point),
+                Shape::Text { position, .. } => *position = point,
+            }
+            return true;
+        }
+        false
+    }
+
+    // Handle the end of a drag and add the drawn annotation, returns true if a redraw is required:
+    pub fn drag_end(&mut self, offset_x: f64, offset_y: f64) -> bool {
+        let redraw = self.drag_update(offset_x, offset_y);
+        self.drag_start = None;
+        if let Some(current) = self.current.take() {
+            debug!("Add annotation {current:?}");
+            self.annotations.push(current);
+            return true;
+        }
+        redraw
+    }
+
+    // Handle a pointer motion on the drawing overlay, returns true if a redraw is required:
+    pub fn motion(&mut self, x: f64, y: f64) -> bool {
+        let hovered = if self.tool == AnnotationTool::Eraser {
+            self.to_video(x, y).and_then(|point| self.annotation_at(&point))
+        } else {
+            None
+        };
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            return true;
+        }
+        false
+    }
+
+    // Create a drag gesture to be used as drag_gesture of the player component initialization:
+    pub fn new_drag_gesture(layer: &SharedAnnotationLayer) -> gtk::GestureDrag {
+        let drag = gtk::GestureDrag::builder().button(gtk::gdk::BUTTON_PRIMARY).build();
+
+        let redraw = |gesture: &gtk::GestureDrag, redraw: bool| {
+            if redraw && let Some(widget) = gesture.widget() {
+                widget.queue_draw();
+            }
+        };
+
+        drag.connect_drag_begin(clone!(
+            #[weak]
+            layer,
+            move |gesture, x, y| {
+                let required = layer.borrow_mut().drag_begin(x, y);
+                redraw(gesture, required);
+            }
+        ));
+        drag.connect_drag_update(clone!(
+            #[weak]
+            layer,
+            move |gesture, x, y| {
+                let required = layer.borrow_mut().drag_update(x, y);
+                redraw(gesture, required);
+            }
+        ));
+        drag.connect_drag_end(clone!(
+            #[weak]
+            layer,
+            move |gesture, x, y| {
+                let required = layer.borrow_mut().drag_end(x, y);
+                redraw(gesture, required);
+            }
+        ));
+
+        drag
+    }
+
+    // Create a motion tracker to be used as motion_tracker of the player component initialization:
+    pub fn new_motion_tracker(layer: &SharedAnnotationLayer) -> gtk::EventControllerMotion {
+        let tracker = gtk::EventControllerMotion::builder().build();
+
+        tracker.connect_motion(clone!(
+            #[weak]
+            layer,
+            move |tracker, x, y| {
+                if layer.borrow_mut().motion(x, y)
+                    && let Some(widget) = tracker.widget()
+                {
+                    widget.queue_draw();
+                }
+            }
+        ));
+
+        tracker
+    }
+}
@@ -0,0 +1,424 @@
+use anyhow::{Context, Result, anyhow, bail};
+use gst::prelude::*;
+use log::*;
+use mxl_relm4_components::relm4::gtk::gio;
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::tag_helpers::{self, MEDIA_ID_TAGS};
+
+const DISCOVERER_TIMEOUT_SECS: u64 = 10;
+const PREROLL_TIMEOUT_SECS: u64 = 30;
+const BUS_POLL_INTERVAL_MS: u64 = 100;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClipExportMode {
+    // Try a stream copy first and fall back to a re-encode if the streams cannot be remuxed:
+    #[default]
+    Auto,
+    StreamCopy,
+    ReEncode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipContainer {
+    Mp4,
+    QuickTime,
+    Matroska,
+    MpegTs,
+}
+
+impl ClipContainer {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "mp4" | "m4v" => Some(ClipContainer::Mp4),
+            "mov" => Some(ClipContainer::QuickTime),
+            "mkv" => Some(ClipContainer::Matroska),
+            "ts" | "mts" | "m2ts" => Some(ClipContainer::MpegTs),
+            _ => None,
+        }
+    }
+
+    fn muxer_name(&self) -> &'static str {
+        match self {
+            ClipContainer::Mp4 => "mp4mux",
+            ClipContainer::QuickTime => "qtmux",
+            ClipContainer::Matroska => "matroskamux",
+            ClipContainer::MpegTs => "mpegtsmux",
+        }
+    }
+
+    fn caps(&self) -> gst::Caps {
+        match self {
+            ClipContainer::Mp4 => gst::Caps::builder("video/quicktime").field("variant", "iso").build(),
+            ClipContainer::QuickTime => gst::Caps::builder("video/quicktime").build(),
+            ClipContainer::Matroska => gst::Caps::builder("video/x-matroska").build(),
+            ClipContainer::MpegTs => gst::Caps::builder("video/mpegts")
+                .field("systemstream", true)
+                .field("packetsize", 188i32)
+                .build(),
+        }
+    }
+
+    fn encoding_profile(&self) -> gst_pbutils::EncodingContainerProfile {
+        let video_profile = gst_pbutils::EncodingVideoProfile::builder(&gst::Caps::builder("video/x-h264").build())
+            .presence(0)
+            .build();
+        let audio_profile = gst_pbutils::EncodingAudioProfile::builder(
+            &gst::Caps::builder("audio/mpeg").field("mpegversion", 4i32).build(),
+        )
+        .presence(0)
+        .build();
+        gst_pbutils::EncodingContainerProfile::builder(&self.caps())
+            .name("clip-export")
+            .add_profile(video_profile)
+            .add_profile(audio_profile)
+            .build()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ClipExportSettings {
+    pub uri: String,
+    // Start and end of the clip in seconds:
+    pub start: f64,
+    pub end: f64,
+    pub output: PathBuf,
+    pub mode: ClipExportMode,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClipExportCancellation(Arc<AtomicBool>);
+
+impl ClipExportCancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+// Export the clip on a blocking thread, the progress callback is called with values between 0.0 and 1.0:
+pub fn export_clip_async<F>(
+    settings: ClipExportSettings,
+    progress: F,
+    cancellation: ClipExportCancellation,
+) -> gio::JoinHandle<Result<PathBuf>>
+where
+    F: Fn(f64) + Send + 'static,
+{
+    gio::spawn_blocking(move || export_clip(&settings, &progress, &cancellation).map(|_| settings.output))
+}
+
+pub fn export_clip(
+    settings: &ClipExportSettings,
+    progress: &dyn Fn(f64),
+    cancellation: &ClipExportCancellation,
+) -> Result<()> {
+    if settings.start < 0.0 || settings.end <= settings.start {
+        bail!("Invalid clip range {} - {}", settings.start, settings.end);
+    }
+    let container = ClipContainer::from_path(&settings.output)
+        .with_context(|| format!("Unknown container format of {:?}", settings.output))?;
+    let tags = media_id_tags(&settings.uri);
+
+    debug!(
+        "Export clip {} - {} of '{}' to {:?} ({:?})",
+        settings.start, settings.end, settings.uri, settings.output, settings.mode
+    );
+    match settings.mode {
+        ClipExportMode::StreamCopy => run_export(settings, container, &tags, false, progress, cancellation),
+        ClipExportMode::ReEncode => run_export(settings, container, &tags, true, progress, cancellation),
+        ClipExportMode::Auto => match run_export(settings, container, &tags, false, progress, cancellation) {
+            Ok(()) => Ok(()),
+            Err(error) if cancellation.is_cancelled() => Err(error),
+            Err(error) => {
+                warn!("Cannot export clip with a stream copy, re-encode it: {error:?}");
+                progress(0.0);
+                run_export(settings, container, &tags, true, progress, cancellation)
+            }
+        },
+    }
+}
+
+// Collect the media identification tags of the source, so that they can be written to the clip:
+fn media_id_tags(uri: &str) -> Vec<(&'static str, String)> {
+    match gst_pbutils::Discoverer::new(gst::ClockTime::from_seconds(DISCOVERER_TIMEOUT_SECS))
+        .and_then(|discoverer| discoverer.discover_uri(uri))
+    {
+        Ok(info) => MEDIA_ID_TAGS
+            .iter()
+            .filter_map(|name| tag_helpers::media_info_get_global_tag(name, &info).map(|value| (*name, value)))
+            .collect(),
+        Err(error) => {
+            warn!("Cannot discover tags of '{uri}': {error:?}");
+            Vec::new()
+        }
+    }
+}
+
+fn run_export(
+    settings: &ClipExportSettings,
+    container: ClipContainer,
+    tags: &[(&'static str, String)],
+    re_encode: bool,
+    progress: &dyn Fn(f64),
+    cancellation: &ClipExportCancellation,
+) -> Result<()> {
+    let pipeline = gst::Pipeline::builder().name("clip-export").build();
+    let result = build_pipeline(&pipeline, settings, container, re_encode)
+        .and_then(|_| run_pipeline(&pipeline, settings, tags, re_encode, progress, cancellation));
+    pipeline.set_state(gst::State::Null).ok();
+
+    if result.is_err() && settings.output.exists() {
+        if let Err(error) = std::fs::remove_file(&settings.output) {
+            warn!("Cannot remove incomplete clip {:?}: {error:?}", settings.output);
+        }
+    }
+    result
+}
+
+fn build_pipeline(
+    pipeline: &gst::Pipeline,
+    settings: &ClipExportSettings,
+    container: ClipContainer,
+    re_encode: bool,
+) -> Result<()> {
+    let location = settings
+        .output
+        .to_str()
+        .with_context(|| format!("Invalid output path {:?}", settings.output))?;
+    let sink = gst::ElementFactory::make("filesink")
+        .property("location", location)
+        .build()?;
+
+    let (source, demux, target) = if re_encode {
+        let source = gst::ElementFactory::make("uridecodebin")
+            .property("uri", &settings.uri)
+            .build()?;
+        let encodebin = gst::ElementFactory::make("encodebin")
+            .property("profile", &container.encoding_profile())
+            .build()?;
+        (source, None, encodebin)
+    } else {
+        let source = gst::ElementFactory::make("urisourcebin")
+            .property("uri", &settings.uri)
+            .build()?;
+        let parsebin = gst::ElementFactory::make("parsebin").build()?;
+        let muxer = gst::ElementFactory::make(container.muxer_name()).build()?;
+        (source, Some(parsebin), muxer)
+    };
+
+    pipeline.add_many([&source, &target, &sink])?;
+    target.link(&sink)?;
+
+    let pad_owner = if let Some(demux) = demux {
+        pipeline.add(&demux)?;
+        let demux_weak = demux.downgrade();
+        source.connect_pad_added(move |_, pad| {
+            if let Some(demux) = demux_weak.upgrade()
+                && let Some(sink_pad) = demux.static_pad("sink")
+                && !sink_pad.is_linked()
+                && let Err(error) = pad.link(&sink_pad)
+            {
+                error!("Cannot link source to parser: {error:?}");
+            }
+        });
+        demux
+    } else {
+        source
+    };
+
+    let pipeline_weak = pipeline.downgrade();
+    pad_owner.connect_pad_added(move |element, pad| {
+        if let Some(pipeline) = pipeline_weak.upgrade()
+            && let Err(error) = link_stream(&pipeline, &target, pad, re_encode)
+        {
+            gst::element_error!(element, gst::StreamError::Format, ("{error:?}"));
+        }
+    });
+    Ok(())
+}
+
+fn link_stream(pipeline: &gst::Pipeline, target: &gst::Element, pad: &gst::Pad, re_encode: bool) -> Result<()> {
+    let caps = pad.current_caps().unwrap_or_else(|| pad.query_caps(None));
+    let media_type = caps.structure(0).map(|s| s.name().to_string()).unwrap_or_default();
+
+    let sink_pad = if media_type.starts_with("video/") || media_type.starts_with("audio/") {
+        if re_encode {
+            let template = if media_type.starts_with("video/") {
+                "video_%u"
+            } else {
+                "audio_%u"
+            };
+            target.request_pad_simple(template)
+        } else {
+            target.compatible_pad(pad, Some(&caps))
+        }
+    } else {
+        None
+    };
+
+    if let Some(sink_pad) = sink_pad {
+        debug!("Link stream {media_type} to {}", sink_pad.name());
+        pad.link(&sink_pad)
+            .with_context(|| format!("Cannot link stream {media_type} to {}", target.name()))?;
+    } else if media_type.starts_with("video/") {
+        // The video stream is mandatory for a clip:
+        return Err(anyhow!("{} cannot handle the stream {caps}", target.name()));
+    } else {
+        debug!("Drop unsupported stream {media_type}");
+        let fakesink = gst::ElementFactory::make("fakesink").property("async", false).build()?;
+        pipeline.add(&fakesink)?;
+        fakesink.sync_state_with_parent()?;
+        pad.link(&fakesink.static_pad("sink").context("fakesink has no sink pad")?)?;
+    }
+    Ok(())
+}
+
+fn run_pipeline(
+    pipeline: &gst::Pipeline,
+    settings: &ClipExportSettings,
+    tags: &[(&'static str, String)],
+    re_encode: bool,
+    progress: &dyn Fn(f64),
+    cancellation: &ClipExportCancellation,
+) -> Result<()> {
+    let bus = pipeline.bus().context("Pipeline has no bus")?;
+    let start = clock_time_from_seconds(settings.start);
+    let end = clock_time_from_seconds(settings.end);
+
+    pipeline
+        .set_state(gst::State::Paused)
+        .context("Cannot pause the export pipeline")?;
+    wait_for_async_done(&bus, cancellation)?;
+
+    apply_tags(pipeline, tags, settings.output.as_path());
+
+    // A stream copy can only start at a keyframe, a re-encode cuts exactly:
+    let seek_flags = if re_encode {
+        gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE
+    } else {
+        gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT | gst::SeekFlags::SNAP_BEFORE
+    };
+    pipeline
+        .seek(1.0, seek_flags, gst::SeekType::Set, start, gst::SeekType::Set, end)
+        .context("Cannot seek to the start of the clip")?;
+    wait_for_async_done(&bus, cancellation)?;
+
+    pipeline
+        .set_state(gst::State::Playing)
+        .context("Cannot start the export pipeline")?;
+
+    let duration = (end - start).nseconds() as f64;
+    loop {
+        if cancellation.is_cancelled() {
+            bail!("Clip export cancelled");
+        }
+        if let Some(message) = bus.timed_pop(gst::ClockTime::from_mseconds(BUS_POLL_INTERVAL_MS)) {
+            match message.view() {
+                gst::MessageView::Eos(_) => break,
+                gst::MessageView::Error(err) => {
+                    return Err(anyhow!(
+                        "Clip export failed in {}: {} ({:?})",
+                        err.src().map(|s| s.path_string()).unwrap_or_default(),
+                        err.error(),
+                        err.debug()
+                    ));
+                }
+                gst::MessageView::Warning(warning) => {
+                    warn!("Clip export: {} ({:?})", warning.error(), warning.debug());
+                }
+                _ => (),
+            }
+        }
+        if let Some(position) = pipeline.query_position::<gst::ClockTime>() {
+            let done = position.saturating_sub(start).nseconds() as f64;
+            progress((done / duration).clamp(0.0, 1.0));
+        }
+    }
+    progress(1.0);
+    debug!("Exported clip to {:?}", settings.output);
+    Ok(())
+}
+
+fn wait_for_async_done(bus: &gst::Bus, cancellation: &ClipExportCancellation) -> Result<()> {
+    let timeout = std::time::Instant::now() + std::time::Duration::from_secs(PREROLL_TIMEOUT_SECS);
+    while std::time::Instant::now() < timeout {
+        if cancellation.is_cancelled() {
+            bail!("Clip export cancelled");
+        }
+        if let Some(message) = bus.timed_pop_filtered(
+            gst::ClockTime::from_mseconds(BUS_POLL_INTERVAL_MS),
+            &[gst::MessageType::AsyncDone, gst::MessageType::Error],
+        ) {
+            match message.view() {
+                gst::MessageView::AsyncDone(_) => return Ok(()),
+                gst::MessageView::Error(err) => {
+                    return Err(anyhow!(
+                        "Cannot prepare the clip export in {}: {} ({:?})",
+                        err.src().map(|s| s.path_string()).unwrap_or_default(),
+                        err.error(),
+                        err.debug()
+                    ));
+                }
+                _ => (),
+            }
+        }
+    }
+    bail!("Timeout while preparing the clip export")
+}
+
+// Write the media identification tags as extended comments, like they are read in the playlist.
+// Only muxers with the TagSetter interface can store them, e.g. mpegtsmux cannot:
+fn apply_tags(pipeline: &gst::Pipeline, tags: &[(&'static str, String)], output: &Path) {
+    if tags.is_empty() {
+        return;
+    }
+    let mut written = false;
+    for element in pipeline
+        .iterate_all_by_interface(gst::TagSetter::static_type())
+        .into_iter()
+        .flatten()
+    {
+        let is_muxer = element
+            .factory()
+            .is_some_and(|factory| factory.klass().contains("Muxer"));
+        if let Some(setter) = element.dynamic_cast_ref::<gst::TagSetter>()
+            && is_muxer
+        {
+            for (name, value) in tags {
+                trace!("Set tag {name}={value} on {}", element.name());
+                setter.add_tag::<gst::tags::ExtendedComment>(
+                    &format!("{name}={value}").as_str(),
+                    gst::TagMergeMode::Append,
+                );
+            }
+            written = true;
+        }
+    }
+    if !written {
+        warn!(
+            "The container of {output:?} cannot store the media identification tags, the clip is exported without: {}",
+            tags.iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+fn clock_time_from_seconds(seconds: f64) -> gst::ClockTime {
+    gst::ClockTime::from_nseconds((seconds.max(0.0) * 1_000_000_000_f64) as u64)
+}
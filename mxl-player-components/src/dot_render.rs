@@ -0,0 +1,44 @@
+use anyhow::{Context, Result, anyhow};
+use layout::{
+    backends::svg::SVGWriter,
+    gv::{DotParser, GraphBuilder},
+};
+use std::path::Path;
+
+// Render a dot graph as SVG without Graphviz. Clusters are not supported and flattened: GStreamer draws every
+// element and bin as a cluster, so their boxes and labels are missing and only the pads and links are drawn:
+pub fn dot_to_svg(dot: &str) -> Result<String> {
+    let dot = normalize_numbers(dot);
+    let mut parser = DotParser::new(&dot);
+    let graph = parser
+        .process()
+        .map_err(|error| anyhow!("Cannot parse dot graph: {error}"))?;
+    let mut builder = GraphBuilder::new();
+    builder.visit_graph(&graph);
+    let mut visual_graph = builder.get();
+    let mut svg = SVGWriter::new();
+    visual_graph.do_it(false, false, false, &mut svg);
+    Ok(svg.finalize())
+}
+
+pub fn render_svg_file(dot_path: &Path, svg_path: &Path) -> Result<()> {
+    let dot = std::fs::read_to_string(dot_path).with_context(|| format!("Cannot read dot file {dot_path:?}"))?;
+    let svg = dot_to_svg(&dot).with_context(|| format!("Cannot render dot file {dot_path:?}"))?;
+    std::fs::write(svg_path, svg).with_context(|| format!("Cannot write SVG file {svg_path:?}"))?;
+    Ok(())
+}
+
+// The parser does not accept numbers without a leading zero like 'nodesep=.1', which GStreamer writes:
+fn normalize_numbers(dot: &str) -> String {
+    let mut normalized = String::with_capacity(dot.len());
+    let mut previous = None;
+    let mut chars = dot.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '.' && previous == Some('=') && chars.peek().is_some_and(|next| next.is_ascii_digit()) {
+            normalized.push('0');
+        }
+        normalized.push(c);
+        previous = Some(c);
+    }
+    normalized
+}
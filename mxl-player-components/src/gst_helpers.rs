@@ -1,6 +1,28 @@
+use anyhow::{Context, Result, bail};
 use gst::glib;
 use log::*;
-use std::path::Path;
+use mxl_base::logging::{LogRotation, RotatingLogFile};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex, RwLock,
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+};
+
+#[cfg(feature = "investigator")]
+const GST_DEBUG_LOG_FILE_NAME: &str = "gstreamer.log";
+const GST_DEBUG_LOG_MAX_SIZE: u64 = 50 * 1024 * 1024;
+const GST_DEBUG_LOG_KEEP_FILES: usize = 3;
+// Lines, which are not written yet. Further lines are dropped, so that a slow disk does not use up the memory:
+const GST_DEBUG_LOG_QUEUE_SIZE: usize = 10_000;
+const DEFAULT_THRESHOLD: gst::DebugLevel = gst::DebugLevel::Warning;
+const ENV_NAME_GST_DEBUG: &str = "GST_DEBUG";
+
+static GST_DEBUG_SPEC: Mutex<Option<String>> = Mutex::new(None);
+static GST_DEBUG_LOG: RwLock<Option<GstDebugLog>> = RwLock::new(None);
 
 pub fn init(cache_dir: &Path) {
     // Set logger for GStreamer:
@@ -15,6 +37,7 @@ pub fn init(cache_dir: &Path) {
             };
         }
         let module_path = get_module_path!("gst");
+        let above_default_threshold = level > DEFAULT_THRESHOLD;
 
         let (gst_level, level) = match &level {
             gst::DebugLevel::None => ("none", log::Level::Trace),
@@ -31,6 +54,18 @@ pub fn init(cache_dir: &Path) {
 
         let target = format!("{gst_level}|{module_path}");
 
+        if let Some(log_file) = GST_DEBUG_LOG.read().unwrap().as_ref() {
+            log_file.send(format!(
+                "{} {gst_level:7} {module_path} {file}:{line} {}",
+                chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.6f"),
+                message.get().unwrap_or(glib::gstr!("Empty message").into())
+            ));
+            // The raised thresholds are meant for the log file, the application log only gets the messages of init():
+            if above_default_threshold {
+                return;
+            }
+        }
+
         log::logger().log(
             &log::RecordBuilder::new()
                 .args(format_args!(
@@ -54,7 +89,165 @@ pub fn init(cache_dir: &Path) {
     unsafe { std::env::set_var("GST_REGISTRY", registry) };
 
     // Set debug level before gstreamer initialization
-    if gst::log::get_default_threshold() < gst::DebugLevel::Warning {
-        gst::log::set_default_threshold(gst::DebugLevel::Warning);
+    if gst::log::get_default_threshold() < DEFAULT_THRESHOLD {
+        gst::log::set_default_threshold(DEFAULT_THRESHOLD);
+    }
+}
+
+fn parse_gst_debug_level(level: &str) -> Option<gst::DebugLevel> {
+    Some(match level.trim().to_ascii_lowercase().as_str() {
+        "0" | "none" => gst::DebugLevel::None,
+        "1" | "error" => gst::DebugLevel::Error,
+        "2" | "warning" => gst::DebugLevel::Warning,
+        "3" | "fixme" => gst::DebugLevel::Fixme,
+        "4" | "info" => gst::DebugLevel::Info,
+        "5" | "debug" => gst::DebugLevel::Debug,
+        "6" | "log" => gst::DebugLevel::Log,
+        "7" | "trace" => gst::DebugLevel::Trace,
+        "9" | "memdump" => gst::DebugLevel::Memdump,
+        _ => return None,
+    })
+}
+
+// Parse a GST_DEBUG style list like "play:6,decodebin*:5", entries without a category set the default threshold:
+pub fn parse_gst_debug(spec: &str) -> Result<Vec<(Option<String>, gst::DebugLevel)>> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (category, level) = match entry.rsplit_once(':') {
+                Some((category, level)) => (Some(category.trim()), level),
+                None => (None, entry),
+            };
+            if category.is_some_and(str::is_empty) {
+                bail!("Missing category in GStreamer debug entry '{entry}'");
+            }
+            let level = parse_gst_debug_level(level)
+                .with_context(|| format!("Invalid level in GStreamer debug entry '{entry}'"))?;
+            Ok((category.map(str::to_string), level))
+        })
+        .collect()
+}
+
+// Set the GStreamer debug thresholds at runtime, this replaces the thresholds of a previous call:
+pub fn set_gst_debug(spec: &str) -> Result<()> {
+    let entries = parse_gst_debug(spec)?;
+    debug!("Set GStreamer debug thresholds to '{spec}'");
+    gst::log::set_threshold_from_string(spec, true);
+    // Keep the default threshold of init(), if the list does not override it:
+    if entries.iter().all(|(category, _)| category.is_some()) {
+        gst::log::set_default_threshold(DEFAULT_THRESHOLD);
+    }
+    *GST_DEBUG_SPEC.lock().unwrap() = (!entries.is_empty()).then(|| spec.to_string());
+    Ok(())
+}
+
+// Restore the thresholds of init() and the GST_DEBUG environment variable:
+pub fn reset_gst_debug() {
+    debug!("Reset GStreamer debug thresholds");
+    gst::log::set_threshold_from_string("", true);
+    gst::log::set_default_threshold(DEFAULT_THRESHOLD);
+    if let Ok(spec) = std::env::var(ENV_NAME_GST_DEBUG) {
+        gst::log::set_threshold_from_string(&spec, false);
+    }
+    *GST_DEBUG_SPEC.lock().unwrap() = None;
+}
+
+// The list set with set_gst_debug():
+pub fn gst_debug() -> Option<String> {
+    GST_DEBUG_SPEC.lock().unwrap().clone()
+}
+
+#[cfg(feature = "investigator")]
+pub fn proc_dir_gst_debug_log_file() -> PathBuf {
+    mxl_investigator::proc_dir::proc_dir().join(GST_DEBUG_LOG_FILE_NAME)
+}
+
+// Write all GStreamer log messages additionally into a separate file, which is rotated when it gets too large:
+pub fn set_gst_debug_log_file(path: Option<&Path>) -> Result<()> {
+    let log_file = path.map(GstDebugLog::open).transpose()?;
+    if let Some(log_file) = &log_file {
+        debug!("Write GStreamer log messages to {:?}", log_file.path);
+    }
+    // The previous log file is dropped without the lock, because it waits for its writer thread:
+    let previous = std::mem::replace(&mut *GST_DEBUG_LOG.write().unwrap(), log_file);
+    drop(previous);
+    Ok(())
+}
+
+pub fn gst_debug_log_file() -> Option<PathBuf> {
+    GST_DEBUG_LOG
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|log_file| log_file.path.clone())
+}
+
+// The lines are written by a separate thread, so that the streaming threads do not wait for each other and the disk:
+struct GstDebugLog {
+    path: PathBuf,
+    sender: Option<mpsc::SyncSender<String>>,
+    dropped: Arc<AtomicUsize>,
+    writer: Option<std::thread::JoinHandle<()>>,
+}
+
+impl GstDebugLog {
+    fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Cannot create directory {parent:?}"))?;
+        }
+        let rotation = LogRotation {
+            max_size: Some(GST_DEBUG_LOG_MAX_SIZE),
+            keep: GST_DEBUG_LOG_KEEP_FILES,
+            ..Default::default()
+        };
+        let mut log_file = RotatingLogFile::open(path, rotation)
+            .with_context(|| format!("Cannot open GStreamer log file {path:?}"))?;
+        let (sender, receiver) = mpsc::sync_channel::<String>(GST_DEBUG_LOG_QUEUE_SIZE);
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let writer = std::thread::Builder::new()
+            .name("gst_debug_log".to_string())
+            .spawn({
+                let dropped = dropped.clone();
+                move || {
+                    while let Ok(line) = receiver.recv() {
+                        _ = writeln!(log_file, "{line}");
+                        // Flush and rotate when all pending lines are written:
+                        for line in receiver.try_iter() {
+                            _ = writeln!(log_file, "{line}");
+                        }
+                        let dropped = dropped.swap(0, Ordering::Relaxed);
+                        if dropped > 0 {
+                            _ = writeln!(log_file, "{dropped} GStreamer log messages were dropped");
+                        }
+                        _ = log_file.flush();
+                    }
+                }
+            })
+            .context("Cannot start the GStreamer log file writer")?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            sender: Some(sender),
+            dropped,
+            writer: Some(writer),
+        })
+    }
+
+    fn send(&self, line: String) {
+        if let Some(sender) = &self.sender
+            && let Err(mpsc::TrySendError::Full(_)) = sender.try_send(line)
+        {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Drop for GstDebugLog {
+    // Closing the channel ends the writer thread after the pending lines are written:
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(writer) = self.writer.take() {
+            _ = writer.join();
+        }
     }
 }
@@ -0,0 +1,254 @@
+use anyhow::{Context, Result, anyhow, bail};
+use gst::prelude::*;
+use log::*;
+use mxl_relm4_components::relm4::gtk::gio;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::{misc, uri_helpers};
+
+const CACHE_DIR_NAME: &str = "keyframes";
+const KEYFRAME_INDEX_FILE_VERSION: u32 = 1;
+const BUILD_TIMEOUT_SECS: u64 = 600;
+const BUS_POLL_INTERVAL_MS: u64 = 100;
+// Tolerance in seconds to skip the keyframe at the current position when stepping to the next or previous one:
+const STEP_TOLERANCE: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekDirection {
+    Forward,
+    Backward,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeyframeIndexFile {
+    version: u32,
+    keyframes: Vec<f64>,
+}
+
+// Sorted positions in seconds of all keyframes of the first video stream of a media:
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct KeyframeIndex {
+    keyframes: Vec<f64>,
+}
+
+impl KeyframeIndex {
+    pub fn new(mut keyframes: Vec<f64>) -> Self {
+        keyframes.sort_by(|a, b| a.total_cmp(b));
+        keyframes.dedup();
+        Self { keyframes }
+    }
+
+    pub fn keyframes(&self) -> &[f64] {
+        &self.keyframes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keyframes.len()
+    }
+
+    // The last keyframe at or before the position, which is the keyframe where decoding starts:
+    pub fn at_or_before(&self, position: f64) -> Option<f64> {
+        let index = self.keyframes.partition_point(|k| *k <= position);
+        index.checked_sub(1).map(|i| self.keyframes[i])
+    }
+
+    pub fn nearest(&self, position: f64) -> Option<f64> {
+        let index = self.keyframes.partition_point(|k| *k < position);
+        let after = self.keyframes.get(index).copied();
+        let before = index.checked_sub(1).map(|i| self.keyframes[i]);
+        match (before, after) {
+            (Some(before), Some(after)) => Some(if position - before <= after - position {
+                before
+            } else {
+                after
+            }),
+            (before, after) => before.or(after),
+        }
+    }
+
+    pub fn step(&self, position: f64, direction: SeekDirection) -> Option<f64> {
+        match direction {
+            SeekDirection::Forward => self.keyframes.iter().find(|k| **k > position + STEP_TOLERANCE),
+            SeekDirection::Backward => self.keyframes.iter().rev().find(|k| **k < position - STEP_TOLERANCE),
+        }
+        .copied()
+    }
+
+    pub fn cache_dir() -> PathBuf {
+        misc::cache_dir().join(CACHE_DIR_NAME)
+    }
+
+    pub fn cache_path(uri: &str) -> PathBuf {
+        Self::cache_dir().join(format!("{}.json", uri_helpers::media_cache_key(uri)))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path).with_context(|| format!("Cannot open keyframe index {path:?}"))?;
+        let content: KeyframeIndexFile = serde_json::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Cannot parse keyframe index {path:?}"))?;
+        if content.version != KEYFRAME_INDEX_FILE_VERSION {
+            bail!(
+                "Keyframe index {path:?} has the unsupported version {}",
+                content.version
+            );
+        }
+        Ok(Self::new(content.keyframes))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Cannot create directory {parent:?}"))?;
+        }
+        let content = KeyframeIndexFile {
+            version: KEYFRAME_INDEX_FILE_VERSION,
+            keyframes: self.keyframes.clone(),
+        };
+        let file = std::fs::File::create(path).with_context(|| format!("Cannot create keyframe index {path:?}"))?;
+        serde_json::to_writer(std::io::BufWriter::new(file), &content)
+            .with_context(|| format!("Cannot write keyframe index {path:?}"))?;
+        Ok(())
+    }
+
+    // Load the cached keyframe index of the media or build and cache it, this blocks until the media is scanned:
+    pub fn load_or_build(uri: &str) -> Result<Self> {
+        let path = Self::cache_path(uri);
+        if path.exists() {
+            match Self::load(&path) {
+                Ok(index) => {
+                    misc::touch_cache_file(&path);
+                    debug!("Loaded keyframe index of '{uri}' with {} keyframes", index.len());
+                    return Ok(index);
+                }
+                Err(error) => warn!("Rebuild keyframe index: {error:?}"),
+            }
+        }
+        let index = Self::build(uri)?;
+        if let Err(error) = index.save(&path) {
+            warn!("Cannot cache keyframe index of '{uri}': {error:?}");
+        }
+        Ok(index)
+    }
+
+    pub async fn load_or_build_async(uri: &str) -> Result<Self> {
+        let uri = uri.to_string();
+        gio::spawn_blocking(move || Self::load_or_build(&uri))
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("Keyframe index creation aborted unexpectedly")))
+    }
+
+    // Scan the media for keyframes, the streams are parsed but not decoded:
+    pub fn build(uri: &str) -> Result<Self> {
+        let pipeline = gst::Pipeline::builder().name("keyframe-index").build();
+        let source = gst::ElementFactory::make("urisourcebin").property("uri", uri).build()?;
+        let parsebin = gst::ElementFactory::make("parsebin").build()?;
+        pipeline.add_many([&source, &parsebin])?;
+
+        let parsebin_weak = parsebin.downgrade();
+        source.connect_pad_added(move |_, pad| {
+            if let Some(parsebin) = parsebin_weak.upgrade()
+                && let Some(sink_pad) = parsebin.static_pad("sink")
+                && !sink_pad.is_linked()
+                && let Err(error) = pad.link(&sink_pad)
+            {
+                error!("Cannot link source to parser: {error:?}");
+            }
+        });
+
+        let keyframes = Arc::new(Mutex::new(Vec::new()));
+        let has_video = Arc::new(Mutex::new(false));
+        let pipeline_weak = pipeline.downgrade();
+        parsebin.connect_pad_added({
+            let keyframes = keyframes.clone();
+            move |_, pad| {
+                let Some(pipeline) = pipeline_weak.upgrade() else {
+                    return;
+                };
+                let is_video = pad
+                    .current_caps()
+                    .and_then(|caps| caps.structure(0).map(|s| s.name().starts_with("video/")))
+                    .unwrap_or_default();
+                // Only the first video stream is indexed:
+                let index_stream = is_video && !std::mem::replace(&mut *has_video.lock().unwrap(), true);
+                if index_stream {
+                    let keyframes = keyframes.clone();
+                    pad.add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+                        if let Some(buffer) = info.buffer()
+                            && !buffer.flags().contains(gst::BufferFlags::DELTA_UNIT)
+                            && let Some(pts) = buffer.pts()
+                        {
+                            // Store the stream time, which is the position reported by the player:
+                            let position = pad
+                                .sticky_event::<gst::event::Segment>(0)
+                                .and_then(|event| {
+                                    event
+                                        .segment()
+                                        .downcast_ref::<gst::ClockTime>()
+                                        .and_then(|segment| segment.to_stream_time(pts))
+                                })
+                                .unwrap_or(pts);
+                            keyframes
+                                .lock()
+                                .unwrap()
+                                .push(position.nseconds() as f64 / 1_000_000_000_f64);
+                        }
+                        gst::PadProbeReturn::Ok
+                    });
+                }
+                let link = || -> Result<()> {
+                    let sink = gst::ElementFactory::make("fakesink")
+                        .property("sync", false)
+                        .property("async", false)
+                        .build()?;
+                    pipeline.add(&sink)?;
+                    sink.sync_state_with_parent()?;
+                    pad.link(&sink.static_pad("sink").context("fakesink has no sink pad")?)?;
+                    Ok(())
+                };
+                if let Err(error) = link() {
+                    error!("Cannot link stream for the keyframe index: {error:?}");
+                }
+            }
+        });
+
+        let result = run_to_eos(&pipeline);
+        pipeline.set_state(gst::State::Null).ok();
+        result.with_context(|| format!("Cannot create keyframe index of '{uri}'"))?;
+
+        let keyframes = std::mem::take(&mut *keyframes.lock().unwrap());
+        if keyframes.is_empty() {
+            bail!("No keyframes found in '{uri}'");
+        }
+        let index = Self::new(keyframes);
+        debug!("Created keyframe index of '{uri}' with {} keyframes", index.len());
+        Ok(index)
+    }
+}
+
+fn run_to_eos(pipeline: &gst::Pipeline) -> Result<()> {
+    let bus = pipeline.bus().context("Pipeline has no bus")?;
+    pipeline.set_state(gst::State::Playing)?;
+    let timeout = std::time::Instant::now() + std::time::Duration::from_secs(BUILD_TIMEOUT_SECS);
+    while std::time::Instant::now() < timeout {
+        if let Some(message) = bus.timed_pop_filtered(
+            gst::ClockTime::from_mseconds(BUS_POLL_INTERVAL_MS),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        ) {
+            match message.view() {
+                gst::MessageView::Eos(_) => return Ok(()),
+                gst::MessageView::Error(err) => {
+                    bail!("{} ({:?})", err.error(), err.debug());
+                }
+                _ => (),
+            }
+        }
+    }
+    bail!("Timeout while scanning for keyframes")
+}
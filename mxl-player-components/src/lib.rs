@@ -2,16 +2,27 @@ pub extern crate gst;
 pub extern crate gst_pbutils;
 pub extern crate gst_play;
 pub extern crate gst_tag;
+pub extern crate gst_video;
 
 mod icon_names;
 mod localization;
 
 pub mod actions;
+pub mod active_elements;
+pub mod annotations;
+pub mod clip_export;
+#[cfg(feature = "pipeline_svg")]
+pub mod dot_render;
 pub mod glib_helpers;
 pub mod gst_helpers;
+pub mod keyframe_index;
+pub mod markers;
 pub mod misc;
 pub mod player;
+pub mod tag_helpers;
+pub mod thumbnails;
 pub mod ui;
 pub mod uri_helpers;
+pub mod video_filter;
 
 pub use misc::init;
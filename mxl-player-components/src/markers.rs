@@ -0,0 +1,276 @@
+use anyhow::{Context, Result, bail};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{misc, uri_helpers};
+
+const MARKERS_FILE_EXTENSION: &str = "markers.json";
+const MARKERS_FILE_VERSION: u32 = 1;
+// Tolerance in seconds to skip a marker at the current position when navigating:
+const NAVIGATION_TOLERANCE: f64 = 0.1;
+
+// Identifies a marker of a list independent of its index, which changes when markers are added or removed:
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MarkerId(u64);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Marker {
+    // Assigned by the list, not persisted:
+    #[serde(skip)]
+    pub id: MarkerId,
+    pub name: String,
+    // Position in seconds:
+    pub position: f64,
+}
+
+impl Marker {
+    pub fn time_text(&self) -> String {
+        format!("{:.3}", position_to_clock_time(self.position))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkerExportFormat {
+    Csv,
+    Json,
+}
+
+impl MarkerExportFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "csv" => Some(MarkerExportFormat::Csv),
+            "json" => Some(MarkerExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+// Media information, that is optionally included in an export:
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MarkerMediaInfo {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recorder_machine_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_uuid: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MarkersFile {
+    version: u32,
+    markers: Vec<Marker>,
+}
+
+#[derive(Debug, Serialize)]
+struct MarkersExport<'a> {
+    #[serde(flatten)]
+    media: Option<&'a MarkerMediaInfo>,
+    markers: Vec<ExportedMarker<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportedMarker<'a> {
+    name: &'a str,
+    position: f64,
+    time: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MarkerList {
+    markers: Vec<Marker>,
+    next_id: u64,
+}
+
+impl MarkerList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn markers(&self) -> &[Marker] {
+        &self.markers
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.markers.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.markers.len()
+    }
+
+    // Add a marker and keep the list sorted by position, returns the index of the new marker:
+    pub fn add(&mut self, name: &str, position: f64) -> usize {
+        let index = self.markers.partition_point(|m| m.position <= position);
+        let id = self.new_id();
+        self.markers.insert(
+            index,
+            Marker {
+                id,
+                name: name.to_string(),
+                position,
+            },
+        );
+        index
+    }
+
+    pub fn index_of(&self, id: MarkerId) -> Option<usize> {
+        self.markers.iter().position(|m| m.id == id)
+    }
+
+    pub fn remove(&mut self, id: MarkerId) -> Option<Marker> {
+        self.index_of(id).map(|index| self.markers.remove(index))
+    }
+
+    pub fn rename(&mut self, id: MarkerId, name: &str) {
+        if let Some(marker) = self.markers.iter_mut().find(|m| m.id == id) {
+            marker.name = name.to_string();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.markers.clear();
+    }
+
+    pub fn next(&self, position: f64) -> Option<&Marker> {
+        self.markers
+            .iter()
+            .find(|m| m.position > position + NAVIGATION_TOLERANCE)
+    }
+
+    pub fn previous(&self, position: f64) -> Option<&Marker> {
+        self.markers
+            .iter()
+            .rev()
+            .find(|m| m.position < position - NAVIGATION_TOLERANCE)
+    }
+
+    pub fn path_for_uri(uri: &str) -> Result<PathBuf> {
+        uri_helpers::sidecar_path_from_uri(uri, MARKERS_FILE_EXTENSION)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path).with_context(|| format!("Cannot open markers file {path:?}"))?;
+        let content: MarkersFile = serde_json::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("Cannot parse markers file {path:?}"))?;
+        // Newer versions may contain fields, which would be lost when the file is saved again:
+        if content.version > MARKERS_FILE_VERSION {
+            bail!("Markers file {path:?} has the unsupported version {}", content.version);
+        }
+        let mut markers = content.markers;
+        markers.sort_by(|a, b| a.position.total_cmp(&b.position));
+        for (index, marker) in markers.iter_mut().enumerate() {
+            marker.id = MarkerId(index as u64 + 1);
+        }
+        debug!("Loaded {} markers from {path:?}", markers.len());
+        Ok(Self {
+            next_id: markers.len() as u64,
+            markers,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = MarkersFile {
+            version: MARKERS_FILE_VERSION,
+            markers: self.markers.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&content).context("Cannot serialize markers")?;
+        misc::write_file_replacing(path, &json).with_context(|| format!("Cannot write markers file {path:?}"))?;
+        debug!("Saved {} markers to {path:?}", self.markers.len());
+        Ok(())
+    }
+
+    // Load the markers stored next to the media file, an empty list is returned if no file exists:
+    pub fn load_for_uri(uri: &str) -> Result<Self> {
+        let path = Self::path_for_uri(uri)?;
+        if path.exists() {
+            Self::load(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save_for_uri(&self, uri: &str) -> Result<()> {
+        let path = Self::path_for_uri(uri)?;
+        if self.markers.is_empty() && !path.exists() {
+            return Ok(());
+        }
+        self.save(&path)
+    }
+
+    pub fn export(&self, path: &Path, format: MarkerExportFormat, media: Option<&MarkerMediaInfo>) -> Result<()> {
+        let file = std::fs::File::create(path).with_context(|| format!("Cannot create export file {path:?}"))?;
+        let mut writer = std::io::BufWriter::new(file);
+        match format {
+            MarkerExportFormat::Csv => self.write_csv(&mut writer, media),
+            MarkerExportFormat::Json => self.write_json(&mut writer, media),
+        }
+        .with_context(|| format!("Cannot export markers to {path:?}"))?;
+        writer.flush()?;
+        debug!("Exported {} markers to {path:?} as {format:?}", self.markers.len());
+        Ok(())
+    }
+
+    pub fn write_csv(&self, writer: &mut impl Write, media: Option<&MarkerMediaInfo>) -> Result<()> {
+        let mut header = vec!["name", "position", "time"];
+        if media.is_some() {
+            header.extend(["uri", "recorder_machine_id", "current_uuid"]);
+        }
+        writeln!(writer, "{}", header.join(","))?;
+
+        for marker in &self.markers {
+            let mut fields = vec![
+                csv_escape(&marker.name),
+                format!("{:.3}", marker.position),
+                marker.time_text(),
+            ];
+            if let Some(media) = media {
+                fields.extend([
+                    csv_escape(&media.uri),
+                    csv_escape(media.recorder_machine_id.as_deref().unwrap_or_default()),
+                    csv_escape(media.current_uuid.as_deref().unwrap_or_default()),
+                ]);
+            }
+            writeln!(writer, "{}", fields.join(","))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_json(&self, writer: &mut impl Write, media: Option<&MarkerMediaInfo>) -> Result<()> {
+        let export = MarkersExport {
+            media,
+            markers: self
+                .markers
+                .iter()
+                .map(|m| ExportedMarker {
+                    name: &m.name,
+                    position: m.position,
+                    time: m.time_text(),
+                })
+                .collect(),
+        };
+        serde_json::to_writer_pretty(&mut *writer, &export)?;
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    fn new_id(&mut self) -> MarkerId {
+        self.next_id += 1;
+        MarkerId(self.next_id)
+    }
+}
+
+fn position_to_clock_time(position: f64) -> gst::ClockTime {
+    gst::ClockTime::from_mseconds((position.max(0.0) * 1000_f64) as u64)
+}
+
+pub fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -1,8 +1,21 @@
-use crate::{gst_helpers, icon_names};
-use anyhow::Result;
-use std::path::Path;
+use crate::{gst_helpers, icon_names, keyframe_index::KeyframeIndex, thumbnails::ThumbnailGenerator};
+use anyhow::{Context, Result};
+use log::*;
+use mxl_relm4_components::relm4::gtk::glib;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::{Duration, SystemTime},
+};
 
 pub const ENV_NAME_GST_DEBUG_DUMP_DOT_DIR: &str = "GST_DEBUG_DUMP_DOT_DIR";
+#[cfg(feature = "investigator")]
+const PIPELINE_DUMP_DIR_NAME: &str = "pipelines";
+// Cached thumbnails and keyframe indexes, which were not used for this time, are removed by init():
+pub const CACHE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
 
 pub fn init(gst_debug_dump_dot_dir: &Path, cache_dir: &Path) -> Result<()> {
     crate::localization::init();
@@ -10,10 +23,105 @@ pub fn init(gst_debug_dump_dot_dir: &Path, cache_dir: &Path) -> Result<()> {
     relm4_icons::initialize_icons(icon_names::GRESOURCE_BYTES, icon_names::RESOURCE_PREFIX);
 
     unsafe { std::env::set_var(ENV_NAME_GST_DEBUG_DUMP_DOT_DIR, gst_debug_dump_dot_dir) };
+    CACHE_DIR.set(cache_dir.to_path_buf()).ok();
     gst_helpers::init(cache_dir);
     gst::init()?;
 
     gstgtk4::plugin_register_static().expect("Failed to register the gstgtk4 plugin");
 
+    let cache_dirs = [ThumbnailGenerator::default_cache_dir(), KeyframeIndex::cache_dir()];
+    std::thread::Builder::new()
+        .name("cache_cleanup".to_string())
+        .spawn(move || {
+            for dir in cache_dirs {
+                match remove_unused_cache_files(&dir, CACHE_MAX_AGE) {
+                    Ok(0) => {}
+                    Ok(count) => debug!("Removed {count} unused files from the cache {dir:?}"),
+                    Err(error) => warn!("Cannot clean up the cache {dir:?}: {error:?}"),
+                }
+            }
+        })
+        .context("Cannot start the cache cleanup")?;
+
     Ok(())
 }
+
+// Initialize the crate and write the pipeline dumps into the run directory of the investigator,
+// so that they are included in the report archives. The investigator has to be initialized before:
+#[cfg(feature = "investigator")]
+pub fn init_with_proc_dir(cache_dir: &Path) -> Result<()> {
+    init(&proc_dir_pipeline_dump_dir(), cache_dir)
+}
+
+#[cfg(feature = "investigator")]
+pub fn proc_dir_pipeline_dump_dir() -> PathBuf {
+    mxl_investigator::proc_dir::proc_dir().join(PIPELINE_DUMP_DIR_NAME)
+}
+
+// The directory for pipeline dumps passed to init():
+pub fn pipeline_dump_dir() -> Option<PathBuf> {
+    std::env::var_os(ENV_NAME_GST_DEBUG_DUMP_DOT_DIR).map(PathBuf::from)
+}
+
+// The cache directory passed to init() or the user cache directory, if the crate was not initialized:
+pub fn cache_dir() -> PathBuf {
+    CACHE_DIR
+        .get()
+        .cloned()
+        .unwrap_or_else(|| glib::user_cache_dir().join(env!("CARGO_PKG_NAME")))
+}
+
+// Write into a temporary file and rename it, so that an existing file is never truncated or written partially:
+pub(crate) fn write_file_replacing(path: &Path, content: &[u8]) -> Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+    let result = std::fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(content)?;
+            file.sync_all()
+        })
+        .with_context(|| format!("Cannot write {tmp_path:?}"))
+        .and_then(|_| std::fs::rename(&tmp_path, path).with_context(|| format!("Cannot replace {path:?}")));
+    if result.is_err() {
+        _ = std::fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+// Remove the files, which were not modified for the given time, and the directories, which become empty.
+// Returns the number of removed files:
+pub fn remove_unused_cache_files(dir: &Path, max_age: Duration) -> Result<usize> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let now = SystemTime::now();
+    let mut count = 0;
+    for entry in std::fs::read_dir(dir).with_context(|| format!("Cannot read directory {dir:?}"))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            count += remove_unused_cache_files(&path, max_age)?;
+            if std::fs::read_dir(&path).is_ok_and(|mut entries| entries.next().is_none()) {
+                _ = std::fs::remove_dir(&path);
+            }
+        } else {
+            let modified = path.metadata().and_then(|metadata| metadata.modified());
+            if modified.is_ok_and(|modified| now.duration_since(modified).unwrap_or_default() > max_age) {
+                std::fs::remove_file(&path).with_context(|| format!("Cannot remove {path:?}"))?;
+                count += 1;
+            }
+        }
+    }
+    Ok(count)
+}
+
+// Mark a cached file as used, so that it is not removed by remove_unused_cache_files():
+pub(crate) fn touch_cache_file(path: &Path) {
+    if let Err(error) = std::fs::File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()))
+    {
+        trace!("Cannot update the modification time of {path:?}: {error}");
+    }
+}
@@ -2,16 +2,28 @@ use anyhow::{Context, Result};
 use gst::{event::Step, format::Buffers, glib, prelude::*};
 use gst_play::PlayMessage;
 use log::*;
+use mxl_base::settings::Settings;
 use mxl_relm4_components::relm4::{self, Sender, gtk::gdk};
-use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
 use glib::clone;
 
-use crate::ui::player::messages::{PlaybackState, PlayerComponentCommand, Track};
+use crate::{
+    active_elements::{self, ActiveStream},
+    misc::{self, ENV_NAME_GST_DEBUG_DUMP_DOT_DIR},
+    ui::player::messages::{PlaybackState, PlayerComponentCommand, Track},
+    video_filter::{VideoCrop, VideoFilter, VideoOrientation},
+};
 
 const GLSINKBIN_NAME: &str = "glsinkbin";
+const PITCH_CORRECTION_NAME: &str = "scaletempo";
+const DUMP_TIMESTAMP_FMT: &str = "%Y-%m-%d_%H_%M_%S%.3f";
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MaxLateness {
     Unlimited,
     #[default]
@@ -19,14 +31,44 @@ pub enum MaxLateness {
     Custom(i64),
 }
 
+// Options of the player builder, which can be persisted with mxl_base::settings::settings::<PlayerSettings>():
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerSettings {
+    pub seek_accurate: bool,
+    pub qos: bool,
+    pub max_lateness: MaxLateness,
+    pub audio_offset: i64,
+    pub subtitle_offset: i64,
+}
+
+impl Default for PlayerSettings {
+    fn default() -> Self {
+        PlayerBuilder::new().to_settings()
+    }
+}
+
+impl Settings for PlayerSettings {
+    const NAME: &'static str = "player";
+    const VERSION: u32 = 1;
+}
+
 #[derive(Debug)]
 pub struct PlayerBuilder {
     seek_accurate: bool,
     compositor: Option<gst::Element>,
+    clock: Option<gst::Clock>,
     audio_offset: i64,
     subtitle_offset: i64,
     qos: bool,
     max_lateness: MaxLateness,
+    pitch_correction: bool,
+    video_orientation: VideoOrientation,
+    video_crop: VideoCrop,
+    brightness: f64,
+    contrast: f64,
+    hue: f64,
+    saturation: f64,
 }
 
 impl Default for PlayerBuilder {
@@ -40,10 +82,18 @@ impl PlayerBuilder {
         Self {
             seek_accurate: false,
             compositor: None,
+            clock: None,
             audio_offset: 0,
             subtitle_offset: 0,
             qos: true,
             max_lateness: MaxLateness::Default,
+            pitch_correction: false,
+            video_orientation: VideoOrientation::Identity,
+            video_crop: VideoCrop::default(),
+            brightness: 0.0,
+            contrast: 1.0,
+            hue: 0.0,
+            saturation: 1.0,
         }
     }
 
@@ -57,6 +107,12 @@ impl PlayerBuilder {
         self
     }
 
+    // Use the given clock instead of the one selected by the pipeline, e.g. to synchronize several players:
+    pub fn clock(&mut self, clock: Option<gst::Clock>) -> &mut Self {
+        self.clock = clock;
+        self
+    }
+
     pub fn audio_offset(&mut self, offset: i64) -> &mut Self {
         self.audio_offset = offset;
         self
@@ -77,6 +133,61 @@ impl PlayerBuilder {
         self
     }
 
+    // Keep the audio pitch at playback rates other than 1.0:
+    pub fn pitch_correction(&mut self, pitch_correction: bool) -> &mut Self {
+        self.pitch_correction = pitch_correction;
+        self
+    }
+
+    pub fn video_orientation(&mut self, orientation: VideoOrientation) -> &mut Self {
+        self.video_orientation = orientation;
+        self
+    }
+
+    pub fn video_crop(&mut self, crop: VideoCrop) -> &mut Self {
+        self.video_crop = crop;
+        self
+    }
+
+    pub fn brightness(&mut self, brightness: f64) -> &mut Self {
+        self.brightness = brightness;
+        self
+    }
+
+    pub fn contrast(&mut self, contrast: f64) -> &mut Self {
+        self.contrast = contrast;
+        self
+    }
+
+    pub fn hue(&mut self, hue: f64) -> &mut Self {
+        self.hue = hue;
+        self
+    }
+
+    pub fn saturation(&mut self, saturation: f64) -> &mut Self {
+        self.saturation = saturation;
+        self
+    }
+
+    pub fn settings(&mut self, settings: &PlayerSettings) -> &mut Self {
+        self.seek_accurate = settings.seek_accurate;
+        self.qos = settings.qos;
+        self.max_lateness = settings.max_lateness;
+        self.audio_offset = settings.audio_offset;
+        self.subtitle_offset = settings.subtitle_offset;
+        self
+    }
+
+    pub fn to_settings(&self) -> PlayerSettings {
+        PlayerSettings {
+            seek_accurate: self.seek_accurate,
+            qos: self.qos,
+            max_lateness: self.max_lateness,
+            audio_offset: self.audio_offset,
+            subtitle_offset: self.subtitle_offset,
+        }
+    }
+
     pub fn build(&self, sender: relm4::Sender<PlayerComponentCommand>) -> Result<Player> {
         let gtk_sink = gst::ElementFactory::make("gtk4paintablesink").build()?;
 
@@ -109,6 +220,32 @@ impl PlayerBuilder {
         if let Some(compositor) = &self.compositor {
             pipeline.set_property("video-stream-combiner", compositor);
         }
+        // With a shared clock the pipeline does not select its base time, so that the host can start several
+        // players at the same running time with set_base_time():
+        if let Some(clock) = &self.clock
+            && let Some(pipeline) = pipeline.downcast_ref::<gst::Pipeline>()
+        {
+            pipeline.use_clock(Some(clock));
+            pipeline.set_start_time(gst::ClockTime::NONE);
+        }
+
+        if self.pitch_correction {
+            match gst::ElementFactory::make(PITCH_CORRECTION_NAME).build() {
+                Ok(audio_filter) => pipeline.set_property("audio-filter", &audio_filter),
+                Err(error) => warn!("Pitch correction is not available: {error:?}"),
+            }
+        }
+
+        let video_filter = match VideoFilter::new() {
+            Ok(video_filter) => {
+                pipeline.set_property("video-filter", video_filter.element());
+                Some(video_filter)
+            }
+            Err(error) => {
+                warn!("Video filters are not available: {error:?}");
+                None
+            }
+        };
 
         let mut config = gst_play.config();
         config.set_seek_accurate(self.seek_accurate);
@@ -120,6 +257,10 @@ impl PlayerBuilder {
         let player_data = Arc::new(Mutex::new(PlayerData {
             sender,
             current_state: None,
+            rate: 1.0,
+            start_of_stream: false,
+            seek_pending: false,
+            pipeline_seek: None,
         }));
 
         let _bus_watch = gst_play
@@ -135,8 +276,21 @@ impl PlayerBuilder {
                     match PlayMessage::parse(message) {
                         Ok(PlayMessage::EndOfStream(_msg)) => {
                             if let Some(uri) = gst_play.uri() {
-                                let player_data = player_data.lock().unwrap();
-                                player_data.send(PlayerComponentCommand::EndOfStream(uri.into()));
+                                let mut player_data = player_data.lock().unwrap();
+                                let rate = gst_play.rate();
+                                if rate < 0.0 {
+                                    // Reverse playback reached the start, pause at the first frame instead of stopping.
+                                    // The next playback is forward with the speed chosen by the user:
+                                    debug!("Reverse playback reached the start of {uri}");
+                                    player_data.start_of_stream = true;
+                                    player_data.send(PlayerComponentCommand::StartOfStream(uri.into()));
+                                    drop(player_data);
+                                    gst_play.set_rate(rate.abs());
+                                    gst_play.pause();
+                                    gst_play.seek(gst::ClockTime::ZERO);
+                                } else {
+                                    player_data.send(PlayerComponentCommand::EndOfStream(uri.into()));
+                                }
                             }
                         }
                         Ok(PlayMessage::MediaInfoUpdated(msg)) => {
@@ -192,8 +346,12 @@ impl PlayerBuilder {
                             )));
                         }
                         Ok(PlayMessage::SeekDone(_msg)) => {
-                            let player_data = player_data.lock().unwrap();
-                            player_data.send(PlayerComponentCommand::SeekDone);
+                            let mut player_data = player_data.lock().unwrap();
+                            if std::mem::take(&mut player_data.seek_pending) {
+                                player_data.send(PlayerComponentCommand::SeekDone);
+                            }
+                            // A rate change is applied with a seek, report the rate that is actually used:
+                            player_data.update_rate(effective_rate(&gst_play));
                         }
                         Ok(PlayMessage::Warning(msg)) => {
                             let player_data = player_data.lock().unwrap();
@@ -231,10 +389,32 @@ impl PlayerBuilder {
             }
         ));
 
+        // GstPlay only reports the seeks it has sent itself, pipeline seeks are done with the async-done
+        // of the same seqnum. Other async-done messages, e.g. of a state change or an earlier seek, are ignored:
+        if let Some(bus) = pipeline.bus() {
+            bus.connect_message(
+                Some("async-done"),
+                clone!(
+                    #[weak]
+                    player_data,
+                    move |_, msg| {
+                        let mut player_data = player_data.lock().unwrap();
+                        if player_data.pipeline_seek == Some(msg.seqnum()) {
+                            player_data.pipeline_seek = None;
+                            player_data.seek_pending = false;
+                            player_data.send(PlayerComponentCommand::SeekDone);
+                        }
+                    }
+                ),
+            );
+        }
+
         let player = Player {
             player: gst_play,
+            seek_accurate: self.seek_accurate,
             renderer,
             gtk_sink,
+            video_filter,
             _bus_watch,
             data: player_data,
         };
@@ -243,6 +423,12 @@ impl PlayerBuilder {
         player.set_subtitle_video_offset(self.subtitle_offset);
         player.set_qos(self.qos);
         player.set_max_lateness(&self.max_lateness);
+        player.set_video_orientation(self.video_orientation);
+        player.set_video_crop(&self.video_crop);
+        player.set_brightness(self.brightness);
+        player.set_contrast(self.contrast);
+        player.set_hue(self.hue);
+        player.set_saturation(self.saturation);
 
         Ok(player)
     }
@@ -251,8 +437,11 @@ impl PlayerBuilder {
 #[derive(Debug)]
 pub struct Player {
     player: gst_play::Play,
+    // Accuracy of the seeks of GstPlay, which can only be configured while the player is stopped:
+    seek_accurate: bool,
     renderer: gst_play::PlayVideoOverlayVideoRenderer,
     gtk_sink: gst::Element,
+    video_filter: Option<VideoFilter>,
     _bus_watch: gst::bus::BusWatchGuard,
     data: Arc<Mutex<PlayerData>>,
 }
@@ -261,10 +450,27 @@ pub struct Player {
 struct PlayerData {
     sender: Sender<PlayerComponentCommand>,
     current_state: Option<PlaybackState>,
+    rate: f64,
+    // Reverse playback reached the start of the media and the player is about to pause there:
+    start_of_stream: bool,
+    // A seek was requested with seek() and not finished yet. Rate changes are also applied with a seek,
+    // which must not be reported as SeekDone:
+    seek_pending: bool,
+    // Seqnum of a seek with a different accuracy than configured, which was sent directly to the pipeline
+    // and finishes with the async-done message of the same seqnum instead of a seek-done of GstPlay:
+    pipeline_seek: Option<gst::Seqnum>,
 }
 
 impl PlayerData {
     fn change_state(&mut self, new_state: PlaybackState) {
+        if self.start_of_stream {
+            if new_state == PlaybackState::Stopped {
+                // The player stops at the end of a reverse playback, but it is paused at the start right away:
+                trace!("Ignore player state change to {new_state:?} at the start of the stream");
+                return;
+            }
+            self.start_of_stream = false;
+        }
         let target_state = if let Some(current_state) = self.current_state {
             if current_state != new_state {
                 if current_state == PlaybackState::Error && new_state == PlaybackState::Stopped {
@@ -296,6 +502,14 @@ impl PlayerData {
         self.send(PlayerComponentCommand::StateChanged(old_state, new_state));
     }
 
+    fn update_rate(&mut self, rate: f64) {
+        if self.rate != rate {
+            debug!("Effective playback rate changed from {} to {rate}", self.rate);
+            self.rate = rate;
+            self.send(PlayerComponentCommand::SpeedChanged(rate));
+        }
+    }
+
     fn send(&self, cmd: PlayerComponentCommand) {
         self.sender.send(cmd).unwrap_or_default();
     }
@@ -349,8 +563,75 @@ impl Player {
     }
 
     pub fn seek(&self, to: &f64) {
-        let to = gst::ClockTime::from_mseconds((to * 1000_f64) as u64);
-        self.player.seek(to);
+        self.seek_with_accuracy(to, self.seek_accurate);
+    }
+
+    // Seek independent of the configured accuracy, e.g. to refine a keyframe seek:
+    pub fn seek_with_accuracy(&self, to: &f64, accurate: bool) {
+        let position = gst::ClockTime::from_mseconds((to * 1000_f64) as u64);
+        if accurate == self.seek_accurate {
+            let mut player_data = self.data.lock().unwrap();
+            player_data.seek_pending = true;
+            // A previous pipeline seek is replaced and never finishes:
+            player_data.pipeline_seek = None;
+            drop(player_data);
+            self.player.seek(position);
+            return;
+        }
+
+        let rate = self.player.rate();
+        let mut flags = gst::SeekFlags::FLUSH;
+        if accurate {
+            flags |= gst::SeekFlags::ACCURATE;
+        }
+        // In reverse playback the position is the end of the segment:
+        let (start, stop) = if rate < 0.0 {
+            (gst::ClockTime::ZERO, Some(position))
+        } else {
+            (position, None)
+        };
+        let seek = gst::event::Seek::new(rate, flags, gst::SeekType::Set, start, gst::SeekType::Set, stop);
+        let mut player_data = self.data.lock().unwrap();
+        player_data.seek_pending = true;
+        player_data.pipeline_seek = Some(seek.seqnum());
+        drop(player_data);
+        if !self.player.pipeline().send_event(seek) {
+            warn!("Cannot seek to {position}");
+            let mut player_data = self.data.lock().unwrap();
+            player_data.pipeline_seek = None;
+            if std::mem::take(&mut player_data.seek_pending) {
+                player_data.send(PlayerComponentCommand::SeekDone);
+            }
+        }
+    }
+
+    // Current position queried from the pipeline, which is more precise than the periodic position updates:
+    pub fn position(&self) -> Option<f64> {
+        self.player
+            .position()
+            .map(|position| position.nseconds() as f64 / 1_000_000_000_f64)
+    }
+
+    // Only needed for players built with a shared clock, must be set before the playback starts:
+    pub fn set_base_time(&self, base_time: gst::ClockTime) {
+        self.player.pipeline().set_base_time(base_time);
+    }
+
+    pub fn latency(&self) -> Option<gst::ClockTime> {
+        let mut query = gst::query::Latency::new();
+        if self.player.pipeline().query(&mut query) {
+            let (_live, min_latency, _max_latency) = query.result();
+            Some(min_latency)
+        } else {
+            None
+        }
+    }
+
+    // Use the given latency instead of the one of the pipeline, e.g. to render synchronized players at the same time:
+    pub fn set_latency(&self, latency: Option<gst::ClockTime>) {
+        if let Some(pipeline) = self.player.pipeline().downcast_ref::<gst::Pipeline>() {
+            pipeline.set_latency(latency);
+        }
     }
 
     pub fn set_volume(&self, vol: f64) {
@@ -376,8 +657,25 @@ impl Player {
         self.player.rate()
     }
 
+    // The rate that is actually applied by the pipeline, which can differ from the requested speed:
+    pub fn effective_speed(&self) -> f64 {
+        self.data.lock().unwrap().rate
+    }
+
+    // Negative speeds play the media in reverse, the effective rate is reported with SpeedChanged:
     pub fn set_speed(&self, speed: f64) {
+        if speed == 0.0 {
+            warn!("Ignore playback speed of 0, pause the player instead");
+            return;
+        }
+        debug!("Set playback speed to {speed}");
         self.player.set_rate(speed);
+        let mut player_data = self.data.lock().unwrap();
+        match player_data.current_state {
+            Some(PlaybackState::Playing) | Some(PlaybackState::Paused) | Some(PlaybackState::Buffering) => (),
+            // Without a running pipeline the rate is applied with the next playback:
+            _ => player_data.update_rate(speed),
+        }
     }
 
     pub fn next_frame(&self) {
@@ -395,11 +693,43 @@ impl Player {
         self.player.set_subtitle_video_offset(offset);
     }
 
+    // Write the pipeline graph as dot file and the active elements into the pipeline dump directory:
     pub fn dump_pipeline(&self, label: &str) {
+        let name = format!("{}-{label}", chrono::Local::now().format(DUMP_TIMESTAMP_FMT));
+        match self.dump_pipeline_graph(&name) {
+            Ok(path) => render_svg(path),
+            Err(error) => warn!("Cannot dump pipeline: {error:?}"),
+        }
+        if let Err(error) = self.dump_active_elements(&name) {
+            warn!("Cannot dump active elements: {error:?}");
+        }
+    }
+
+    pub fn dump_pipeline_graph(&self, name: &str) -> Result<PathBuf> {
         let element = self.player.pipeline();
-        if let Ok(pipeline) = element.downcast::<gst::Pipeline>() {
-            pipeline.debug_to_dot_file_with_ts(gst::DebugGraphDetails::all(), label);
+        let bin = element.downcast_ref::<gst::Bin>().context("Pipeline is not a bin")?;
+        let path = dump_file_path(&format!("{name}.dot"))?;
+        std::fs::write(&path, bin.debug_to_dot_data(gst::DebugGraphDetails::all()).as_str())
+            .with_context(|| format!("Cannot write pipeline graph to {path:?}"))?;
+        debug!("Dumped pipeline graph to {path:?}");
+        Ok(path)
+    }
+
+    // The parser, decoder and sink that were selected for each stream of the current media:
+    pub fn active_elements(&self) -> Vec<ActiveStream> {
+        active_elements::collect(&self.player.pipeline())
+    }
+
+    pub fn dump_active_elements(&self, name: &str) -> Result<PathBuf> {
+        let path = dump_file_path(&format!("{name}.active-elements.txt"))?;
+        let mut content = String::new();
+        if let Some(uri) = self.player.uri() {
+            content.push_str(&format!("URI: {uri}\n\n"));
         }
+        content.push_str(&active_elements::report(&self.active_elements()));
+        std::fs::write(&path, content).with_context(|| format!("Cannot write active elements to {path:?}"))?;
+        debug!("Dumped active elements to {path:?}");
+        Ok(path)
     }
 
     pub fn set_qos(&self, qos: bool) {
@@ -424,4 +754,70 @@ impl Player {
         debug!("Set max-lateness to {value}");
         self.gtk_sink.set_property(property_name, value);
     }
+
+    pub fn set_video_orientation(&self, orientation: VideoOrientation) {
+        if let Some(video_filter) = &self.video_filter {
+            video_filter.set_orientation(orientation);
+        }
+    }
+
+    pub fn set_video_crop(&self, crop: &VideoCrop) {
+        if let Some(video_filter) = &self.video_filter {
+            video_filter.set_crop(crop);
+        }
+    }
+
+    pub fn set_brightness(&self, brightness: f64) {
+        if let Some(video_filter) = &self.video_filter {
+            video_filter.set_brightness(brightness);
+        }
+    }
+
+    pub fn set_contrast(&self, contrast: f64) {
+        if let Some(video_filter) = &self.video_filter {
+            video_filter.set_contrast(contrast);
+        }
+    }
+
+    pub fn set_hue(&self, hue: f64) {
+        if let Some(video_filter) = &self.video_filter {
+            video_filter.set_hue(hue);
+        }
+    }
+
+    pub fn set_saturation(&self, saturation: f64) {
+        if let Some(video_filter) = &self.video_filter {
+            video_filter.set_saturation(saturation);
+        }
+    }
 }
+
+fn effective_rate(play: &gst_play::Play) -> f64 {
+    let mut query = gst::query::Segment::new(gst::Format::Time);
+    if play.pipeline().query(&mut query) {
+        query.result().0
+    } else {
+        play.rate()
+    }
+}
+
+fn dump_file_path(file_name: &str) -> Result<PathBuf> {
+    let dir = misc::pipeline_dump_dir().with_context(|| format!("{ENV_NAME_GST_DEBUG_DUMP_DOT_DIR} is not set"))?;
+    std::fs::create_dir_all(&dir).with_context(|| format!("Cannot create directory {dir:?}"))?;
+    Ok(dir.join(file_name))
+}
+
+// Large pipelines take a while to render, so the SVG file is written in the background:
+#[cfg(feature = "pipeline_svg")]
+fn render_svg(dot_path: PathBuf) {
+    _ = relm4::gtk::gio::spawn_blocking(move || {
+        let svg_path = dot_path.with_extension("svg");
+        match crate::dot_render::render_svg_file(&dot_path, &svg_path) {
+            Ok(()) => debug!("Rendered pipeline graph to {svg_path:?}"),
+            Err(error) => warn!("Cannot render pipeline graph: {error:?}"),
+        }
+    });
+}
+
+#[cfg(not(feature = "pipeline_svg"))]
+fn render_svg(_dot_path: PathBuf) {}
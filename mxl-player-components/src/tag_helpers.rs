@@ -0,0 +1,74 @@
+use gst::TagList;
+use gst_pbutils::{DiscovererInfo, DiscovererResult, prelude::*};
+
+pub const TAG_CURRENT_UUID: &str = "CURRENT_UUID";
+pub const TAG_NEXT_UUID: &str = "NEXT_UUID";
+pub const TAG_PREVIOUS_UUID: &str = "PREVIOUS_UUID";
+pub const TAG_RECORDER_MACHINE_ID: &str = "RECORDER_MACHINE_ID";
+
+pub const MEDIA_ID_TAGS: [&str; 4] = [
+    TAG_CURRENT_UUID,
+    TAG_NEXT_UUID,
+    TAG_PREVIOUS_UUID,
+    TAG_RECORDER_MACHINE_ID,
+];
+
+pub fn media_info_get_global_tag(name: &str, info: &DiscovererInfo) -> Option<String> {
+    if info.result() == DiscovererResult::Ok {
+        if let Some(info) = info.stream_info()
+            && let Some(info) = info.downcast_ref::<gst_pbutils::DiscovererContainerInfo>()
+            && let Some(tag) = find_tag_in_tag_list(name, info.tags().as_ref())
+        {
+            return Some(tag);
+        }
+        for stream in info.container_streams() {
+            if let Some(tag) = find_tag_in_tag_list(name, stream.tags().as_ref()) {
+                return Some(tag);
+            }
+        }
+    }
+    None
+}
+
+pub fn find_tag_in_tag_list(search_name: &str, tags: Option<&TagList>) -> Option<String> {
+    if let Some(tags) = tags {
+        for (name, values) in tags.iter_generic() {
+            for value in values {
+                if let Some(tag) = get_tag_value(search_name, name, value) {
+                    return Some(tag);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn get_tag_value(search_name: &str, name: &str, value: &gst::glib::value::SendValue) -> Option<String> {
+    let get_tag_value = |value: &gst::glib::value::SendValue| -> Option<String> {
+        if let Ok(s) = value.get::<&str>() {
+            Some(s.to_string())
+        } else {
+            None
+        }
+    };
+
+    if let Some(value) = get_tag_value(value) {
+        if name == gst::tags::ExtendedComment::TAG_NAME {
+            if let Ok(ext_comment) = gst_tag::tag_parse_extended_comment(&value, true) {
+                if ext_comment.key.unwrap_or_default() == search_name {
+                    Some(ext_comment.value.to_string())
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        } else if name == search_name {
+            Some(value)
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
@@ -0,0 +1,234 @@
+use anyhow::{Context, Result, anyhow, bail};
+use gst::prelude::*;
+use log::*;
+use mxl_relm4_components::relm4::gtk::{gdk, gio};
+use std::path::{Path, PathBuf};
+
+use crate::{misc, uri_helpers};
+
+const CACHE_DIR_NAME: &str = "thumbnails";
+const DEFAULT_WIDTH: u32 = 160;
+// Interval in seconds between the thumbnails of a strip, preview positions are rounded to it:
+const DEFAULT_INTERVAL: f64 = 10.0;
+const PREROLL_TIMEOUT_SECS: u64 = 10;
+const CONVERT_TIMEOUT_SECS: u64 = 5;
+const BUS_POLL_INTERVAL_MS: u64 = 100;
+
+// Generates thumbnails of media files in a separate pipeline and caches them as PNG files on disk:
+#[derive(Debug, Clone)]
+pub struct ThumbnailGenerator {
+    cache_dir: PathBuf,
+    width: u32,
+    interval: f64,
+}
+
+impl Default for ThumbnailGenerator {
+    fn default() -> Self {
+        Self::new(Self::default_cache_dir())
+    }
+}
+
+impl ThumbnailGenerator {
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+            width: DEFAULT_WIDTH,
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+
+    pub fn default_cache_dir() -> PathBuf {
+        misc::cache_dir().join(CACHE_DIR_NAME)
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn set_width(&mut self, width: u32) {
+        self.width = width.max(1);
+    }
+
+    pub fn interval(&self) -> f64 {
+        self.interval
+    }
+
+    pub fn set_interval(&mut self, interval: f64) {
+        self.interval = interval.max(0.1);
+    }
+
+    // Round the position down to the thumbnail interval, so that nearby positions share a thumbnail:
+    pub fn slot_position(&self, position: f64) -> f64 {
+        (position.max(0.0) / self.interval).floor() * self.interval
+    }
+
+    // Positions of all thumbnails of a strip for a media with the given duration:
+    pub fn strip_positions(&self, duration: f64) -> Vec<f64> {
+        let count = (duration.max(0.0) / self.interval).ceil() as usize;
+        (0..count.max(1)).map(|i| i as f64 * self.interval).collect()
+    }
+
+    pub fn cache_path(&self, uri: &str, position: f64) -> PathBuf {
+        self.cache_dir.join(uri_helpers::media_cache_key(uri)).join(format!(
+            "{}-{}.png",
+            self.width,
+            (position * 1000_f64) as u64
+        ))
+    }
+
+    // Return the path of the cached thumbnail at the given position and generate it if necessary,
+    // this blocks until the frame is decoded:
+    pub fn thumbnail(&self, uri: &str, position: f64) -> Result<PathBuf> {
+        let position = self.slot_position(position);
+        let path = self.cache_path(uri, position);
+        if path.exists() {
+            misc::touch_cache_file(&path);
+            return Ok(path);
+        }
+        let extractor = FrameExtractor::new(uri, self.width)?;
+        self.extract_to_cache(&extractor, position, &path)?;
+        Ok(path)
+    }
+
+    // Generate all thumbnails of a strip with a single pipeline, already cached thumbnails are skipped:
+    pub fn strip(&self, uri: &str, duration: f64) -> Result<Vec<(f64, PathBuf)>> {
+        let mut extractor = None;
+        let mut thumbnails = Vec::new();
+        for position in self.strip_positions(duration) {
+            let path = self.cache_path(uri, position);
+            if path.exists() {
+                misc::touch_cache_file(&path);
+            } else {
+                if extractor.is_none() {
+                    extractor = Some(FrameExtractor::new(uri, self.width)?);
+                }
+                if let Some(extractor) = &extractor {
+                    self.extract_to_cache(extractor, position, &path)?;
+                }
+            }
+            thumbnails.push((position, path));
+        }
+        Ok(thumbnails)
+    }
+
+    // Texture of the thumbnail at the given position, e.g. for a preview while hovering over the seek bar:
+    pub fn preview_at(&self, uri: &str, position: f64) -> Result<gdk::Texture> {
+        let path = self.thumbnail(uri, position)?;
+        gdk::Texture::from_filename(&path).with_context(|| format!("Cannot load thumbnail {path:?}"))
+    }
+
+    pub async fn preview_at_async(&self, uri: &str, position: f64) -> Result<gdk::Texture> {
+        let generator = self.clone();
+        let uri = uri.to_string();
+        gio::spawn_blocking(move || generator.preview_at(&uri, position))
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("Thumbnail generation aborted unexpectedly")))
+    }
+
+    pub async fn strip_async(&self, uri: &str, duration: f64) -> Result<Vec<(f64, PathBuf)>> {
+        let generator = self.clone();
+        let uri = uri.to_string();
+        gio::spawn_blocking(move || generator.strip(&uri, duration))
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("Thumbnail generation aborted unexpectedly")))
+    }
+
+    pub fn clear_cache(&self) -> Result<()> {
+        if self.cache_dir.exists() {
+            std::fs::remove_dir_all(&self.cache_dir)
+                .with_context(|| format!("Cannot remove thumbnail cache {:?}", self.cache_dir))?;
+        }
+        Ok(())
+    }
+
+    fn extract_to_cache(&self, extractor: &FrameExtractor, position: f64, path: &Path) -> Result<()> {
+        let png = extractor.extract(position)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("Cannot create directory {parent:?}"))?;
+        }
+        // Write to a temporary file first, so that concurrent readers never see a partial thumbnail:
+        let tmp_path = path.with_extension("png.tmp");
+        std::fs::write(&tmp_path, png).with_context(|| format!("Cannot write thumbnail {tmp_path:?}"))?;
+        std::fs::rename(&tmp_path, path).with_context(|| format!("Cannot write thumbnail {path:?}"))?;
+        trace!("Cached thumbnail {path:?}");
+        Ok(())
+    }
+}
+
+struct FrameExtractor {
+    pipeline: gst::Element,
+    caps: gst::Caps,
+}
+
+impl FrameExtractor {
+    fn new(uri: &str, width: u32) -> Result<Self> {
+        let pipeline = gst::ElementFactory::make("playbin")
+            .name("thumbnails")
+            .property("uri", uri)
+            .property("audio-sink", gst::ElementFactory::make("fakesink").build()?)
+            .property("video-sink", gst::ElementFactory::make("fakesink").build()?)
+            .build()?;
+        let caps = gst::Caps::builder("image/png")
+            .field("width", width as i32)
+            .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
+            .build();
+        let extractor = Self { pipeline, caps };
+
+        extractor
+            .pipeline
+            .set_state(gst::State::Paused)
+            .with_context(|| format!("Cannot pause thumbnail pipeline for '{uri}'"))?;
+        extractor.wait_for_async_done()?;
+        Ok(extractor)
+    }
+
+    fn extract(&self, position: f64) -> Result<Vec<u8>> {
+        self.pipeline
+            .seek_simple(
+                gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+                gst::ClockTime::from_mseconds((position * 1000_f64) as u64),
+            )
+            .with_context(|| format!("Cannot seek to {position} for a thumbnail"))?;
+        self.wait_for_async_done()?;
+
+        let sample = self
+            .pipeline
+            .property::<Option<gst::Sample>>("sample")
+            .context("No frame available for the thumbnail")?;
+        let png = gst_video::convert_sample(&sample, &self.caps, gst::ClockTime::from_seconds(CONVERT_TIMEOUT_SECS))
+            .context("Cannot convert frame to a thumbnail")?;
+        let buffer = png.buffer().context("Thumbnail has no data")?;
+        let map = buffer.map_readable()?;
+        Ok(map.as_slice().to_vec())
+    }
+
+    fn wait_for_async_done(&self) -> Result<()> {
+        let bus = self.pipeline.bus().context("Thumbnail pipeline has no bus")?;
+        let timeout = std::time::Instant::now() + std::time::Duration::from_secs(PREROLL_TIMEOUT_SECS);
+        while std::time::Instant::now() < timeout {
+            if let Some(message) = bus.timed_pop_filtered(
+                gst::ClockTime::from_mseconds(BUS_POLL_INTERVAL_MS),
+                &[gst::MessageType::AsyncDone, gst::MessageType::Error],
+            ) {
+                match message.view() {
+                    gst::MessageView::AsyncDone(_) => return Ok(()),
+                    gst::MessageView::Error(err) => {
+                        bail!("Thumbnail pipeline error: {} ({:?})", err.error(), err.debug());
+                    }
+                    _ => (),
+                }
+            }
+        }
+        bail!("Timeout while decoding a thumbnail")
+    }
+}
+
+impl Drop for FrameExtractor {
+    fn drop(&mut self) {
+        self.pipeline.set_state(gst::State::Null).ok();
+    }
+}
@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum ClipExportComponentInput {
+    SetClip { uri: String, start: f64, end: f64 },
+    SetOutput(PathBuf),
+    PrivateMessage(internal::PrivateMsg),
+}
+
+#[derive(Debug)]
+pub enum ClipExportComponentOutput {
+    FileChooserRequest,
+    Exported(PathBuf),
+    Error(anyhow::Error),
+}
+
+pub(super) mod internal {
+    use crate::clip_export::ClipExportMode;
+
+    #[derive(Debug)]
+    pub enum PrivateMsg {
+        StartChanged(f64),
+        EndChanged(f64),
+        ModeChanged(ClipExportMode),
+        Export,
+        Cancel,
+        Progress(f64),
+        LanguageChanged,
+    }
+}
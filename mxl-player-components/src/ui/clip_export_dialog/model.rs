@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use crate::clip_export::{ClipExportCancellation, ClipExportMode};
+
+#[derive(Debug, Default)]
+pub struct ClipExportComponentInit {
+    pub uri: Option<String>,
+    pub start: f64,
+    pub end: f64,
+    pub mode: ClipExportMode,
+}
+
+#[derive(Debug)]
+pub struct ClipExportComponentModel {
+    pub(super) uri: Option<String>,
+    pub(super) start: f64,
+    pub(super) end: f64,
+    pub(super) mode: ClipExportMode,
+    pub(super) output: Option<PathBuf>,
+    pub(super) progress: f64,
+    pub(super) cancellation: Option<ClipExportCancellation>,
+    pub(super) _language_subscription: mxl_base::language::LanguageSubscription,
+}
@@ -0,0 +1,315 @@
+use anyhow::anyhow;
+use log::*;
+use mxl_relm4_components::relm4::{self, adw::prelude::*, css as adw_css, prelude::*};
+
+use crate::clip_export::{self, ClipExportCancellation, ClipExportMode, ClipExportSettings};
+use crate::localization::helper::fl;
+use crate::ui::clip_export_dialog::messages::{
+    ClipExportComponentInput, ClipExportComponentOutput, internal::PrivateMsg,
+};
+use crate::ui::clip_export_dialog::model::{ClipExportComponentInit, ClipExportComponentModel};
+
+const POSITION_MAX: f64 = 24_f64 * 60_f64 * 60_f64;
+const POSITION_INCREMENT_STEP: f64 = 0.1_f64;
+const POSITION_PAGE_INCREMENT: f64 = 10_f64;
+const POSITION_PAGE_SIZE: f64 = 0_f64;
+const POSITION_DIGITS: u32 = 3;
+
+const MODES: [ClipExportMode; 3] = [
+    ClipExportMode::Auto,
+    ClipExportMode::StreamCopy,
+    ClipExportMode::ReEncode,
+];
+
+// The names in the order of MODES:
+fn mode_names() -> gtk::StringList {
+    gtk::StringList::new(&[
+        fl!("clip-export-mode", "auto").as_str(),
+        fl!("clip-export-mode", "stream-copy").as_str(),
+        fl!("clip-export-mode", "re-encode").as_str(),
+    ])
+}
+
+#[relm4::component(pub)]
+impl Component for ClipExportComponentModel {
+    type Init = ClipExportComponentInit;
+    type Input = ClipExportComponentInput;
+    type Output = ClipExportComponentOutput;
+    type CommandOutput = anyhow::Result<std::path::PathBuf>;
+
+    view! {
+        adw::PreferencesWindow {
+            #[watch]
+            set_title: Some(&fl!("clip-export")),
+            set_hide_on_close: true,
+            set_destroy_with_parent: true,
+            // A running export is cancelled when the dialog is closed:
+            connect_close_request[sender] => move |_| {
+                sender.input(ClipExportComponentInput::PrivateMessage(PrivateMsg::Cancel));
+                gtk::glib::Propagation::Proceed
+            },
+
+            add = &adw::PreferencesPage {
+                set_vexpand: true,
+
+                add = &adw::PreferencesGroup {
+                    #[watch]
+                    set_sensitive: !model.is_exporting(),
+
+                    adw::ActionRow {
+                        #[watch]
+                        set_title: &fl!("clip-export-start"),
+
+                        add_suffix = &gtk::SpinButton {
+                            set_valign: gtk::Align::Center,
+                            set_digits: POSITION_DIGITS,
+                            set_adjustment: &gtk::Adjustment::new(model.start,
+                                                                  0_f64,
+                                                                  POSITION_MAX,
+                                                                  POSITION_INCREMENT_STEP,
+                                                                  POSITION_PAGE_INCREMENT,
+                                                                  POSITION_PAGE_SIZE),
+                            #[watch]
+                            #[block_signal(start_changed_handler)]
+                            set_value: model.start,
+                            connect_value_changed[sender] => move |spin_button| {
+                                sender.input(ClipExportComponentInput::PrivateMessage(PrivateMsg::StartChanged(spin_button.value())));
+                            } @start_changed_handler,
+                        },
+                    },
+
+                    adw::ActionRow {
+                        #[watch]
+                        set_title: &fl!("clip-export-end"),
+
+                        add_suffix = &gtk::SpinButton {
+                            set_valign: gtk::Align::Center,
+                            set_digits: POSITION_DIGITS,
+                            set_adjustment: &gtk::Adjustment::new(model.end,
+                                                                  0_f64,
+                                                                  POSITION_MAX,
+                                                                  POSITION_INCREMENT_STEP,
+                                                                  POSITION_PAGE_INCREMENT,
+                                                                  POSITION_PAGE_SIZE),
+                            #[watch]
+                            #[block_signal(end_changed_handler)]
+                            set_value: model.end,
+                            connect_value_changed[sender] => move |spin_button| {
+                                sender.input(ClipExportComponentInput::PrivateMessage(PrivateMsg::EndChanged(spin_button.value())));
+                            } @end_changed_handler,
+                        },
+                    },
+
+                    #[name(mode_row)]
+                    adw::ComboRow {
+                        #[watch]
+                        set_title: &fl!("clip-export-mode"),
+                        set_model: Some(&mode_names()),
+                        #[watch]
+                        #[block_signal(mode_changed_handler)]
+                        set_selected: MODES.iter().position(|mode| *mode == model.mode).unwrap_or_default() as u32,
+                        connect_selected_notify[sender] => move |row| {
+                            if let Some(mode) = MODES.get(row.selected() as usize) {
+                                sender.input(ClipExportComponentInput::PrivateMessage(PrivateMsg::ModeChanged(*mode)));
+                            }
+                        } @mode_changed_handler,
+                    },
+
+                    adw::ActionRow {
+                        #[watch]
+                        set_title: &fl!("clip-export-output"),
+                        #[watch]
+                        set_subtitle: &model
+                            .output
+                            .as_ref()
+                            .map(|path| gtk::glib::markup_escape_text(&path.to_string_lossy()).to_string())
+                            .unwrap_or_default(),
+
+                        add_suffix = &gtk::Button {
+                            #[watch]
+                            set_label: &fl!("clip-export-choose-output"),
+                            set_valign: gtk::Align::Center,
+                            connect_clicked[sender] => move |_| {
+                                sender.output(ClipExportComponentOutput::FileChooserRequest).unwrap_or_default();
+                            },
+                        },
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    gtk::ProgressBar {
+                        set_show_text: true,
+                        #[watch]
+                        set_fraction: model.progress,
+                    },
+
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_halign: gtk::Align::End,
+                        set_spacing: 6,
+                        set_margin_top: 12,
+
+                        gtk::Button {
+                            #[watch]
+                            set_label: &fl!("clip-export-cancel"),
+                            #[watch]
+                            set_sensitive: model.is_exporting(),
+                            connect_clicked => ClipExportComponentInput::PrivateMessage(PrivateMsg::Cancel),
+                        },
+
+                        gtk::Button {
+                            #[watch]
+                            set_label: &fl!("clip-export"),
+                            set_css_classes: &[adw_css::SUGGESTED_ACTION],
+                            #[watch]
+                            set_sensitive: model.can_export(),
+                            connect_clicked => ClipExportComponentInput::PrivateMessage(PrivateMsg::Export),
+                        },
+                    },
+                },
+            }
+        }
+    }
+
+    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
+        self.cancel();
+    }
+
+    // Initialize the component.
+    fn init(init: Self::Init, root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let model = ClipExportComponentModel {
+            uri: init.uri,
+            start: init.start,
+            end: init.end,
+            mode: init.mode,
+            output: None,
+            progress: 0.0,
+            cancellation: None,
+            _language_subscription: mxl_base::language::subscription({
+                let sender = sender.input_sender().clone();
+                move |_| sender.emit(ClipExportComponentInput::PrivateMessage(PrivateMsg::LanguageChanged))
+            }),
+        };
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match msg {
+            ClipExportComponentInput::SetClip { uri, start, end } => {
+                if !self.is_exporting() {
+                    self.uri = Some(uri);
+                    self.start = start;
+                    self.end = end;
+                    self.progress = 0.0;
+                }
+            }
+            ClipExportComponentInput::SetOutput(output) => self.output = Some(output),
+            ClipExportComponentInput::PrivateMessage(msg) => match msg {
+                PrivateMsg::StartChanged(start) => self.start = start,
+                PrivateMsg::EndChanged(end) => self.end = end,
+                PrivateMsg::ModeChanged(mode) => self.mode = mode,
+                PrivateMsg::Export => self.export(&sender),
+                PrivateMsg::Cancel => self.cancel(),
+                PrivateMsg::Progress(progress) => {
+                    if self.is_exporting() {
+                        self.progress = progress;
+                    }
+                }
+                // The mode names are set again without changing the mode, the other labels are watched:
+                PrivateMsg::LanguageChanged => {
+                    widgets.mode_row.block_signal(&widgets.mode_changed_handler);
+                    widgets.mode_row.set_model(Some(&mode_names()));
+                    widgets.mode_row.unblock_signal(&widgets.mode_changed_handler);
+                }
+            },
+        }
+        self.update_view(widgets, sender)
+    }
+
+    fn update_cmd(&mut self, result: Self::CommandOutput, sender: ComponentSender<Self>, _: &Self::Root) {
+        let cancelled = self
+            .cancellation
+            .take()
+            .is_some_and(|cancellation| cancellation.is_cancelled());
+        match result {
+            Ok(output) => {
+                self.progress = 1.0;
+                sender
+                    .output(ClipExportComponentOutput::Exported(output))
+                    .unwrap_or_default();
+            }
+            Err(error) if cancelled => {
+                self.progress = 0.0;
+                debug!("Clip export cancelled: {error:?}");
+            }
+            Err(error) => {
+                self.progress = 0.0;
+                sender
+                    .output(ClipExportComponentOutput::Error(error))
+                    .unwrap_or_default();
+            }
+        }
+    }
+}
+
+impl ClipExportComponentModel {
+    pub fn is_exporting(&self) -> bool {
+        self.cancellation.is_some()
+    }
+
+    fn can_export(&self) -> bool {
+        !self.is_exporting() && self.uri.is_some() && self.output.is_some() && self.end > self.start
+    }
+
+    fn cancel(&self) {
+        if let Some(cancellation) = &self.cancellation {
+            debug!("Cancel clip export");
+            cancellation.cancel();
+        }
+    }
+
+    fn export(&mut self, sender: &ComponentSender<Self>) {
+        let (Some(uri), Some(output)) = (self.uri.clone(), self.output.clone()) else {
+            return;
+        };
+        if self.is_exporting() {
+            return;
+        }
+
+        let settings = ClipExportSettings {
+            uri,
+            start: self.start,
+            end: self.end,
+            output,
+            mode: self.mode,
+        };
+        let cancellation = ClipExportCancellation::new();
+        let input_sender = sender.input_sender().clone();
+        let handle = clip_export::export_clip_async(
+            settings,
+            move |progress| {
+                input_sender
+                    .send(ClipExportComponentInput::PrivateMessage(PrivateMsg::Progress(progress)))
+                    .unwrap_or_default();
+            },
+            cancellation.clone(),
+        );
+        self.progress = 0.0;
+        self.cancellation = Some(cancellation);
+
+        sender.oneshot_command(async move {
+            handle
+                .await
+                .unwrap_or_else(|_| Err(anyhow!("The clip export was aborted unexpectedly")))
+        });
+    }
+}
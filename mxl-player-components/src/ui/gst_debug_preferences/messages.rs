@@ -0,0 +1,22 @@
+#[derive(Debug)]
+pub enum GstDebugPreferencesComponentInput {
+    SetEnabled(bool),
+    SetSpec(String),
+    PrivateMessage(internal::PrivateMsg),
+}
+
+#[derive(Debug)]
+pub enum GstDebugPreferencesComponentOutput {
+    // The settings were changed by the user and can be persisted:
+    Changed { enabled: bool, spec: String },
+    Error(anyhow::Error),
+}
+
+pub(super) mod internal {
+    #[derive(Debug)]
+    pub enum PrivateMsg {
+        EnabledChanged(bool),
+        SpecChanged(String),
+        LanguageChanged,
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+pub const DEFAULT_GST_DEBUG_SPEC: &str = "*:4";
+
+#[derive(Debug)]
+pub struct GstDebugPreferencesComponentInit {
+    pub enabled: bool,
+    pub spec: String,
+    // The GStreamer log messages are written additionally into this file while enabled,
+    // e.g. gst_helpers::proc_dir_gst_debug_log_file():
+    pub log_file: Option<PathBuf>,
+}
+
+impl Default for GstDebugPreferencesComponentInit {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            spec: DEFAULT_GST_DEBUG_SPEC.to_string(),
+            log_file: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GstDebugPreferencesComponentModel {
+    pub(super) enabled: bool,
+    pub(super) spec: String,
+    pub(super) log_file: Option<PathBuf>,
+    pub(super) invalid_spec: bool,
+    pub(super) _language_subscription: mxl_base::language::LanguageSubscription,
+}
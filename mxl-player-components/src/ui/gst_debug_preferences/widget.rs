@@ -0,0 +1,167 @@
+use mxl_relm4_components::relm4::{self, adw::prelude::*, prelude::*};
+
+use super::{
+    messages::{GstDebugPreferencesComponentInput, GstDebugPreferencesComponentOutput, internal::PrivateMsg},
+    model::{GstDebugPreferencesComponentInit, GstDebugPreferencesComponentModel},
+};
+use crate::gst_helpers;
+use crate::localization::helper::fl;
+
+const ERROR_CSS_CLASS: &str = "error";
+
+#[relm4::component(pub)]
+impl Component for GstDebugPreferencesComponentModel {
+    type Init = GstDebugPreferencesComponentInit;
+    type Input = GstDebugPreferencesComponentInput;
+    type Output = GstDebugPreferencesComponentOutput;
+    type CommandOutput = ();
+
+    view! {
+        adw::PreferencesGroup {
+            #[watch]
+            set_title: &fl!("gst-debug"),
+            #[watch]
+            set_description: Some(&fl!("gst-debug", "description")),
+
+            adw::ActionRow {
+                #[watch]
+                set_title: &fl!("gst-debug-enable"),
+                #[watch]
+                set_subtitle: &model.log_file_description(),
+                set_activatable_widget: Some(&enabled_switch),
+
+                #[name(enabled_switch)]
+                add_suffix = &gtk::Switch {
+                    set_valign: gtk::Align::Center,
+                    #[watch]
+                    #[block_signal(enabled_changed_handler)]
+                    set_active: model.enabled,
+                    connect_active_notify[sender] => move |switch| {
+                        sender.input(GstDebugPreferencesComponentInput::PrivateMessage(PrivateMsg::EnabledChanged(switch.is_active())));
+                    } @enabled_changed_handler,
+                },
+            },
+
+            adw::ActionRow {
+                #[watch]
+                set_title: &fl!("gst-debug-spec"),
+                #[watch]
+                set_subtitle: &fl!("gst-debug-spec", "description"),
+
+                #[name(spec_entry)]
+                add_suffix = &gtk::Entry {
+                    set_valign: gtk::Align::Center,
+                    set_text: &model.spec,
+                    #[watch]
+                    set_class_active: (ERROR_CSS_CLASS, model.invalid_spec),
+                    connect_activate[sender] => move |entry| {
+                        sender.input(GstDebugPreferencesComponentInput::PrivateMessage(PrivateMsg::SpecChanged(entry.text().to_string())));
+                    },
+                },
+            },
+        }
+    }
+
+    // Initialize the component.
+    fn init(init: Self::Init, root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let mut model = GstDebugPreferencesComponentModel {
+            enabled: init.enabled,
+            spec: init.spec,
+            log_file: init.log_file,
+            invalid_spec: false,
+            _language_subscription: mxl_base::language::subscription({
+                let sender = sender.input_sender().clone();
+                move |_| {
+                    sender.emit(GstDebugPreferencesComponentInput::PrivateMessage(
+                        PrivateMsg::LanguageChanged,
+                    ))
+                }
+            }),
+        };
+        if model.enabled {
+            model.apply(&sender);
+        }
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match msg {
+            GstDebugPreferencesComponentInput::SetEnabled(enabled) => {
+                self.enabled = enabled;
+                self.apply(&sender);
+            }
+            GstDebugPreferencesComponentInput::SetSpec(spec) => {
+                widgets.spec_entry.set_text(&spec);
+                self.spec = spec;
+                self.apply(&sender);
+            }
+            GstDebugPreferencesComponentInput::PrivateMessage(msg) => {
+                match msg {
+                    PrivateMsg::EnabledChanged(enabled) => self.enabled = enabled,
+                    PrivateMsg::SpecChanged(spec) => self.spec = spec,
+                    // The translated labels are watched and updated with the view:
+                    PrivateMsg::LanguageChanged => return self.update_view(widgets, sender),
+                }
+                if self.apply(&sender) {
+                    sender
+                        .output(GstDebugPreferencesComponentOutput::Changed {
+                            enabled: self.enabled,
+                            spec: self.spec.clone(),
+                        })
+                        .unwrap_or_default();
+                }
+            }
+        }
+        self.update_view(widgets, sender)
+    }
+}
+
+impl GstDebugPreferencesComponentModel {
+    fn log_file_description(&self) -> String {
+        self.log_file
+            .as_ref()
+            .map(|path| {
+                let description = fl!(
+                    "gst-debug-enable",
+                    "log-file",
+                    path = path.to_string_lossy().to_string()
+                );
+                gtk::glib::markup_escape_text(&description).to_string()
+            })
+            .unwrap_or_default()
+    }
+
+    // Apply the settings to GStreamer and return whether they are valid:
+    fn apply(&mut self, sender: &ComponentSender<Self>) -> bool {
+        self.invalid_spec = false;
+        if !self.enabled {
+            gst_helpers::reset_gst_debug();
+            gst_helpers::set_gst_debug_log_file(None).ok();
+            self.invalid_spec = gst_helpers::parse_gst_debug(&self.spec).is_err();
+            return !self.invalid_spec;
+        }
+
+        if let Err(error) = gst_helpers::set_gst_debug(&self.spec) {
+            self.invalid_spec = true;
+            sender
+                .output(GstDebugPreferencesComponentOutput::Error(error))
+                .unwrap_or_default();
+            return false;
+        }
+        if let Err(error) = gst_helpers::set_gst_debug_log_file(self.log_file.as_deref()) {
+            sender
+                .output(GstDebugPreferencesComponentOutput::Error(error))
+                .unwrap_or_default();
+        }
+        true
+    }
+}
@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use crate::markers::{Marker, MarkerId, MarkerMediaInfo};
+
+#[derive(Debug)]
+pub enum MarkersComponentInput {
+    // Usually PlaylistComponentModel::active_marker_media_info(), which should be sent again after the playlist
+    // updated its metadata. The markers are only reloaded if the URI changes:
+    SetMedia(Option<MarkerMediaInfo>),
+    SetPosition(f64),
+    AddMarker(Option<String>),
+    RemoveMarker(MarkerId),
+    NextMarker,
+    PreviousMarker,
+    // Export the markers to the given file, the format is selected by the file extension (csv or json).
+    // The flag includes the media information into the export:
+    Export(PathBuf, bool),
+    PrivateMessage(internal::PrivateMsg),
+}
+
+#[derive(Debug)]
+pub enum MarkersComponentOutput {
+    Seek(f64),
+    MarkersChanged(Vec<Marker>),
+    Error(anyhow::Error),
+}
+
+pub(super) mod internal {
+    #[derive(Debug)]
+    pub enum PrivateMsg {
+        Activated(usize),
+        LanguageChanged,
+    }
+}
@@ -0,0 +1,18 @@
+use mxl_relm4_components::relm4::adw;
+
+use crate::markers::{MarkerList, MarkerMediaInfo};
+
+#[derive(Debug, Default)]
+pub struct MarkersComponentInit {
+    pub media: Option<MarkerMediaInfo>,
+}
+
+#[derive(Debug)]
+pub struct MarkersComponentModel {
+    pub(super) media: Option<MarkerMediaInfo>,
+    pub(super) markers: MarkerList,
+    pub(super) position: f64,
+    // Rows of the list box in the order of the markers:
+    pub(super) rows: Vec<adw::ActionRow>,
+    pub(super) _language_subscription: mxl_base::language::LanguageSubscription,
+}
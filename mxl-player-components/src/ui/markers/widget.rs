@@ -0,0 +1,245 @@
+use anyhow::Context;
+use log::*;
+use mxl_relm4_components::relm4::{self, adw::prelude::*, css as adw_css, gtk::glib::clone, prelude::*};
+
+use super::{
+    messages::{MarkersComponentInput, MarkersComponentOutput, internal::PrivateMsg},
+    model::{MarkersComponentInit, MarkersComponentModel},
+};
+use crate::{
+    icon_names,
+    localization::helper::fl,
+    markers::{MarkerExportFormat, MarkerList, MarkerMediaInfo},
+};
+
+#[relm4::component(pub)]
+impl Component for MarkersComponentModel {
+    type Init = MarkersComponentInit;
+    type Input = MarkersComponentInput;
+    type Output = MarkersComponentOutput;
+    type CommandOutput = ();
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_css_classes: &[adw_css::BACKGROUND],
+
+            adw::HeaderBar {
+                set_css_classes: &[adw_css::FLAT],
+                set_show_start_title_buttons: false,
+                set_show_end_title_buttons: false,
+                #[wrap(Some)]
+                set_title_widget = &gtk::Label {
+                    #[watch]
+                    set_label: &fl!("markers"),
+                },
+                pack_start = &gtk::Button {
+                    #[watch]
+                    set_sensitive: model.media.is_some(),
+                    set_has_tooltip: true,
+                    #[watch]
+                    set_tooltip_text: Some(&fl!("add-marker")),
+                    set_icon_name: icon_names::PLUS,
+                    set_css_classes: &[adw_css::FLAT, "image-button"],
+                    set_valign: gtk::Align::Center,
+                    connect_clicked => MarkersComponentInput::AddMarker(None),
+                },
+            },
+
+            gtk::ScrolledWindow {
+                set_vexpand: true,
+                set_hscrollbar_policy: gtk::PolicyType::Never,
+
+                #[name(list_box)]
+                gtk::ListBox {
+                    set_selection_mode: gtk::SelectionMode::None,
+                    add_css_class: adw_css::BOXED_LIST,
+                    set_margin_all: 6,
+                    set_valign: gtk::Align::Start,
+                    #[wrap(Some)]
+                    set_placeholder = &gtk::Label {
+                        #[watch]
+                        set_label: &fl!("markers-empty"),
+                    },
+                    connect_row_activated[sender] => move |_, row| {
+                        sender.input(MarkersComponentInput::PrivateMessage(PrivateMsg::Activated(row.index() as usize)));
+                    },
+                },
+            },
+        }
+    }
+
+    // Initialize the component.
+    fn init(init: Self::Init, root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let mut model = MarkersComponentModel {
+            media: None,
+            markers: MarkerList::default(),
+            position: 0.0,
+            rows: Vec::new(),
+            _language_subscription: mxl_base::language::subscription({
+                let sender = sender.input_sender().clone();
+                move |_| sender.emit(MarkersComponentInput::PrivateMessage(PrivateMsg::LanguageChanged))
+            }),
+        };
+        model.set_media(init.media, &sender);
+
+        let widgets = view_output!();
+
+        model.rebuild_rows(&widgets, &sender);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match msg {
+            MarkersComponentInput::SetMedia(media) => {
+                let uri_changed = self.media.as_ref().map(|m| &m.uri) != media.as_ref().map(|m| &m.uri);
+                if uri_changed {
+                    self.set_media(media, &sender);
+                    self.rebuild_rows(widgets, &sender);
+                    self.markers_changed(&sender);
+                } else {
+                    self.media = media;
+                }
+            }
+            MarkersComponentInput::SetPosition(position) => self.position = position,
+            MarkersComponentInput::AddMarker(name) => {
+                if self.media.is_some() {
+                    let name = name.unwrap_or_else(|| fl!("marker-default-name", number = self.markers.len() + 1));
+                    debug!("Add marker '{name}' at {}", self.position);
+                    let index = self.markers.add(&name, self.position);
+                    self.save_markers(&sender);
+                    self.insert_row(widgets, &sender, index);
+                    self.markers_changed(&sender);
+                }
+            }
+            MarkersComponentInput::RemoveMarker(id) => {
+                if let Some(index) = self.markers.index_of(id)
+                    && let Some(marker) = self.markers.remove(id)
+                {
+                    debug!("Remove marker {marker:?}");
+                    self.save_markers(&sender);
+                    widgets.list_box.remove(&self.rows.remove(index));
+                    self.markers_changed(&sender);
+                }
+            }
+            MarkersComponentInput::NextMarker => {
+                if let Some(marker) = self.markers.next(self.position) {
+                    sender
+                        .output(MarkersComponentOutput::Seek(marker.position))
+                        .unwrap_or_default();
+                }
+            }
+            MarkersComponentInput::PreviousMarker => {
+                if let Some(marker) = self.markers.previous(self.position) {
+                    sender
+                        .output(MarkersComponentOutput::Seek(marker.position))
+                        .unwrap_or_default();
+                }
+            }
+            MarkersComponentInput::Export(path, include_media_info) => {
+                let media = if include_media_info { self.media.as_ref() } else { None };
+                if let Err(error) = MarkerExportFormat::from_path(&path)
+                    .with_context(|| format!("Unknown marker export format of {path:?}"))
+                    .and_then(|format| self.markers.export(&path, format, media))
+                {
+                    sender.output(MarkersComponentOutput::Error(error)).unwrap_or_default();
+                }
+            }
+            MarkersComponentInput::PrivateMessage(msg) => match msg {
+                PrivateMsg::Activated(index) => {
+                    if let Some(marker) = self.markers.markers().get(index) {
+                        sender
+                            .output(MarkersComponentOutput::Seek(marker.position))
+                            .unwrap_or_default();
+                    }
+                }
+                // The tooltips of the rows are translated when they are created, the other labels are watched:
+                PrivateMsg::LanguageChanged => {
+                    self.rebuild_rows(widgets, &sender);
+                }
+            },
+        }
+        self.update_view(widgets, sender)
+    }
+}
+
+impl MarkersComponentModel {
+    pub fn markers(&self) -> &MarkerList {
+        &self.markers
+    }
+
+    fn set_media(&mut self, media: Option<MarkerMediaInfo>, sender: &ComponentSender<Self>) {
+        self.markers = if let Some(media) = &media {
+            match MarkerList::load_for_uri(&media.uri) {
+                Ok(markers) => markers,
+                Err(error) => {
+                    sender.output(MarkersComponentOutput::Error(error)).unwrap_or_default();
+                    MarkerList::default()
+                }
+            }
+        } else {
+            MarkerList::default()
+        };
+        self.media = media;
+    }
+
+    fn save_markers(&self, sender: &ComponentSender<Self>) {
+        if let Some(media) = &self.media
+            && let Err(error) = self.markers.save_for_uri(&media.uri)
+        {
+            sender.output(MarkersComponentOutput::Error(error)).unwrap_or_default();
+        }
+    }
+
+    fn markers_changed(&self, sender: &ComponentSender<Self>) {
+        sender
+            .output(MarkersComponentOutput::MarkersChanged(self.markers.markers().to_vec()))
+            .unwrap_or_default();
+    }
+
+    fn rebuild_rows(&mut self, widgets: &MarkersComponentModelWidgets, sender: &ComponentSender<Self>) {
+        for row in self.rows.drain(..) {
+            widgets.list_box.remove(&row);
+        }
+        for index in 0..self.markers.len() {
+            self.insert_row(widgets, sender, index);
+        }
+    }
+
+    // Insert the row of the marker at the index, the other rows are kept:
+    fn insert_row(&mut self, widgets: &MarkersComponentModelWidgets, sender: &ComponentSender<Self>, index: usize) {
+        let Some(marker) = self.markers.markers().get(index) else {
+            return;
+        };
+        let id = marker.id;
+        let sender = sender.clone();
+        let remove_button = gtk::Button::builder()
+            .icon_name(icon_names::CROSS_SMALL)
+            .tooltip_text(fl!("remove-marker"))
+            .css_classes([adw_css::FLAT])
+            .valign(gtk::Align::Center)
+            .build();
+        remove_button.connect_clicked(clone!(
+            #[strong]
+            sender,
+            move |_| {
+                sender.input(MarkersComponentInput::RemoveMarker(id));
+            }
+        ));
+        let row = adw::ActionRow::builder()
+            .title(gtk::glib::markup_escape_text(&marker.name))
+            .subtitle(marker.time_text())
+            .activatable(true)
+            .build();
+        row.add_suffix(&remove_button);
+        widgets.list_box.insert(&row, index as i32);
+        self.rows.insert(index, row);
+    }
+}
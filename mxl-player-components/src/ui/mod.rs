@@ -1,5 +1,9 @@
+pub mod clip_export_dialog;
 pub mod codec_ranking;
+pub mod gst_debug_preferences;
+pub mod markers;
 pub mod message_dialog;
+pub mod multi_player;
 pub mod player;
 pub mod playlist;
 pub mod video_offsets_dialog;
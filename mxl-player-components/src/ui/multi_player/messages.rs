@@ -0,0 +1,36 @@
+use crate::ui::player::messages::PlaybackState;
+
+#[derive(Debug)]
+pub enum MultiPlayerComponentInput {
+    SetUri(usize, String),
+    // Offset in seconds of a player relative to the shared timeline:
+    SetOffset(usize, f64),
+    SetVolume(usize, f64),
+    ChangeState(PlaybackState),
+    Seek(f64),
+    NextFrame,
+    SetSpeed(f64),
+    PrivateMessage(internal::PrivateMsg),
+}
+
+#[derive(Debug)]
+pub enum MultiPlayerComponentOutput {
+    PlayerInitialized(usize, Option<anyhow::Error>),
+    DurationChanged(f64),
+    PositionUpdated(f64),
+    SeekDone,
+    EndOfStream,
+    StateChanged(PlaybackState),
+    SpeedChanged(f64),
+    Warning(usize, anyhow::Error),
+    Error(usize, anyhow::Error),
+}
+
+pub(super) mod internal {
+    use crate::ui::player::messages::PlayerComponentOutput;
+
+    #[derive(Debug)]
+    pub enum PrivateMsg {
+        PlayerOutput(usize, PlayerComponentOutput),
+    }
+}
@@ -0,0 +1,47 @@
+use mxl_relm4_components::relm4::{Controller, gtk};
+
+use crate::ui::player::{messages::PlaybackState, model::PlayerComponentModel};
+
+pub struct MultiPlayerComponentInit {
+    pub players: usize,
+    pub orientation: gtk::Orientation,
+    pub seek_accurate: bool,
+    // Maximal difference in seconds between a player and the first player before all players are re-synchronized,
+    // by default about one frame at 25 fps:
+    pub max_drift: f64,
+}
+
+impl Default for MultiPlayerComponentInit {
+    fn default() -> Self {
+        Self {
+            players: 2,
+            orientation: gtk::Orientation::Horizontal,
+            seek_accurate: true,
+            max_drift: 0.04,
+        }
+    }
+}
+
+pub(super) struct MultiPlayerEntry {
+    pub(super) controller: Controller<PlayerComponentModel>,
+    pub(super) uri: Option<String>,
+    pub(super) offset: f64,
+    pub(super) duration: Option<f64>,
+    pub(super) state: PlaybackState,
+    pub(super) seeking: bool,
+}
+
+pub struct MultiPlayerComponentModel {
+    pub(super) players: Vec<MultiPlayerEntry>,
+    // Shared by all players, which are started with the same base time:
+    pub(super) clock: gst::Clock,
+    pub(super) max_drift: f64,
+    pub(super) state: PlaybackState,
+    pub(super) speed: f64,
+    // Position on the shared timeline as reported by the first player:
+    pub(super) timeline_position: f64,
+    pub(super) group_seek: bool,
+    pub(super) resume_after_seek: bool,
+    // Playback was requested while stopped, the players are started when all of them are paused:
+    pub(super) play_after_preroll: bool,
+}
@@ -0,0 +1,335 @@
+use log::*;
+use mxl_relm4_components::relm4::{self, gtk::prelude::*, prelude::*};
+
+use super::{
+    messages::{MultiPlayerComponentInput, MultiPlayerComponentOutput, internal::PrivateMsg},
+    model::{MultiPlayerComponentInit, MultiPlayerComponentModel, MultiPlayerEntry},
+};
+use crate::ui::player::{
+    messages::{PlaybackState, PlayerComponentInput, PlayerComponentOutput},
+    model::{PlayerComponentInit, PlayerComponentModel},
+};
+
+const SPACING: i32 = 6;
+// Time for all players to change to playing before the first frame is rendered:
+const START_DELAY_MS: u64 = 100;
+
+#[relm4::component(pub)]
+impl Component for MultiPlayerComponentModel {
+    type Init = MultiPlayerComponentInit;
+    type Input = MultiPlayerComponentInput;
+    type Output = MultiPlayerComponentOutput;
+    type CommandOutput = ();
+
+    view! {
+        gtk::Box {
+            set_orientation: init.orientation,
+            set_homogeneous: true,
+            set_spacing: SPACING,
+            set_hexpand: true,
+            set_vexpand: true,
+        }
+    }
+
+    // Initialize the component.
+    fn init(init: Self::Init, root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        // All players run on the same clock and are started with the same base time to keep them in sync:
+        let clock = gst::SystemClock::obtain();
+
+        let players = (0..init.players.max(1))
+            .map(|index| {
+                let controller = PlayerComponentModel::builder()
+                    .launch(PlayerComponentInit {
+                        seek_accurate: init.seek_accurate,
+                        clock: Some(clock.clone()),
+                        ..Default::default()
+                    })
+                    .forward(sender.input_sender(), move |msg| {
+                        MultiPlayerComponentInput::PrivateMessage(PrivateMsg::PlayerOutput(index, msg))
+                    });
+                MultiPlayerEntry {
+                    controller,
+                    uri: None,
+                    offset: 0.0,
+                    duration: None,
+                    state: PlaybackState::Stopped,
+                    seeking: false,
+                }
+            })
+            .collect();
+
+        let model = MultiPlayerComponentModel {
+            players,
+            clock,
+            max_drift: init.max_drift,
+            state: PlaybackState::Stopped,
+            speed: 1.0,
+            timeline_position: 0.0,
+            group_seek: false,
+            resume_after_seek: false,
+            play_after_preroll: false,
+        };
+
+        let widgets = view_output!();
+
+        for player in &model.players {
+            root.append(player.controller.widget());
+        }
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>, _: &Self::Root) {
+        match msg {
+            MultiPlayerComponentInput::SetUri(index, uri) => {
+                if let Some(player) = self.players.get_mut(index) {
+                    player.duration = None;
+                    player.controller.emit(PlayerComponentInput::UpdateUri(uri.clone()));
+                    player.uri = Some(uri);
+                }
+            }
+            MultiPlayerComponentInput::SetOffset(index, offset) => {
+                if let Some(player) = self.players.get_mut(index) {
+                    debug!("Set offset of player {index} to {offset}");
+                    player.offset = offset;
+                    // A single player cannot be moved without losing the shared base time:
+                    if player.uri.is_some() && self.state != PlaybackState::Stopped {
+                        self.seek(self.current_position(), self.state == PlaybackState::Playing);
+                    }
+                }
+            }
+            MultiPlayerComponentInput::SetVolume(index, volume) => {
+                if let Some(player) = self.players.get(index) {
+                    player.controller.emit(PlayerComponentInput::SetVolume(volume));
+                }
+            }
+            MultiPlayerComponentInput::ChangeState(state) => {
+                self.resume_after_seek = false;
+                self.play_after_preroll = false;
+                match (state, self.state) {
+                    (PlaybackState::Playing, PlaybackState::Playing) => (),
+                    // The players can only be seeked and started together after they prerolled:
+                    (PlaybackState::Playing, PlaybackState::Stopped | PlaybackState::Error) => {
+                        self.play_after_preroll = true;
+                        self.emit_all(|| PlayerComponentInput::ChangeState(PlaybackState::Paused));
+                    }
+                    // Every start seeks all players, so that they start at the same running time:
+                    (PlaybackState::Playing, _) => self.seek(self.current_position(), true),
+                    _ => self.emit_all(|| PlayerComponentInput::ChangeState(state)),
+                }
+            }
+            MultiPlayerComponentInput::Seek(position) => {
+                self.seek(position, self.state == PlaybackState::Playing || self.resume_after_seek)
+            }
+            MultiPlayerComponentInput::NextFrame => {
+                self.emit_all(|| PlayerComponentInput::NextFrame);
+            }
+            MultiPlayerComponentInput::SetSpeed(speed) => {
+                // The rate is applied with a flushing seek, which restarts the running time of each player:
+                self.emit_all(|| PlayerComponentInput::SetSpeed(speed));
+                if self.state != PlaybackState::Stopped {
+                    self.seek(self.current_position(), self.state == PlaybackState::Playing);
+                }
+            }
+            MultiPlayerComponentInput::PrivateMessage(msg) => match msg {
+                PrivateMsg::PlayerOutput(index, output) => self.player_output(index, output, &sender),
+            },
+        }
+    }
+}
+
+impl MultiPlayerComponentModel {
+    pub fn players(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn player(&self, index: usize) -> Option<&relm4::Controller<PlayerComponentModel>> {
+        self.players.get(index).map(|player| &player.controller)
+    }
+
+    pub fn offset(&self, index: usize) -> Option<f64> {
+        self.players.get(index).map(|player| player.offset)
+    }
+
+    pub fn position(&self) -> f64 {
+        self.timeline_position
+    }
+
+    // Position of a player on the shared timeline, queried from its pipeline:
+    pub fn player_position(&self, index: usize) -> Option<f64> {
+        let player = self.players.get(index)?;
+        let position = player.controller.model().player()?.position()?;
+        Some(position - player.offset)
+    }
+
+    fn emit_all(&self, msg: impl Fn() -> PlayerComponentInput) {
+        for player in self.players.iter().filter(|player| player.uri.is_some()) {
+            player.controller.emit(msg());
+        }
+    }
+
+    // Seek all players to the position on the shared timeline, playback is resumed when all seeks are done:
+    fn seek(&mut self, position: f64, resume: bool) {
+        let position = position.max(0.0);
+        debug!("Seek all players to {position}");
+        self.resume_after_seek = resume;
+        if self.state == PlaybackState::Playing {
+            self.emit_all(|| PlayerComponentInput::ChangeState(PlaybackState::Paused));
+        }
+        self.timeline_position = position;
+        self.group_seek = false;
+        for player in self.players.iter_mut().filter(|player| player.uri.is_some()) {
+            player.seeking = true;
+            self.group_seek = true;
+            player
+                .controller
+                .emit(PlayerComponentInput::Seek((position + player.offset).max(0.0)));
+        }
+    }
+
+    // Start all players with the same base time and latency, so that they render the same running time together:
+    fn start_synchronized(&self) {
+        let players: Vec<_> = self
+            .players
+            .iter()
+            .filter(|player| player.uri.is_some())
+            .map(|player| player.controller.model())
+            .collect();
+        let latency = players
+            .iter()
+            .filter_map(|model| model.player()?.latency())
+            .max()
+            .unwrap_or(gst::ClockTime::ZERO);
+        let base_time = self.clock.time() + gst::ClockTime::from_mseconds(START_DELAY_MS);
+        debug!("Start all players with base time {base_time} and latency {latency}");
+        for player in players.iter().filter_map(|model| model.player()) {
+            player.set_latency(Some(latency));
+            player.set_base_time(base_time);
+        }
+        drop(players);
+        self.emit_all(|| PlayerComponentInput::ChangeState(PlaybackState::Playing));
+    }
+
+    // Position on the shared timeline, the first player is queried for steps since the last position update:
+    fn current_position(&self) -> f64 {
+        self.player_position(0).unwrap_or(self.timeline_position)
+    }
+
+    fn correct_drift(&mut self, index: usize) {
+        if index == 0 || self.group_seek || self.state != PlaybackState::Playing {
+            return;
+        }
+        let (Some(timeline_position), Some(position)) = (self.player_position(0), self.player_position(index)) else {
+            return;
+        };
+        let drifted = self.players.get(index).is_some_and(|player| {
+            let expected = timeline_position + player.offset;
+            let within_media = expected >= 0.0 && player.duration.is_none_or(|duration| expected < duration);
+            !player.seeking && within_media && (position - timeline_position).abs() > self.max_drift
+        });
+        if drifted {
+            debug!("Player {index} drifted to {position}, expected {timeline_position}: re-synchronize all players");
+            self.seek(timeline_position, true);
+        }
+    }
+
+    fn player_output(&mut self, index: usize, output: PlayerComponentOutput, sender: &ComponentSender<Self>) {
+        let is_master = index == 0;
+        match output {
+            PlayerComponentOutput::PlayerInitialized(error) => {
+                sender
+                    .output(MultiPlayerComponentOutput::PlayerInitialized(index, error))
+                    .unwrap_or_default();
+            }
+            PlayerComponentOutput::DurationChanged(duration) => {
+                if let Some(player) = self.players.get_mut(index) {
+                    player.duration = Some(duration);
+                }
+                // The shared timeline covers the media of all players:
+                let timeline_duration = self
+                    .players
+                    .iter()
+                    .filter_map(|player| player.duration.map(|duration| duration - player.offset))
+                    .fold(0.0, f64::max);
+                sender
+                    .output(MultiPlayerComponentOutput::DurationChanged(timeline_duration))
+                    .unwrap_or_default();
+            }
+            PlayerComponentOutput::PositionUpdated(position) => {
+                if is_master {
+                    if !self.group_seek {
+                        self.timeline_position = position - self.players[0].offset;
+                    }
+                    sender
+                        .output(MultiPlayerComponentOutput::PositionUpdated(self.timeline_position))
+                        .unwrap_or_default();
+                } else {
+                    self.correct_drift(index);
+                }
+            }
+            PlayerComponentOutput::SeekDone => {
+                if let Some(player) = self.players.get_mut(index) {
+                    player.seeking = false;
+                }
+                if self.group_seek && !self.players.iter().any(|player| player.seeking) {
+                    self.group_seek = false;
+                    if self.resume_after_seek {
+                        self.resume_after_seek = false;
+                        self.start_synchronized();
+                    }
+                    sender.output(MultiPlayerComponentOutput::SeekDone).unwrap_or_default();
+                }
+            }
+            PlayerComponentOutput::EndOfStream(_) => {
+                if is_master {
+                    sender
+                        .output(MultiPlayerComponentOutput::EndOfStream)
+                        .unwrap_or_default();
+                }
+            }
+            PlayerComponentOutput::StateChanged(_, state) => {
+                if let Some(player) = self.players.get_mut(index) {
+                    player.state = state;
+                }
+                if self.play_after_preroll
+                    && self
+                        .players
+                        .iter()
+                        .filter(|player| player.uri.is_some())
+                        .all(|player| player.state == PlaybackState::Paused)
+                {
+                    self.play_after_preroll = false;
+                    self.seek(self.timeline_position, true);
+                }
+                if is_master {
+                    self.state = state;
+                    sender
+                        .output(MultiPlayerComponentOutput::StateChanged(state))
+                        .unwrap_or_default();
+                }
+            }
+            PlayerComponentOutput::SpeedChanged(speed) => {
+                if is_master {
+                    self.speed = speed;
+                    sender
+                        .output(MultiPlayerComponentOutput::SpeedChanged(speed))
+                        .unwrap_or_default();
+                }
+            }
+            PlayerComponentOutput::Warning(error) => {
+                sender
+                    .output(MultiPlayerComponentOutput::Warning(index, error))
+                    .unwrap_or_default();
+            }
+            PlayerComponentOutput::Error(error) => {
+                if let Some(player) = self.players.get_mut(index) {
+                    player.seeking = false;
+                }
+                sender
+                    .output(MultiPlayerComponentOutput::Error(index, error))
+                    .unwrap_or_default();
+            }
+            _ => (),
+        }
+    }
+}
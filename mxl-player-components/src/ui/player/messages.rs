@@ -1,4 +1,10 @@
 use gst_play::PlayMediaInfo;
+use gst_video::VideoRectangle;
+
+use crate::{
+    keyframe_index::{KeyframeIndex, SeekDirection},
+    video_filter::{VideoCrop, VideoOrientation},
+};
 
 #[derive(Debug)]
 pub enum Track {
@@ -7,22 +13,44 @@ pub enum Track {
     Stream(i32),
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SeekMode {
+    // Seek as configured with seek_accurate:
+    #[default]
+    Default,
+    // Snap the seek position to the nearest keyframe:
+    Keyframe,
+    // Seek to the keyframe before the position first and then accurately to the position:
+    Hybrid,
+}
+
 #[derive(Debug)]
 pub enum PlayerComponentInput {
     UpdateUri(String),
     ChangeState(PlaybackState),
     SwitchAudioTrack(Track),
     Seek(f64),
+    SeekKeyframe(SeekDirection),
+    SetSeekMode(SeekMode),
     NextFrame,
     SetVolume(f64),
     SetSpeed(f64),
     DumpPipeline(String),
     SetZoomRelative(f64),
     SetZoom(Option<f64>),
+    ZoomToRect(VideoRectangle),
+    PanTo(f64, f64),
+    SetMinimapVisible(bool),
     SetAudioVideoOffset(i64),
     SetSubtitleVideoOffset(i64),
     SetOverlayVisible(bool),
     RequestOverlayRedraw,
+    SetVideoOrientation(VideoOrientation),
+    SetVideoCrop(VideoCrop),
+    SetBrightness(f64),
+    SetContrast(f64),
+    SetHue(f64),
+    SetSaturation(f64),
     ReloadPlayer,
     PrivateMessage(internal::PrivateMsg),
 }
@@ -44,11 +72,14 @@ pub enum PlayerComponentOutput {
     PositionUpdated(f64),
     SeekDone,
     EndOfStream(std::string::String),
+    // Reverse playback reached the start of the media:
+    StartOfStream(std::string::String),
     StateChanged(Option<PlaybackState>, PlaybackState),
     VolumeChanged(f64),
     SpeedChanged(f64),
     AudioVideoOffsetChanged(i64),
     SubtitleVideoOffsetChanged(i64),
+    ViewChanged(VideoRectangle),
     Warning(anyhow::Error),
     Error(anyhow::Error),
 }
@@ -60,9 +91,12 @@ pub enum PlayerComponentCommand {
     DurationChanged(f64),
     SeekDone,
     EndOfStream(std::string::String),
+    StartOfStream(std::string::String),
     StateChanged(Option<PlaybackState>, PlaybackState),
     VideoDimensionsChanged(i32, i32),
+    KeyframeIndexUpdated(String, Option<KeyframeIndex>),
     VolumeChanged(f64),
+    SpeedChanged(f64),
     AudioVideoOffsetChanged(i64),
     SubtitleVideoOffsetChanged(i64),
     Warning(anyhow::Error),
@@ -76,5 +110,7 @@ pub(super) mod internal {
         DragUpdate(f64, f64),
         DragEnd(f64, f64),
         MotionDetected(f64, f64),
+        ViewportChanged,
+        LanguageChanged,
     }
 }
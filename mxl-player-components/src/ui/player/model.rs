@@ -1,5 +1,7 @@
+use crate::keyframe_index::KeyframeIndex;
 use crate::player::{MaxLateness, Player, PlayerBuilder};
-use crate::ui::player::messages::PlaybackState;
+use crate::ui::player::messages::{PlaybackState, SeekMode};
+use crate::video_filter::{VideoCrop, VideoOrientation};
 use log::*;
 use mxl_relm4_components::relm4::{gtk, gtk::prelude::*};
 use std::{rc::Rc, sync::Mutex};
@@ -8,10 +10,16 @@ type DrawCallbackFn = dyn Fn(&gtk::cairo::Context, &mut VideoViewData);
 
 pub struct PlayerComponentInit {
     pub seek_accurate: bool,
+    pub seek_mode: SeekMode,
+    // Build a keyframe index of every file in the background, required for the keyframe seek modes:
+    pub keyframe_index: bool,
     pub show_seeking_overlay: bool,
     pub compositor: Option<gst::Element>,
+    pub clock: Option<gst::Clock>,
     pub qos: bool,
+    pub pitch_correction: bool,
     pub max_lateness: MaxLateness,
+    pub show_minimap: bool,
     pub draw_callback: Option<Box<DrawCallbackFn>>,
     pub drag_gesture: Option<gtk::GestureDrag>,
     pub motion_tracker: Option<gtk::EventControllerMotion>,
@@ -21,10 +29,15 @@ impl Default for PlayerComponentInit {
     fn default() -> Self {
         Self {
             seek_accurate: true,
+            seek_mode: SeekMode::Default,
+            keyframe_index: false,
             show_seeking_overlay: false,
             compositor: None,
+            clock: None,
             qos: false,
+            pitch_correction: false,
             max_lateness: Default::default(),
+            show_minimap: false,
             draw_callback: None,
             drag_gesture: None,
             motion_tracker: None,
@@ -32,7 +45,7 @@ impl Default for PlayerComponentInit {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct VideoViewData {
     pub drawing_area: Option<gst_video::VideoRectangle>,
     pub view_rect: Option<gst_video::VideoRectangle>,
@@ -40,6 +53,9 @@ pub struct VideoViewData {
     pub scaled_paintable_rect: Option<gst_video::VideoRectangle>,
     pub fitted_paintable_rect: Option<gst_video::VideoRectangle>,
     pub zoom_factor: f64,
+    pub scroll_offset: (f64, f64),
+    pub video_orientation: VideoOrientation,
+    pub video_crop: VideoCrop,
     pub(super) cursor_widgets: Vec<gtk::Widget>,
     cursor_name: Option<String>,
 }
@@ -55,10 +71,22 @@ pub struct PlayerComponentModel {
     pub(super) playback_state: PlaybackState,
     pub(super) show_seeking_overlay: bool,
     pub(super) seeking: bool,
+    // Accuracy of the seeks in SeekMode::Default, the other modes select it for every seek:
+    pub(super) seek_accurate: bool,
+    pub(super) seek_mode: SeekMode,
+    // Position of the accurate second step of a hybrid seek:
+    pub(super) pending_seek: Option<f64>,
+    pub(super) uri: Option<String>,
+    pub(super) position: f64,
+    pub(super) build_keyframe_index: bool,
+    pub(super) keyframe_index: Option<KeyframeIndex>,
     pub(super) show_drawing_overlay: bool,
+    pub(super) show_minimap: bool,
+    pub(super) visible_video_rect: Option<gst_video::VideoRectangle>,
     pub(super) view_data: Rc<Mutex<ViewData>>,
     pub(super) drag_position: Option<(f64, f64)>,
     pub(super) mouse_position: Option<(f64, f64)>,
+    pub(super) _language_subscription: mxl_base::language::LanguageSubscription,
 }
 
 impl VideoViewData {
@@ -88,6 +116,193 @@ impl VideoViewData {
         }
     }
 
+    // Copy of the coordinate mapping without the cursor widgets, e.g. to map coordinates outside of the draw callback:
+    pub fn coordinate_mapping(&self) -> Self {
+        Self {
+            drawing_area: self.drawing_area,
+            view_rect: self.view_rect,
+            video_dimensions: self.video_dimensions,
+            scaled_paintable_rect: self.scaled_paintable_rect,
+            fitted_paintable_rect: self.fitted_paintable_rect,
+            zoom_factor: self.zoom_factor,
+            scroll_offset: self.scroll_offset,
+            video_orientation: self.video_orientation,
+            video_crop: self.video_crop,
+            cursor_widgets: Vec::new(),
+            cursor_name: None,
+        }
+    }
+
+    // Size of a video pixel on the drawing overlay, which includes the zoom factor:
+    pub fn video_scale(&self) -> Option<f64> {
+        self.content_scale()
+    }
+
+    // Map a point of the displayed picture in unscaled picture coordinates to the coordinates of the original video frame:
+    pub fn picture_to_video(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let dimensions = self.video_dimensions.as_ref()?;
+        let (x, y) = self
+            .video_orientation
+            .to_source(x, y, dimensions.w as f64, dimensions.h as f64);
+        Some((x + self.video_crop.left as f64, y + self.video_crop.top as f64))
+    }
+
+    // Map a point of the original video frame to the unscaled coordinates of the displayed picture:
+    pub fn video_to_picture(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let dimensions = self.video_dimensions.as_ref()?;
+        let (src_width, src_height) = if self.video_orientation.swaps_dimensions() {
+            (dimensions.h as f64, dimensions.w as f64)
+        } else {
+            (dimensions.w as f64, dimensions.h as f64)
+        };
+        Some(self.video_orientation.from_source(
+            x - self.video_crop.left as f64,
+            y - self.video_crop.top as f64,
+            src_width,
+            src_height,
+        ))
+    }
+
+    // Scale between unscaled picture coordinates and the coordinates of the scrolled content:
+    fn content_scale(&self) -> Option<f64> {
+        let dimensions = self.video_dimensions.as_ref()?;
+        let fitted_paintable_rect = self.fitted_paintable_rect.as_ref()?;
+        if dimensions.w > 0 {
+            Some(fitted_paintable_rect.w as f64 / dimensions.w as f64)
+        } else {
+            None
+        }
+    }
+
+    fn video_rect_to_picture(&self, rect: &gst_video::VideoRectangle) -> Option<(f64, f64, f64, f64)> {
+        let (x1, y1) = self.video_to_picture(rect.x as f64, rect.y as f64)?;
+        let (x2, y2) = self.video_to_picture((rect.x + rect.w) as f64, (rect.y + rect.h) as f64)?;
+        Some((x1.min(x2), y1.min(y2), (x2 - x1).abs(), (y2 - y1).abs()))
+    }
+
+    fn is_inside_picture(&self, x: f64, y: f64) -> bool {
+        self.video_dimensions
+            .as_ref()
+            .is_some_and(|dimensions| x >= 0.0 && y >= 0.0 && x <= dimensions.w as f64 && y <= dimensions.h as f64)
+    }
+
+    // Map a point of the drawing overlay (e.g. from the draw callback or the drag gesture) to the original video frame:
+    pub fn widget_to_video(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let fitted_paintable_rect = self.fitted_paintable_rect.as_ref()?;
+        let scale = self.content_scale()?;
+        let x = (x - fitted_paintable_rect.x as f64) / scale;
+        let y = (y - fitted_paintable_rect.y as f64) / scale;
+        if !self.is_inside_picture(x, y) {
+            return None;
+        }
+        self.picture_to_video(x, y)
+    }
+
+    // Map a point of the original video frame to the drawing overlay:
+    pub fn video_to_widget(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let fitted_paintable_rect = self.fitted_paintable_rect.as_ref()?;
+        let scale = self.content_scale()?;
+        let (x, y) = self.video_to_picture(x, y)?;
+        if !self.is_inside_picture(x, y) {
+            return None;
+        }
+        Some((
+            fitted_paintable_rect.x as f64 + x * scale,
+            fitted_paintable_rect.y as f64 + y * scale,
+        ))
+    }
+
+    // Map a point of the visible view (e.g. relative to the scrolled window) to the original video frame:
+    pub fn view_to_video(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let (scroll_x, scroll_y) = self.scroll_offset;
+        self.widget_to_video(x + scroll_x, y + scroll_y)
+    }
+
+    // Map a point of the original video frame to the visible view:
+    pub fn video_to_view(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let (scroll_x, scroll_y) = self.scroll_offset;
+        let (x, y) = self.video_to_widget(x, y)?;
+        Some((x - scroll_x, y - scroll_y))
+    }
+
+    // Calculate the zoom factor and the video coordinates of the center to fit the given video rectangle into the view:
+    pub(super) fn zoom_target_for_rect(&self, rect: &gst_video::VideoRectangle) -> Option<(f64, (f64, f64))> {
+        let view_rect = self.view_rect.as_ref()?;
+        let unzoomed_scale = self.content_scale()? / self.zoom_factor;
+        let (_, _, w, h) = self.video_rect_to_picture(rect)?;
+        if w <= 0.0 || h <= 0.0 || unzoomed_scale <= 0.0 {
+            return None;
+        }
+        let zoom_factor = (view_rect.w as f64 / (w * unzoomed_scale)).min(view_rect.h as f64 / (h * unzoomed_scale));
+        Some((
+            zoom_factor,
+            (rect.x as f64 + rect.w as f64 / 2.0, rect.y as f64 + rect.h as f64 / 2.0),
+        ))
+    }
+
+    // The part of the video frame that is currently visible in the view:
+    pub(super) fn visible_video_rect(&self) -> Option<gst_video::VideoRectangle> {
+        let view_rect = self.view_rect.as_ref()?;
+        let fitted_paintable_rect = self.fitted_paintable_rect.as_ref()?;
+        let scale = self.content_scale()?;
+        let (scroll_x, scroll_y) = self.scroll_offset;
+
+        let content_to_picture = |x: f64, y: f64| {
+            (
+                ((x - fitted_paintable_rect.x as f64) / scale).clamp(0.0, fitted_paintable_rect.w as f64 / scale),
+                ((y - fitted_paintable_rect.y as f64) / scale).clamp(0.0, fitted_paintable_rect.h as f64 / scale),
+            )
+        };
+        let (px1, py1) = content_to_picture(scroll_x, scroll_y);
+        let (px2, py2) = content_to_picture(scroll_x + view_rect.w as f64, scroll_y + view_rect.h as f64);
+
+        let (x1, y1) = self.picture_to_video(px1, py1)?;
+        let (x2, y2) = self.picture_to_video(px2, py2)?;
+        Some(gst_video::VideoRectangle::new(
+            x1.min(x2).round() as i32,
+            y1.min(y2).round() as i32,
+            (x2 - x1).abs().round() as i32,
+            (y2 - y1).abs().round() as i32,
+        ))
+    }
+
+    // Calculate the frame of the video and the visible viewport inside of a minimap with the given size:
+    pub(super) fn minimap_rects(
+        &self,
+        width: i32,
+        height: i32,
+    ) -> Option<(gst_video::VideoRectangle, (f64, f64, f64, f64))> {
+        let dimensions = self.video_dimensions.as_ref()?;
+        let view_rect = self.view_rect.as_ref()?;
+        let fitted_paintable_rect = self.fitted_paintable_rect.as_ref()?;
+        if fitted_paintable_rect.w <= 0 || fitted_paintable_rect.h <= 0 {
+            return None;
+        }
+
+        let frame =
+            gst_video::center_video_rectangle(dimensions, &gst_video::VideoRectangle::new(0, 0, width, height), true);
+
+        let (scroll_x, scroll_y) = self.scroll_offset;
+        let relative_x =
+            |x: f64| ((x - fitted_paintable_rect.x as f64) / fitted_paintable_rect.w as f64).clamp(0.0, 1.0);
+        let relative_y =
+            |y: f64| ((y - fitted_paintable_rect.y as f64) / fitted_paintable_rect.h as f64).clamp(0.0, 1.0);
+        let left = relative_x(scroll_x);
+        let right = relative_x(scroll_x + view_rect.w as f64);
+        let top = relative_y(scroll_y);
+        let bottom = relative_y(scroll_y + view_rect.h as f64);
+
+        Some((
+            frame.clone(),
+            (
+                frame.x as f64 + left * frame.w as f64,
+                frame.y as f64 + top * frame.h as f64,
+                (right - left) * frame.w as f64,
+                (bottom - top) * frame.h as f64,
+            ),
+        ))
+    }
+
     pub(super) fn update(
         &mut self,
         new_zoom_factor: Option<f64>,
@@ -7,16 +7,25 @@ use glib::clone;
 
 use super::{
     messages::{
-        PlaybackState, PlayerComponentCommand, PlayerComponentInput, PlayerComponentOutput, internal::PrivateMsg,
+        PlaybackState, PlayerComponentCommand, PlayerComponentInput, PlayerComponentOutput, SeekMode,
+        internal::PrivateMsg,
     },
     model::{PlayerComponentInit, PlayerComponentModel, ViewData},
 };
 use crate::{
+    active_elements::ActiveStream,
+    keyframe_index::KeyframeIndex,
     localization::helper::fl,
-    player::{MaxLateness, PlayerBuilder},
+    player::{MaxLateness, Player, PlayerBuilder},
 };
 
 const SCALE_MULTIPLIER: f64 = 2.0;
+const MIN_ZOOM: f64 = 1.0;
+const MAX_ZOOM: f64 = 10.0;
+const MINIMAP_WIDTH: i32 = 192;
+const MINIMAP_HEIGHT: i32 = 108;
+// Minimal distance in seconds to the previous keyframe to do the second step of a hybrid seek:
+const HYBRID_SEEK_TOLERANCE: f64 = 0.04;
 
 #[relm4::component(pub)]
 impl Component for PlayerComponentModel {
@@ -55,6 +64,27 @@ impl Component for PlayerComponentModel {
                 },
             },
 
+            add_overlay = minimap = &gtk::Overlay {
+                #[watch]
+                set_visible: model.show_minimap && model.is_zoomed() && model.playback_state != PlaybackState::Stopped && model.playback_state != PlaybackState::Error,
+                add_css_class: adw_css::OSD,
+                set_halign: gtk::Align::End,
+                set_valign: gtk::Align::End,
+                set_margin_end: 12,
+                set_margin_bottom: 12,
+                set_can_target: false,
+
+                #[name = "minimap_picture"]
+                gtk::Picture {
+                    set_content_fit: gtk::ContentFit::Contain,
+                    set_size_request: (MINIMAP_WIDTH, MINIMAP_HEIGHT),
+                },
+
+                add_overlay = minimap_viewport = &gtk::DrawingArea {
+                    set_can_target: false,
+                },
+            },
+
             add_overlay = overlay = &gtk::Box {
                 #[watch]
                 set_visible: model.show_seeking_overlay && model.playback_state == PlaybackState::Buffering,
@@ -97,7 +127,9 @@ impl Component for PlayerComponentModel {
 
         player_builder
             .seek_accurate(init.seek_accurate)
-            .compositor(init.compositor);
+            .compositor(init.compositor)
+            .clock(init.clock)
+            .pitch_correction(init.pitch_correction);
 
         let player = match player_builder.build(sender.command_sender().clone()) {
             Ok(player) => {
@@ -124,10 +156,23 @@ impl Component for PlayerComponentModel {
             playback_state: PlaybackState::Stopped,
             show_seeking_overlay: init.show_seeking_overlay,
             seeking: false,
+            seek_accurate: init.seek_accurate,
+            seek_mode: init.seek_mode,
+            pending_seek: None,
+            uri: None,
+            position: 0.0,
+            build_keyframe_index: init.keyframe_index,
+            keyframe_index: None,
             show_drawing_overlay: false,
+            show_minimap: init.show_minimap,
+            visible_video_rect: None,
             view_data: Rc::new(Mutex::new(ViewData::default())),
             drag_position: None,
             mouse_position: None,
+            _language_subscription: mxl_base::language::subscription({
+                let sender = sender.input_sender().clone();
+                move |_| sender.emit(PlayerComponentInput::PrivateMessage(PrivateMsg::LanguageChanged))
+            }),
         };
 
         // Insert the code generation of the view! macro here
@@ -135,6 +180,7 @@ impl Component for PlayerComponentModel {
 
         if let Some(player) = &model.player {
             widgets.video_picture.set_paintable(Some(player.paintable()).as_ref());
+            widgets.minimap_picture.set_paintable(Some(player.paintable()).as_ref());
         }
 
         {
@@ -168,11 +214,48 @@ impl Component for PlayerComponentModel {
                     view_data
                         .video_view
                         .update(None, &video_scrolled_window, &video_picture);
+                    view_data.video_view.scroll_offset = (
+                        video_scrolled_window.hadjustment().value(),
+                        video_scrolled_window.vadjustment().value(),
+                    );
                     (draw_callback)(context, view_data.video_view.borrow_mut());
                 }
             ));
         }
 
+        widgets.minimap_viewport.set_draw_func(clone!(
+            #[weak(rename_to = view_data)]
+            model.view_data,
+            move |_drawing_area, context, w, h| {
+                let view_data = view_data.lock().unwrap();
+                if let Some((frame, (x, y, viewport_w, viewport_h))) = view_data.video_view.minimap_rects(w, h) {
+                    context.set_source_rgba(0.0, 0.0, 0.0, 0.4);
+                    context.rectangle(frame.x as f64, frame.y as f64, frame.w as f64, frame.h as f64);
+                    context.rectangle(x, y, viewport_w, viewport_h);
+                    context.set_fill_rule(gtk::cairo::FillRule::EvenOdd);
+                    context.fill().unwrap_or_default();
+
+                    context.set_source_rgba(1.0, 1.0, 1.0, 0.9);
+                    context.set_line_width(2.0);
+                    context.rectangle(x, y, viewport_w, viewport_h);
+                    context.stroke().unwrap_or_default();
+                }
+            }
+        ));
+
+        for adjustment in [
+            widgets.video_scrolled_window.hadjustment(),
+            widgets.video_scrolled_window.vadjustment(),
+        ] {
+            adjustment.connect_value_changed(clone!(
+                #[strong]
+                sender,
+                move |_| {
+                    sender.input(PlayerComponentInput::PrivateMessage(PrivateMsg::ViewportChanged));
+                }
+            ));
+        }
+
         if let Some(drag_gesture) = init.drag_gesture {
             widgets.drawing_overlay.add_controller(drag_gesture);
         }
@@ -207,6 +290,7 @@ impl Component for PlayerComponentModel {
             match msg {
                 PlayerComponentInput::UpdateUri(uri) => {
                     player.set_uri(&uri);
+                    self.set_uri(uri, &sender);
                 }
                 PlayerComponentInput::ChangeState(state) => match state {
                     PlaybackState::Playing => player.play(),
@@ -222,7 +306,25 @@ impl Component for PlayerComponentModel {
                 }
                 PlayerComponentInput::Seek(to) => {
                     self.seeking = true;
-                    player.seek(&to);
+                    let (to, accurate) = self.seek_target(to);
+                    player.seek_with_accuracy(&to, accurate);
+                }
+                PlayerComponentInput::SeekKeyframe(direction) => {
+                    if let Some(keyframe) = self
+                        .keyframe_index
+                        .as_ref()
+                        .and_then(|index| index.step(self.position, direction))
+                    {
+                        self.seeking = true;
+                        self.pending_seek = None;
+                        self.position = keyframe;
+                        player.seek_with_accuracy(&keyframe, false);
+                    } else {
+                        debug!("No keyframe available to seek {direction:?} from {}", self.position);
+                    }
+                }
+                PlayerComponentInput::SetSeekMode(mode) => {
+                    self.seek_mode = mode;
                 }
                 PlayerComponentInput::NextFrame => {
                     player.next_frame();
@@ -232,9 +334,6 @@ impl Component for PlayerComponentModel {
                 }
                 PlayerComponentInput::SetSpeed(speed) => {
                     player.set_speed(speed);
-                    sender
-                        .output(PlayerComponentOutput::SpeedChanged(speed))
-                        .unwrap_or_default();
                 }
                 PlayerComponentInput::DumpPipeline(label) => {
                     player.dump_pipeline(&label);
@@ -247,14 +346,47 @@ impl Component for PlayerComponentModel {
                     };
                     self.set_zoom(
                         Some(scale),
+                        self.mouse_position,
                         &mut widgets.video_scrolled_window,
                         &mut widgets.video_picture,
                     );
-                    widgets.drawing_overlay.queue_draw();
+                    self.view_changed(widgets, &sender);
                 }
                 PlayerComponentInput::SetZoom(scale) => {
-                    self.set_zoom(scale, &mut widgets.video_scrolled_window, &mut widgets.video_picture);
-                    widgets.drawing_overlay.queue_draw();
+                    self.set_zoom(
+                        scale,
+                        self.mouse_position,
+                        &mut widgets.video_scrolled_window,
+                        &mut widgets.video_picture,
+                    );
+                    self.view_changed(widgets, &sender);
+                }
+                PlayerComponentInput::ZoomToRect(rect) => {
+                    let target = {
+                        let view_data = self.view_data.lock().unwrap();
+                        view_data.video_view.zoom_target_for_rect(&rect)
+                    };
+                    if let Some((scale, (x, y))) = target {
+                        trace!("Zoom to {rect:?} with zoom factor {scale}");
+                        self.set_zoom(
+                            Some(scale),
+                            None,
+                            &mut widgets.video_scrolled_window,
+                            &mut widgets.video_picture,
+                        );
+                        self.pan_to(x, y, &widgets.video_scrolled_window);
+                        self.view_changed(widgets, &sender);
+                    } else {
+                        debug!("Cannot zoom to {rect:?} without video dimensions");
+                    }
+                }
+                PlayerComponentInput::PanTo(x, y) => {
+                    self.pan_to(x, y, &widgets.video_scrolled_window);
+                    self.view_changed(widgets, &sender);
+                }
+                PlayerComponentInput::SetMinimapVisible(visible) => {
+                    self.show_minimap = visible;
+                    widgets.minimap_viewport.queue_draw();
                 }
                 PlayerComponentInput::SetAudioVideoOffset(offset) => {
                     self.player_builder.audio_offset(offset);
@@ -264,6 +396,34 @@ impl Component for PlayerComponentModel {
                     self.player_builder.subtitle_offset(offset);
                     player.set_subtitle_video_offset(offset);
                 }
+                PlayerComponentInput::SetVideoOrientation(orientation) => {
+                    self.player_builder.video_orientation(orientation);
+                    player.set_video_orientation(orientation);
+                    self.view_data.lock().unwrap().video_view.video_orientation = orientation;
+                    widgets.drawing_overlay.queue_draw();
+                }
+                PlayerComponentInput::SetVideoCrop(crop) => {
+                    self.player_builder.video_crop(crop);
+                    player.set_video_crop(&crop);
+                    self.view_data.lock().unwrap().video_view.video_crop = crop;
+                    widgets.drawing_overlay.queue_draw();
+                }
+                PlayerComponentInput::SetBrightness(brightness) => {
+                    self.player_builder.brightness(brightness);
+                    player.set_brightness(brightness);
+                }
+                PlayerComponentInput::SetContrast(contrast) => {
+                    self.player_builder.contrast(contrast);
+                    player.set_contrast(contrast);
+                }
+                PlayerComponentInput::SetHue(hue) => {
+                    self.player_builder.hue(hue);
+                    player.set_hue(hue);
+                }
+                PlayerComponentInput::SetSaturation(saturation) => {
+                    self.player_builder.saturation(saturation);
+                    player.set_saturation(saturation);
+                }
                 PlayerComponentInput::SetOverlayVisible(visible) => {
                     self.show_drawing_overlay = visible;
                     widgets.drawing_overlay.queue_draw();
@@ -273,6 +433,7 @@ impl Component for PlayerComponentModel {
                     self.player = match self.player_builder.build(sender.command_sender().clone()) {
                         Ok(player) => {
                             widgets.video_picture.set_paintable(Some(player.paintable()).as_ref());
+                            widgets.minimap_picture.set_paintable(Some(player.paintable()).as_ref());
                             Some(player)
                         }
                         Err(error) => {
@@ -285,6 +446,11 @@ impl Component for PlayerComponentModel {
                     PrivateMsg::MotionDetected(x, y) => {
                         self.mouse_position = Some((x, y));
                     }
+                    PrivateMsg::ViewportChanged => {
+                        self.view_changed(widgets, &sender);
+                    }
+                    // The translated labels are watched and updated with the view:
+                    PrivateMsg::LanguageChanged => {}
                     PrivateMsg::DragBegin(_, _) => {
                         // Start the drag position at 0.0, 0.0:
                         self.drag_position = Some((0.0, 0.0));
@@ -351,19 +517,42 @@ impl Component for PlayerComponentModel {
                     .unwrap_or_default();
             }
             PlayerComponentCommand::PositionUpdated(pos) => {
+                self.position = pos;
                 sender
                     .output(PlayerComponentOutput::PositionUpdated(pos))
                     .unwrap_or_default();
             }
             PlayerComponentCommand::SeekDone => {
-                self.seeking = false;
-                sender.output(PlayerComponentOutput::SeekDone).unwrap_or_default();
+                if let Some(to) = self.pending_seek.take()
+                    && let Some(player) = &self.player
+                {
+                    // Second step of a hybrid seek, which is accurate independent of seek_accurate:
+                    player.seek_with_accuracy(&to, true);
+                } else {
+                    self.seeking = false;
+                    sender.output(PlayerComponentOutput::SeekDone).unwrap_or_default();
+                }
+            }
+            PlayerComponentCommand::KeyframeIndexUpdated(uri, index) => {
+                if self.uri.as_ref() == Some(&uri) {
+                    self.keyframe_index = index;
+                }
             }
             PlayerComponentCommand::EndOfStream(val) => {
                 sender
                     .output(PlayerComponentOutput::EndOfStream(val))
                     .unwrap_or_default();
             }
+            PlayerComponentCommand::StartOfStream(val) => {
+                sender
+                    .output(PlayerComponentOutput::StartOfStream(val))
+                    .unwrap_or_default();
+            }
+            PlayerComponentCommand::SpeedChanged(speed) => {
+                sender
+                    .output(PlayerComponentOutput::SpeedChanged(speed))
+                    .unwrap_or_default();
+            }
             PlayerComponentCommand::StateChanged(old_state, new_state) => {
                 self.playback_state = new_state;
                 let reset_states = match new_state {
@@ -407,6 +596,17 @@ impl Component for PlayerComponentModel {
 }
 
 impl PlayerComponentModel {
+    pub fn player(&self) -> Option<&Player> {
+        self.player.as_ref()
+    }
+
+    pub fn active_elements(&self) -> Vec<ActiveStream> {
+        self.player
+            .as_ref()
+            .map(|player| player.active_elements())
+            .unwrap_or_default()
+    }
+
     pub fn set_qos(&self, qos: bool) {
         if let Some(player) = &self.player {
             player.set_qos(qos);
@@ -423,16 +623,63 @@ impl PlayerComponentModel {
         }
     }
 
+    pub fn keyframe_index(&self) -> Option<&KeyframeIndex> {
+        self.keyframe_index.as_ref()
+    }
+
+    fn set_uri(&mut self, uri: String, sender: &ComponentSender<Self>) {
+        self.keyframe_index = None;
+        self.pending_seek = None;
+        self.position = 0.0;
+        if self.build_keyframe_index {
+            let index_uri = uri.clone();
+            sender.oneshot_command(async move {
+                let index = match KeyframeIndex::load_or_build_async(&index_uri).await {
+                    Ok(index) => Some(index),
+                    Err(error) => {
+                        warn!("Keyframe seeking is not available: {error:?}");
+                        None
+                    }
+                };
+                PlayerComponentCommand::KeyframeIndexUpdated(index_uri, index)
+            });
+        }
+        self.uri = Some(uri);
+    }
+
+    // Position and accuracy of the (first) seek according to the seek mode:
+    fn seek_target(&mut self, to: f64) -> (f64, bool) {
+        self.pending_seek = None;
+        match (self.seek_mode, &self.keyframe_index) {
+            (SeekMode::Keyframe, Some(index)) => (index.nearest(to).unwrap_or(to), false),
+            (SeekMode::Hybrid, Some(index)) => match index.at_or_before(to) {
+                Some(keyframe) if to - keyframe > HYBRID_SEEK_TOLERANCE => {
+                    self.pending_seek = Some(to);
+                    (keyframe, false)
+                }
+                _ => (to, true),
+            },
+            // Without a keyframe index a hybrid seek is done accurately in one step:
+            (SeekMode::Hybrid, None) => (to, true),
+            _ => (to, self.seek_accurate),
+        }
+    }
+
+    fn is_zoomed(&self) -> bool {
+        self.view_data.lock().unwrap().video_view.zoom_factor != 1.0
+    }
+
     fn set_zoom(
         &mut self,
         new_scale: Option<f64>,
+        anchor: Option<(f64, f64)>,
         video_scrolled_window: &mut gtk::ScrolledWindow,
         video_picture: &mut gtk::Picture,
     ) {
         let mut view_data = self.view_data.lock().unwrap();
 
         let old_zoom = view_data.video_view.zoom_factor;
-        let new_scale = new_scale.unwrap_or(1.0).clamp(1.0, 10.0);
+        let new_scale = new_scale.unwrap_or(MIN_ZOOM).clamp(MIN_ZOOM, MAX_ZOOM);
         trace!("New zoom: {new_scale}");
 
         view_data
@@ -460,18 +707,18 @@ impl PlayerComponentModel {
             video_picture.set_width_request(fitted_paintable_rect.w);
             video_picture.set_height_request(fitted_paintable_rect.h);
 
-            // Adjust scrolled window viewport to the mouse position:
-            if let Some((x, y)) = self.mouse_position {
-                let ha = video_scrolled_window.hadjustment();
-                let va = video_scrolled_window.vadjustment();
+            let ha = video_scrolled_window.hadjustment();
+            let va = video_scrolled_window.vadjustment();
 
-                // Adjust the scrollbar range to the new zoom level.
-                // It is very important to have one step for rescaling and updating
-                // the viewport. If we wait for the upper value of each scrollbar to be
-                // updated, the video image flickers on each zoom.
-                ha.set_upper(fitted_paintable_rect.w as f64);
-                va.set_upper(fitted_paintable_rect.h as f64);
+            // Adjust the scrollbar range to the new zoom level.
+            // It is very important to have one step for rescaling and updating
+            // the viewport. If we wait for the upper value of each scrollbar to be
+            // updated, the video image flickers on each zoom.
+            ha.set_upper(fitted_paintable_rect.w as f64);
+            va.set_upper(fitted_paintable_rect.h as f64);
 
+            // Adjust scrolled window viewport to the anchor position:
+            if let Some((x, y)) = anchor {
                 // Translate the relative pointer position to the actual video image coordinates:
                 let view_point = video_scrolled_window
                     .compute_point(video_picture, &gtk::graphene::Point::new(x as f32, y as f32))
@@ -496,6 +743,51 @@ impl PlayerComponentModel {
         }
     }
 
+    fn pan_to(&self, x: f64, y: f64, video_scrolled_window: &gtk::ScrolledWindow) {
+        let view_data = self.view_data.lock().unwrap();
+        if let (Some((content_x, content_y)), Some(view_rect)) = (
+            view_data.video_view.video_to_widget(x, y),
+            view_data.video_view.view_rect.as_ref(),
+        ) {
+            trace!("Pan view to x={x} y={y}");
+            // Center the viewport on the given position:
+            video_scrolled_window
+                .hadjustment()
+                .set_value(content_x - view_rect.w as f64 / 2.0);
+            video_scrolled_window
+                .vadjustment()
+                .set_value(content_y - view_rect.h as f64 / 2.0);
+        } else {
+            debug!("Cannot pan to x={x} y={y} without video dimensions");
+        }
+    }
+
+    fn view_changed(&mut self, widgets: &PlayerComponentModelWidgets, sender: &ComponentSender<Self>) {
+        let visible_video_rect = {
+            let mut view_data = self.view_data.lock().unwrap();
+            if widgets.video_picture.paintable().is_some() {
+                view_data
+                    .video_view
+                    .update(None, &widgets.video_scrolled_window, &widgets.video_picture);
+            }
+            view_data.video_view.scroll_offset = (
+                widgets.video_scrolled_window.hadjustment().value(),
+                widgets.video_scrolled_window.vadjustment().value(),
+            );
+            view_data.video_view.visible_video_rect()
+        };
+        widgets.drawing_overlay.queue_draw();
+        widgets.minimap_viewport.queue_draw();
+        if visible_video_rect.is_some() && visible_video_rect != self.visible_video_rect {
+            self.visible_video_rect = visible_video_rect.clone();
+            if let Some(rect) = visible_video_rect {
+                sender
+                    .output(PlayerComponentOutput::ViewChanged(rect))
+                    .unwrap_or_default();
+            }
+        }
+    }
+
     fn new_gesture_drag(&self, sender: ComponentSender<Self>) -> gtk::GestureDrag {
         let drag = gtk::GestureDrag::builder().button(gtk::gdk::BUTTON_PRIMARY).build();
 
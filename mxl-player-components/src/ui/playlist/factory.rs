@@ -15,7 +15,14 @@ use std::path::{Path, PathBuf};
 
 use glib::clone;
 
-use crate::localization::helper::fl;
+use crate::{
+    localization::helper::fl,
+    markers::MarkerMediaInfo,
+    tag_helpers::{
+        TAG_CURRENT_UUID, TAG_NEXT_UUID, TAG_PREVIOUS_UUID, TAG_RECORDER_MACHINE_ID, media_info_get_global_tag,
+    },
+    thumbnails::ThumbnailGenerator,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DropState {
@@ -34,6 +41,7 @@ pub struct PlaylistEntryInit {
     pub movable: bool,
     pub removable: bool,
     pub drop_files_to_add: bool,
+    pub thumbnails: Option<ThumbnailGenerator>,
 }
 
 #[derive(Debug)]
@@ -59,6 +67,8 @@ pub struct PlaylistEntryModel {
     pub next_uuid: Option<String>,
     pub recorder_machine_id: Option<String>,
     pub media_info: Option<DiscovererInfo>,
+    pub thumbnails: Option<ThumbnailGenerator>,
+    pub poster: Option<gtk::gdk::Texture>,
     pub notify_debouncer: Option<Debouncer<RecommendedWatcher, RecommendedCache>>,
 }
 
@@ -70,9 +80,11 @@ pub enum PlaylistEntryInput {
     FetchMetadata,
     UpdateMetadata(DiscovererInfo),
     UpdateMetadataError(String),
+    UpdatePoster(Option<gtk::gdk::Texture>),
     SetDropState(DropState),
     EnterEvent,
     LeaveEvent,
+    LanguageChanged,
 }
 
 #[derive(Debug)]
@@ -83,16 +95,17 @@ pub enum PlaylistEntryOutput {
     AddBefore(DynamicIndex, Vec<PathBuf>),
     AddAfter(DynamicIndex, Vec<PathBuf>),
     FetchMetadata(String, relm4::Sender<PlaylistEntryInput>),
+    // Request the poster frame at the position in seconds, it is created in the thread pool of the playlist:
+    FetchPoster(String, f64, relm4::Sender<PlaylistEntryInput>),
 }
 
 const NOTIFY_TIMEOUT_SECS: u64 = 2;
 const SPACING: i32 = 12;
 const MARGIN: i32 = 4;
-
-const TAG_CURRENT_UUID: &str = "CURRENT_UUID";
-const TAG_NEXT_UUID: &str = "NEXT_UUID";
-const TAG_PREVIOUS_UUID: &str = "PREVIOUS_UUID";
-const TAG_RECORDER_MACHINE_ID: &str = "RECORDER_MACHINE_ID";
+const POSTER_WIDTH: i32 = 80;
+const POSTER_HEIGHT: i32 = 45;
+// Position in seconds of the poster frame, limited to the middle of shorter files:
+const POSTER_POSITION: f64 = 10.0;
 
 #[relm4::factory(pub)]
 impl FactoryComponent for PlaylistEntryModel {
@@ -163,6 +176,7 @@ impl FactoryComponent for PlaylistEntryModel {
                             set_margin_all: 0,
                             set_valign: gtk::Align::Center,
                             set_icon_name: icon_names::WARNING_OUTLINE,
+                            #[watch]
                             set_tooltip_text: Some(fl!("retry-fetch-metadata").as_str()),
                             set_use_underline: true,
                             add_css_class: adw_css::FLAT,
@@ -170,6 +184,18 @@ impl FactoryComponent for PlaylistEntryModel {
                             },
                     },
 
+                    #[name(poster)]
+                    gtk::Picture {
+                        set_valign: gtk::Align::Center,
+                        set_size_request: (POSTER_WIDTH, POSTER_HEIGHT),
+                        set_content_fit: gtk::ContentFit::Cover,
+                        set_can_shrink: true,
+                        #[watch]
+                        set_visible: self.poster.is_some(),
+                        #[watch]
+                        set_paintable: self.poster.as_ref(),
+                    },
+
                     gtk::Box {
                         set_orientation: gtk::Orientation::Vertical,
 
@@ -258,6 +284,7 @@ impl FactoryComponent for PlaylistEntryModel {
 
                         gtk::Button {
                             set_icon_name: icon_names::CROSS_SMALL,
+                            #[watch]
                             set_tooltip_text: Some(&fl!("remove-file", "desc")),
                             add_css_class: adw_css::DESTRUCTIVE_ACTION,
                             set_valign: gtk::Align::Center,
@@ -313,12 +340,15 @@ impl FactoryComponent for PlaylistEntryModel {
             previous_uuid: None,
             recorder_machine_id: None,
             media_info: None,
+            thumbnails: init.thumbnails,
+            poster: None,
             notify_debouncer,
         };
 
         // Apply already known media info to the new playlist entry or send the fetch request:
         if let Some(media_info) = init.media_info {
             model.update_media_info(media_info);
+            model.fetch_poster(&sender);
         } else {
             sender.input(PlaylistEntryInput::FetchMetadata);
         }
@@ -537,6 +567,9 @@ impl FactoryComponent for PlaylistEntryModel {
             PlaylistEntryInput::UpdateMetadataError(error) => {
                 self.update_metadata(&sender, Err(anyhow::anyhow!("{}", error)));
             }
+            PlaylistEntryInput::UpdatePoster(poster) => {
+                self.poster = poster;
+            }
             PlaylistEntryInput::SetDropState(state) => match state {
                 DropState::None => {
                     widgets.above.set_css_classes(&[adw_css::SPACER]);
@@ -557,12 +590,23 @@ impl FactoryComponent for PlaylistEntryModel {
             PlaylistEntryInput::LeaveEvent => {
                 widgets.remove_button_revealer.set_reveal_child(false);
             }
+            // The translated tooltips are watched and updated with the view:
+            PlaylistEntryInput::LanguageChanged => {}
         }
         self.update_view(widgets, sender)
     }
 }
 
 impl PlaylistEntryModel {
+    // Media information for the marker export, the tags are available after the metadata was fetched:
+    pub fn marker_media_info(&self) -> MarkerMediaInfo {
+        MarkerMediaInfo {
+            uri: self.uri.clone(),
+            recorder_machine_id: self.recorder_machine_id.clone(),
+            current_uuid: self.current_uuid.clone(),
+        }
+    }
+
     fn update_metadata(&mut self, sender: &FactorySender<Self>, result: Result<DiscovererInfo>) {
         self.updating = false;
         self.duration = None;
@@ -570,8 +614,14 @@ impl PlaylistEntryModel {
         self.error = None;
         self.info_tooltip = None;
         match result {
-            Err(error) => self.error = Some(error),
-            Ok(info) => self.update_media_info(info),
+            Err(error) => {
+                self.error = Some(error);
+                self.poster = None;
+            }
+            Ok(info) => {
+                self.update_media_info(info);
+                self.fetch_poster(sender);
+            }
         }
         if let Some(error) = &self.error {
             self.info_text = format!("{error:?}");
@@ -582,6 +632,23 @@ impl PlaylistEntryModel {
             .unwrap_or_default();
     }
 
+    fn fetch_poster(&self, sender: &FactorySender<Self>) {
+        let has_video = self
+            .media_info
+            .as_ref()
+            .is_some_and(|info| !info.video_streams().is_empty());
+        if self.thumbnails.is_some() && has_video {
+            let position = self
+                .duration
+                .map_or(0.0, |duration| POSTER_POSITION.min(duration / 2.0));
+            sender.output_sender().emit(PlaylistEntryOutput::FetchPoster(
+                self.uri.clone(),
+                position,
+                sender.input_sender().clone(),
+            ));
+        }
+    }
+
     fn update_media_info(&mut self, info: DiscovererInfo) {
         trace_media_info(&info);
         self.uri = info.uri().to_string();
@@ -662,66 +729,6 @@ impl PlaylistEntryModel {
     }
 }
 
-fn media_info_get_global_tag(name: &str, info: &DiscovererInfo) -> Option<String> {
-    if info.result() == DiscovererResult::Ok {
-        if let Some(info) = info.stream_info()
-            && let Some(info) = info.downcast_ref::<gst_pbutils::DiscovererContainerInfo>()
-            && let Some(tag) = find_tag_in_tag_list(name, info.tags().as_ref())
-        {
-            return Some(tag);
-        }
-        for stream in info.container_streams() {
-            if let Some(tag) = find_tag_in_tag_list(name, stream.tags().as_ref()) {
-                return Some(tag);
-            }
-        }
-    }
-    None
-}
-
-fn find_tag_in_tag_list(search_name: &str, tags: Option<&TagList>) -> Option<String> {
-    if let Some(tags) = tags {
-        for (name, values) in tags.iter_generic() {
-            for value in values {
-                if let Some(tag) = get_tag_value(search_name, name, value) {
-                    return Some(tag);
-                }
-            }
-        }
-    }
-    None
-}
-
-fn get_tag_value(search_name: &str, name: &str, value: &gst::glib::value::SendValue) -> Option<String> {
-    let get_tag_value = |value: &gst::glib::value::SendValue| -> Option<String> {
-        if let Ok(s) = value.get::<&str>() {
-            Some(s.to_string())
-        } else {
-            None
-        }
-    };
-
-    if let Some(value) = get_tag_value(value) {
-        if name == gst::tags::ExtendedComment::TAG_NAME {
-            if let Ok(ext_comment) = gst_tag::tag_parse_extended_comment(&value, true) {
-                if ext_comment.key.unwrap_or_default() == search_name {
-                    Some(ext_comment.value.to_string())
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        } else if name == search_name {
-            Some(value)
-        } else {
-            None
-        }
-    } else {
-        None
-    }
-}
-
 fn trace_media_info(info: &DiscovererInfo) {
     let mut tree = termtree::Tree::new(format!("URI: {}", info.uri()));
     match info.result() {
@@ -54,6 +54,12 @@ pub enum PlaylistComponentInput {
         String,
         mxl_relm4_components::relm4::Sender<super::factory::PlaylistEntryInput>,
     ),
+    FetchPosterForUri(
+        String,
+        f64,
+        mxl_relm4_components::relm4::Sender<super::factory::PlaylistEntryInput>,
+    ),
+    LanguageChanged,
 }
 
 #[derive(Debug)]
@@ -5,6 +5,8 @@ use mxl_relm4_components::relm4::{
 };
 use std::path::PathBuf;
 
+use crate::markers::MarkerMediaInfo;
+use crate::thumbnails::ThumbnailGenerator;
 use crate::ui::playlist::messages::{
     PlaylistChange, PlaylistCommandOutput, PlaylistComponentInput, PlaylistComponentOutput, PlaylistState, RepeatMode,
     SortOrder,
@@ -36,6 +38,9 @@ pub struct PlaylistComponentInit {
     pub repeat: RepeatMode,
     pub is_user_mutable: bool,
     pub show_file_index: bool,
+    // Generator for the poster frames of the playlist entries, e.g. Some(ThumbnailGenerator::default()).
+    // No poster frames are shown by default:
+    pub thumbnails: Option<ThumbnailGenerator>,
 }
 
 impl Default for PlaylistComponentInit {
@@ -46,6 +51,7 @@ impl Default for PlaylistComponentInit {
             repeat: RepeatMode::default(),
             is_user_mutable: true,
             show_file_index: false,
+            thumbnails: None,
         }
     }
 }
@@ -59,6 +65,9 @@ pub struct PlaylistComponentModel {
     pub repeat: RepeatMode,
     pub thread_pool: Option<rusty_pool::ThreadPool>,
     pub is_user_mutable: bool,
+    pub thumbnails: Option<ThumbnailGenerator>,
+    pub(super) sort_menu: gtk::gio::Menu,
+    pub(super) _language_subscription: mxl_base::language::LanguageSubscription,
 }
 
 #[allow(dead_code)]
@@ -84,6 +93,12 @@ impl PlaylistComponentModel {
         self.index.as_ref()
     }
 
+    // Media information of the active entry for the markers component:
+    pub fn active_marker_media_info(&self) -> Option<MarkerMediaInfo> {
+        let index = self.index.as_ref()?.current_index();
+        self.uris.get(index).map(|entry| entry.marker_media_info())
+    }
+
     pub fn new_drop_target(sender: Sender<PlaylistComponentInput>) -> gtk::DropTarget {
         let formats = gtk::gdk::ContentFormatsBuilder::new()
             .add_type(gtk::gdk::FileList::static_type())
@@ -157,6 +172,7 @@ impl PlaylistComponentModel {
                             movable: self.is_user_mutable,
                             removable: self.is_user_mutable,
                             drop_files_to_add: self.is_user_mutable,
+                            thumbnails: self.thumbnails.clone(),
                         }
                     );
                 }
@@ -179,6 +195,7 @@ impl PlaylistComponentModel {
                             movable: self.is_user_mutable,
                             removable: self.is_user_mutable,
                             drop_files_to_add: self.is_user_mutable,
+                            thumbnails: self.thumbnails.clone(),
                         }
                     );
                 }
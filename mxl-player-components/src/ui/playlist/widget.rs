@@ -39,11 +39,16 @@ impl Component for PlaylistComponentModel {
             adw::HeaderBar {
                 set_css_classes: &[adw_css::FLAT],
                 set_show_end_title_buttons: false,
-                set_title_widget: Some(&gtk::Label::new(Some(&fl!("playlist")))),
+                #[wrap(Some)]
+                set_title_widget = &gtk::Label {
+                    #[watch]
+                    set_label: &fl!("playlist"),
+                },
                 pack_start = &gtk::Button {
                     #[watch]
                     set_visible: model.is_user_mutable,
                     set_has_tooltip: true,
+                    #[watch]
                     set_tooltip_text: Some(&fl!("add-file")),
                     set_icon_name: icon_names::PLUS,
                     set_css_classes: &[adw_css::FLAT, "image-button"],
@@ -69,23 +74,13 @@ impl Component for PlaylistComponentModel {
                     }
                 },
                  pack_end = &gtk::MenuButton {
+                    #[watch]
                     set_label: &fl!("sort-by"),
                     #[watch]
                     set_visible: model.is_user_mutable,
 
 
-                    set_menu_model: Some(&{
-                        let menu_model = gtk::gio::Menu::new();
-                        menu_model.append(
-                            Some(&fl!("sort-by", "start-time")),
-                            Some(&SortByStartTime::action_name()),
-                        );
-                        menu_model.append(
-                            Some(&fl!("sort-by", "file-name")),
-                            Some(&SortByShortUri::action_name()),
-                        );
-                        menu_model
-                    }),
+                    set_menu_model: Some(&model.sort_menu),
                 }
             },
 
@@ -114,7 +109,9 @@ impl Component for PlaylistComponentModel {
                     set_visible: model.show_placeholder,
                     set_vexpand: true,
                     set_icon_name: Some(icon_names::VIDEO_CLIP_MULTIPLE_REGULAR),
+                    #[watch]
                     set_title: &fl!("playlist-empty"),
+                    #[watch]
                     set_description: Some(&fl!("playlist-empty", "desc")),
                 }
             }
@@ -159,6 +156,9 @@ impl Component for PlaylistComponentModel {
                     PlaylistEntryOutput::AddBefore(index, files) => Self::Input::AddBefore(index, files),
                     PlaylistEntryOutput::AddAfter(index, files) => Self::Input::AddAfter(index, files),
                     PlaylistEntryOutput::FetchMetadata(uri, sender) => Self::Input::FetchMetadataForUri(uri, sender),
+                    PlaylistEntryOutput::FetchPoster(uri, position, sender) => {
+                        Self::Input::FetchPosterForUri(uri, position, sender)
+                    }
                 });
 
         let mut model = PlaylistComponentModel {
@@ -170,7 +170,14 @@ impl Component for PlaylistComponentModel {
             repeat: init.repeat,
             thread_pool: Some(PlaylistComponentModel::init_thread_pool()),
             is_user_mutable: init.is_user_mutable,
+            thumbnails: init.thumbnails,
+            sort_menu: gtk::gio::Menu::new(),
+            _language_subscription: mxl_base::language::subscription({
+                let sender = sender.input_sender().clone();
+                move |_| sender.emit(PlaylistComponentInput::LanguageChanged)
+            }),
         };
+        model.translate_sort_menu();
 
         // Add URIs to model:
         model.add_uris(&sender, InsertMode::Back, &init.uris);
@@ -343,6 +350,11 @@ impl Component for PlaylistComponentModel {
                 };
                 debug!("Change repeat to {:?}", self.repeat);
             }
+            PlaylistComponentInput::LanguageChanged => {
+                // The other translated labels are watched and updated with the view:
+                self.translate_sort_menu();
+                self.uris.broadcast(PlaylistEntryInput::LanguageChanged);
+            }
             PlaylistComponentInput::FetchMetadataForUri(uri, sender) => {
                 if let Some(pool) = &self.thread_pool {
                     pool.execute({
@@ -356,6 +368,21 @@ impl Component for PlaylistComponentModel {
                     });
                 }
             }
+            // The poster frames share the bounded thread pool with the metadata, so that a large playlist
+            // does not decode all of them at once:
+            PlaylistComponentInput::FetchPosterForUri(uri, position, sender) => {
+                if let Some(pool) = &self.thread_pool
+                    && let Some(thumbnails) = self.thumbnails.clone()
+                {
+                    pool.execute(move || {
+                        let poster = thumbnails
+                            .preview_at(&uri, position)
+                            .inspect_err(|error| warn!("Cannot create poster frame for '{uri}': {error:?}"))
+                            .ok();
+                        sender.emit(PlaylistEntryInput::UpdatePoster(poster));
+                    });
+                }
+            }
         }
     }
 
@@ -375,3 +402,15 @@ fn get_media_info(uri: &str) -> Result<DiscovererInfo> {
 
     Ok(info)
 }
+
+impl PlaylistComponentModel {
+    fn translate_sort_menu(&self) {
+        self.sort_menu.remove_all();
+        self.sort_menu.append(
+            Some(&fl!("sort-by", "start-time")),
+            Some(&SortByStartTime::action_name()),
+        );
+        self.sort_menu
+            .append(Some(&fl!("sort-by", "file-name")), Some(&SortByShortUri::action_name()));
+    }
+}
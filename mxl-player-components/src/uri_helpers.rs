@@ -1,10 +1,10 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use log::*;
 use mxl_relm4_components::relm4::gtk::{
     gio::{File, prelude::FileExt},
     glib,
 };
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub fn uri_from_pathbuf(path: &Path) -> Result<String> {
     if let Some(path_string) = path.to_str() {
@@ -27,3 +27,40 @@ pub fn uri_from_pathbuf(path: &Path) -> Result<String> {
         path.to_str().unwrap_or_default()
     ))
 }
+
+pub fn pathbuf_from_uri(uri: &str) -> Result<PathBuf> {
+    File::for_uri(uri)
+        .path()
+        .with_context(|| format!("The URI {uri} is not a local file"))
+}
+
+// Path of a file that is stored next to the media file of the given URI, e.g. "video.mp4.<extension>":
+pub fn sidecar_path_from_uri(uri: &str, extension: &str) -> Result<PathBuf> {
+    let path = pathbuf_from_uri(uri)?;
+    let mut file_name = path
+        .file_name()
+        .with_context(|| format!("The path {path:?} has no file name"))?
+        .to_os_string();
+    file_name.push(".");
+    file_name.push(extension);
+    Ok(path.with_file_name(file_name))
+}
+
+// Key for cached data of a media, it contains the modification time and size of local files to detect changes:
+pub fn media_cache_key(uri: &str) -> String {
+    let mut key = uri.to_string();
+    if let Ok(path) = pathbuf_from_uri(uri)
+        && let Ok(metadata) = std::fs::metadata(path)
+    {
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        key.push_str(&format!(":{modified}:{}", metadata.len()));
+    }
+    glib::compute_checksum_for_string(glib::ChecksumType::Sha256, &key)
+        .map(|checksum| checksum.to_string())
+        .unwrap_or(key)
+}
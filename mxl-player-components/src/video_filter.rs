@@ -0,0 +1,168 @@
+use anyhow::{Context, Result};
+use gst::prelude::*;
+use log::*;
+
+const VIDEO_FILTER_BIN_NAME: &str = "mxl_video_filter";
+
+const BRIGHTNESS_RANGE: (f64, f64) = (-1.0, 1.0);
+const CONTRAST_RANGE: (f64, f64) = (0.0, 2.0);
+const HUE_RANGE: (f64, f64) = (-1.0, 1.0);
+const SATURATION_RANGE: (f64, f64) = (0.0, 2.0);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VideoOrientation {
+    #[default]
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl VideoOrientation {
+    fn nick(&self) -> &'static str {
+        match self {
+            VideoOrientation::Identity => "identity",
+            VideoOrientation::Rotate90 => "90r",
+            VideoOrientation::Rotate180 => "180",
+            VideoOrientation::Rotate270 => "90l",
+            VideoOrientation::FlipHorizontal => "horiz",
+            VideoOrientation::FlipVertical => "vert",
+            VideoOrientation::Transpose => "ul-lr",
+            VideoOrientation::AntiTranspose => "ur-ll",
+        }
+    }
+
+    pub fn swaps_dimensions(&self) -> bool {
+        matches!(
+            self,
+            VideoOrientation::Rotate90
+                | VideoOrientation::Rotate270
+                | VideoOrientation::Transpose
+                | VideoOrientation::AntiTranspose
+        )
+    }
+
+    // Map a point of the oriented picture with the given size back to the unoriented source picture:
+    pub fn to_source(&self, x: f64, y: f64, width: f64, height: f64) -> (f64, f64) {
+        let (src_w, src_h) = if self.swaps_dimensions() {
+            (height, width)
+        } else {
+            (width, height)
+        };
+        match self {
+            VideoOrientation::Identity => (x, y),
+            VideoOrientation::Rotate90 => (y, src_h - x),
+            VideoOrientation::Rotate180 => (src_w - x, src_h - y),
+            VideoOrientation::Rotate270 => (src_w - y, x),
+            VideoOrientation::FlipHorizontal => (src_w - x, y),
+            VideoOrientation::FlipVertical => (x, src_h - y),
+            VideoOrientation::Transpose => (y, x),
+            VideoOrientation::AntiTranspose => (src_w - y, src_h - x),
+        }
+    }
+
+    // Map a point of the unoriented source picture with the given size into the oriented picture:
+    pub fn from_source(&self, x: f64, y: f64, src_width: f64, src_height: f64) -> (f64, f64) {
+        match self {
+            VideoOrientation::Identity => (x, y),
+            VideoOrientation::Rotate90 => (src_height - y, x),
+            VideoOrientation::Rotate180 => (src_width - x, src_height - y),
+            VideoOrientation::Rotate270 => (y, src_width - x),
+            VideoOrientation::FlipHorizontal => (src_width - x, y),
+            VideoOrientation::FlipVertical => (x, src_height - y),
+            VideoOrientation::Transpose => (y, x),
+            VideoOrientation::AntiTranspose => (src_height - y, src_width - x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VideoCrop {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+}
+
+#[derive(Debug)]
+pub struct VideoFilter {
+    bin: gst::Bin,
+    crop: gst::Element,
+    flip: gst::Element,
+    balance: gst::Element,
+}
+
+impl VideoFilter {
+    pub fn new() -> Result<Self> {
+        let crop = gst::ElementFactory::make("videocrop")
+            .build()
+            .with_context(|| "Cannot create video crop element")?;
+        let flip = gst::ElementFactory::make("videoflip")
+            .build()
+            .with_context(|| "Cannot create video flip element")?;
+        let balance = gst::ElementFactory::make("videobalance")
+            .build()
+            .with_context(|| "Cannot create video balance element")?;
+
+        let bin = gst::Bin::builder().name(VIDEO_FILTER_BIN_NAME).build();
+        bin.add_many([&crop, &flip, &balance])?;
+        gst::Element::link_many([&crop, &flip, &balance])?;
+
+        let sink_pad = crop.static_pad("sink").with_context(|| "Video crop has no sink pad")?;
+        let src_pad = balance
+            .static_pad("src")
+            .with_context(|| "Video balance has no src pad")?;
+        bin.add_pad(&gst::GhostPad::with_target(&sink_pad)?)?;
+        bin.add_pad(&gst::GhostPad::with_target(&src_pad)?)?;
+
+        Ok(Self {
+            bin,
+            crop,
+            flip,
+            balance,
+        })
+    }
+
+    pub fn element(&self) -> &gst::Element {
+        self.bin.upcast_ref()
+    }
+
+    pub fn set_orientation(&self, orientation: VideoOrientation) {
+        debug!("Set video orientation to {orientation:?}");
+        self.flip.set_property_from_str("video-direction", orientation.nick());
+    }
+
+    pub fn set_crop(&self, crop: &VideoCrop) {
+        debug!("Set video crop to {crop:?}");
+        self.crop.set_property("left", crop.left as i32);
+        self.crop.set_property("right", crop.right as i32);
+        self.crop.set_property("top", crop.top as i32);
+        self.crop.set_property("bottom", crop.bottom as i32);
+    }
+
+    pub fn set_brightness(&self, brightness: f64) {
+        Self::set_balance_property(&self.balance, "brightness", brightness, BRIGHTNESS_RANGE);
+    }
+
+    pub fn set_contrast(&self, contrast: f64) {
+        Self::set_balance_property(&self.balance, "contrast", contrast, CONTRAST_RANGE);
+    }
+
+    pub fn set_hue(&self, hue: f64) {
+        Self::set_balance_property(&self.balance, "hue", hue, HUE_RANGE);
+    }
+
+    pub fn set_saturation(&self, saturation: f64) {
+        Self::set_balance_property(&self.balance, "saturation", saturation, SATURATION_RANGE);
+    }
+
+    fn set_balance_property(balance: &gst::Element, name: &str, value: f64, (min, max): (f64, f64)) {
+        let value = value.clamp(min, max);
+        debug!("Set video {name} to {value}");
+        balance.set_property(name, value);
+    }
+}
@@ -0,0 +1,126 @@
+use mxl_player_components::{
+    active_elements::{self, ActiveStream, StreamMediaType},
+    gst::{self, prelude::*},
+    uri_helpers,
+};
+use std::path::Path;
+
+// Bring the pipeline to the paused state, so that the streams are linked and the caps are known:
+fn preroll(pipeline: &gst::Element) {
+    pipeline.set_state(gst::State::Paused).unwrap();
+    let (result, _, _) = pipeline.state(gst::ClockTime::from_seconds(10));
+    result.unwrap();
+}
+
+fn run_to_end(pipeline: &gst::Element) {
+    pipeline.set_state(gst::State::Playing).unwrap();
+    let bus = pipeline.bus().unwrap();
+    let message = bus
+        .timed_pop_filtered(
+            gst::ClockTime::from_seconds(10),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        )
+        .unwrap();
+    assert!(matches!(message.view(), gst::MessageView::Eos(_)), "{message:?}");
+    pipeline.set_state(gst::State::Null).unwrap();
+}
+
+fn streams_of(streams: &[ActiveStream], media_type: StreamMediaType) -> Vec<&ActiveStream> {
+    streams
+        .iter()
+        .filter(|stream| stream.media_type == media_type)
+        .collect()
+}
+
+fn sink_factory(stream: &ActiveStream) -> Option<&str> {
+    stream.sink.as_ref().map(|sink| sink.factory.as_str())
+}
+
+#[test]
+fn each_playbin_stream_gets_an_entry() {
+    gst::init().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("streams.mkv");
+
+    // A file with one video and two audio streams:
+    let recorder = gst::parse::launch(&format!(
+        "matroskamux name=mux ! filesink location={path:?} \
+         videotestsrc num-buffers=25 ! video/x-raw,width=320,height=240,framerate=25/1 ! jpegenc ! mux. \
+         audiotestsrc num-buffers=25 freq=440 ! audio/x-raw,channels=1 ! mux. \
+         audiotestsrc num-buffers=25 freq=880 ! audio/x-raw,channels=1 ! mux."
+    ))
+    .unwrap();
+    run_to_end(&recorder);
+
+    let playbin = gst::ElementFactory::make("playbin")
+        .property("uri", uri_helpers::uri_from_pathbuf(&path).unwrap())
+        .property("video-sink", gst::ElementFactory::make("fakesink").build().unwrap())
+        .property("audio-sink", gst::ElementFactory::make("fakesink").build().unwrap())
+        .build()
+        .unwrap();
+    preroll(&playbin);
+    let streams = active_elements::collect(&playbin);
+    playbin.set_state(gst::State::Null).unwrap();
+
+    let video = streams_of(&streams, StreamMediaType::Video);
+    assert_eq!(video.len(), 1, "{streams:#?}");
+    assert_eq!(
+        video[0].decoder.as_ref().map(|decoder| decoder.factory.as_str()),
+        Some("jpegdec")
+    );
+    assert_eq!(sink_factory(video[0]), Some("fakesink"));
+
+    // Both audio streams are reported, only the current one is rendered by the sink:
+    let audio = streams_of(&streams, StreamMediaType::Audio);
+    assert_eq!(audio.len(), 2, "{streams:#?}");
+    assert_eq!(audio.iter().filter(|stream| stream.sink.is_some()).count(), 1);
+    assert_eq!(sink_factory(audio[0]), Some("fakesink"));
+}
+
+#[test]
+fn pipeline_without_playbin() {
+    gst::init().unwrap();
+    let pipeline = gst::parse::launch(
+        "videotestsrc ! video/x-raw,width=320,height=240 ! fakesink name=video_sink \
+         audiotestsrc ! audio/x-raw,channels=1 ! fakesink name=audio_sink",
+    )
+    .unwrap();
+    preroll(&pipeline);
+    let streams = active_elements::collect(&pipeline);
+    pipeline.set_state(gst::State::Null).unwrap();
+
+    let video = streams_of(&streams, StreamMediaType::Video);
+    assert_eq!(video.len(), 1, "{streams:#?}");
+    assert_eq!(
+        video[0].sink.as_ref().map(|sink| sink.name.as_str()),
+        Some("video_sink")
+    );
+    let audio = streams_of(&streams, StreamMediaType::Audio);
+    assert_eq!(audio.len(), 1, "{streams:#?}");
+    assert_eq!(
+        audio[0].sink.as_ref().map(|sink| sink.name.as_str()),
+        Some("audio_sink")
+    );
+
+    assert!(!active_elements::report(&streams).is_empty());
+}
+
+#[test]
+fn data_file_has_a_decoder() {
+    gst::init().unwrap();
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/Big_Buck_Bunny_720_10s_2MB.mp4");
+    let playbin = gst::ElementFactory::make("playbin")
+        .property("uri", uri_helpers::uri_from_pathbuf(&path).unwrap())
+        .property("video-sink", gst::ElementFactory::make("fakesink").build().unwrap())
+        .property("audio-sink", gst::ElementFactory::make("fakesink").build().unwrap())
+        .build()
+        .unwrap();
+    preroll(&playbin);
+    let streams = active_elements::collect(&playbin);
+    playbin.set_state(gst::State::Null).unwrap();
+
+    let video = streams_of(&streams, StreamMediaType::Video);
+    assert_eq!(video.len(), 1, "{streams:#?}");
+    assert!(video[0].decoder.is_some(), "{streams:#?}");
+    assert!(video[0].parser.is_some(), "{streams:#?}");
+}
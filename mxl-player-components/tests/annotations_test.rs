@@ -0,0 +1,161 @@
+use mxl_player_components::annotations::{Annotation, AnnotationLayer, Color, Point, Shape};
+
+fn point(x: f64, y: f64) -> Point {
+    Point { x, y }
+}
+
+fn annotation(shape: Shape, start: f64, end: f64) -> Annotation {
+    Annotation {
+        shape,
+        start,
+        end,
+        color: Color::default(),
+        line_width: 3.0,
+    }
+}
+
+#[test]
+fn bounding_box() {
+    let rectangle = Shape::Rectangle {
+        from: point(100.0, 80.0),
+        to: point(20.0, 40.0),
+    };
+    assert_eq!(rectangle.bounding_box(), Some((point(20.0, 40.0), point(100.0, 80.0))));
+
+    let freehand = Shape::Freehand {
+        points: vec![point(10.0, 50.0), point(30.0, 5.0), point(20.0, 70.0)],
+    };
+    assert_eq!(freehand.bounding_box(), Some((point(10.0, 5.0), point(30.0, 70.0))));
+    assert_eq!(Shape::Freehand { points: vec![] }.bounding_box(), None);
+
+    // The text grows to the right of and above the baseline:
+    let text = Shape::Text {
+        position: point(100.0, 200.0),
+        text: "abcd".to_string(),
+        size: 10.0,
+    };
+    let (min, max) = text.bounding_box().unwrap();
+    assert_eq!(min, point(100.0, 190.0));
+    assert!((max.x - 124.0).abs() < 1e-9 && max.y == 200.0);
+}
+
+#[test]
+fn contains() {
+    let arrow = Shape::Arrow {
+        from: point(100.0, 100.0),
+        to: point(200.0, 150.0),
+    };
+    assert!(arrow.contains(&point(150.0, 120.0)));
+    // Points close to the shape hit it too:
+    assert!(arrow.contains(&point(95.0, 155.0)));
+    assert!(!arrow.contains(&point(80.0, 120.0)));
+    assert!(!arrow.contains(&point(150.0, 170.0)));
+}
+
+#[test]
+fn visible_at() {
+    let annotation = annotation(
+        Shape::Rectangle {
+            from: point(0.0, 0.0),
+            to: point(1.0, 1.0),
+        },
+        2.0,
+        7.0,
+    );
+    assert!(!annotation.is_visible_at(1.9));
+    assert!(annotation.is_visible_at(2.0));
+    assert!(annotation.is_visible_at(5.0));
+    assert!(annotation.is_visible_at(7.0));
+    assert!(!annotation.is_visible_at(7.1));
+
+    let mut layer = AnnotationLayer::new();
+    layer.add(annotation);
+    layer.set_position(1.0);
+    assert_eq!(layer.visible_annotations().count(), 0);
+    layer.set_position(3.0);
+    assert_eq!(layer.visible_annotations().count(), 1);
+}
+
+#[test]
+fn save_and_load() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let path = tmp_dir.path().join("video.mp4.annotations.json");
+
+    let mut layer = AnnotationLayer::new();
+    layer.add(annotation(
+        Shape::Rectangle {
+            from: point(10.0, 20.0),
+            to: point(30.0, 40.0),
+        },
+        0.0,
+        5.0,
+    ));
+    layer.add(annotation(
+        Shape::Text {
+            position: point(50.0, 60.0),
+            text: "Look here".to_string(),
+            size: 32.0,
+        },
+        1.5,
+        6.5,
+    ));
+    layer.add(annotation(
+        Shape::Freehand {
+            points: vec![point(1.0, 2.0), point(3.0, 4.0)],
+        },
+        2.0,
+        3.0,
+    ));
+    layer.save(&path).unwrap();
+
+    let mut loaded = AnnotationLayer::new();
+    loaded.load(&path).unwrap();
+    assert_eq!(loaded.annotations(), layer.annotations());
+
+    // The file is replaced without leaving a temporary file:
+    loaded.clear();
+    loaded.save(&path).unwrap();
+    assert_eq!(std::fs::read_dir(tmp_dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn failed_save_keeps_file() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let path = tmp_dir.path().join("video.mp4.annotations.json");
+    let mut layer = AnnotationLayer::new();
+    layer.add(annotation(
+        Shape::Arrow {
+            from: point(0.0, 0.0),
+            to: point(1.0, 1.0),
+        },
+        0.0,
+        1.0,
+    ));
+    layer.save(&path).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+
+    // The temporary file cannot be created, if a directory has its name:
+    std::fs::create_dir(tmp_dir.path().join("video.mp4.annotations.json.tmp")).unwrap();
+    assert!(AnnotationLayer::new().save(&path).is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+}
+
+#[test]
+fn load_unknown_version() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let path = tmp_dir.path().join("video.mp4.annotations.json");
+    std::fs::write(&path, r#"{"version": 2, "annotations": []}"#).unwrap();
+
+    let mut layer = AnnotationLayer::new();
+    layer.add(annotation(
+        Shape::Arrow {
+            from: point(0.0, 0.0),
+            to: point(1.0, 1.0),
+        },
+        0.0,
+        1.0,
+    ));
+    assert!(layer.load(&path).is_err());
+    // The current annotations are kept:
+    assert_eq!(layer.annotations().len(), 1);
+}
@@ -0,0 +1,212 @@
+use mxl_player_components::{
+    clip_export::{self, ClipContainer, ClipExportCancellation, ClipExportMode, ClipExportSettings},
+    gst,
+    gst_pbutils::{self, prelude::*},
+    tag_helpers::{self, TAG_CURRENT_UUID, TAG_RECORDER_MACHINE_ID},
+    uri_helpers,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+const CURRENT_UUID: &str = "5d1c7a52-3f0e-4b8a-9c61-0d2e4f6a8b10";
+const RECORDER_MACHINE_ID: &str = "recorder-42";
+
+fn test_file() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/Big_Buck_Bunny_720_10s_2MB.mp4")
+}
+
+fn settings(start: f64, end: f64, output: PathBuf, mode: ClipExportMode) -> ClipExportSettings {
+    ClipExportSettings {
+        uri: uri_helpers::uri_from_pathbuf(&test_file()).unwrap(),
+        start,
+        end,
+        output,
+        mode,
+    }
+}
+
+fn export(settings: &ClipExportSettings) -> Vec<f64> {
+    let progress = Mutex::new(Vec::new());
+    clip_export::export_clip(
+        settings,
+        &|value| progress.lock().unwrap().push(value),
+        &ClipExportCancellation::new(),
+    )
+    .unwrap();
+    let progress = progress.into_inner().unwrap();
+    assert_eq!(progress.last(), Some(&1.0));
+    assert!(progress.iter().all(|value| (0.0..=1.0).contains(value)));
+    progress
+}
+
+fn discover(path: &Path) -> gst_pbutils::DiscovererInfo {
+    let discoverer = gst_pbutils::Discoverer::new(gst::ClockTime::from_seconds(10)).unwrap();
+    let info = discoverer
+        .discover_uri(&uri_helpers::uri_from_pathbuf(path).unwrap())
+        .unwrap();
+    assert!(!info.video_streams().is_empty());
+    info
+}
+
+fn duration(info: &gst_pbutils::DiscovererInfo) -> f64 {
+    info.duration().unwrap().seconds_f64()
+}
+
+// Remux the video of the test file into a file with media identification tags, like a recorder writes them:
+fn create_tagged_file(path: &Path) {
+    let pipeline = gst::parse::launch(&format!(
+        "filesrc location={:?} ! qtdemux name=demux demux.video_0 ! queue ! h264parse ! matroskamux name=mux \
+         ! filesink location={path:?}",
+        test_file()
+    ))
+    .unwrap()
+    .downcast::<gst::Pipeline>()
+    .unwrap();
+    let mux = pipeline.by_name("mux").unwrap();
+    let setter = mux.dynamic_cast_ref::<gst::TagSetter>().unwrap();
+    for (name, value) in [
+        (TAG_CURRENT_UUID, CURRENT_UUID),
+        (TAG_RECORDER_MACHINE_ID, RECORDER_MACHINE_ID),
+    ] {
+        setter.add_tag::<gst::tags::ExtendedComment>(&format!("{name}={value}").as_str(), gst::TagMergeMode::Append);
+    }
+
+    pipeline.set_state(gst::State::Playing).unwrap();
+    let message = pipeline
+        .bus()
+        .unwrap()
+        .timed_pop_filtered(
+            gst::ClockTime::from_seconds(30),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        )
+        .unwrap();
+    pipeline.set_state(gst::State::Null).unwrap();
+    assert!(matches!(message.view(), gst::MessageView::Eos(_)), "{message:?}");
+}
+
+#[test]
+fn container_from_path() {
+    assert_eq!(
+        ClipContainer::from_path(Path::new("clip.mp4")),
+        Some(ClipContainer::Mp4)
+    );
+    assert_eq!(
+        ClipContainer::from_path(Path::new("clip.M4V")),
+        Some(ClipContainer::Mp4)
+    );
+    assert_eq!(
+        ClipContainer::from_path(Path::new("/tmp/clip.mov")),
+        Some(ClipContainer::QuickTime)
+    );
+    assert_eq!(
+        ClipContainer::from_path(Path::new("clip.mkv")),
+        Some(ClipContainer::Matroska)
+    );
+    for name in ["clip.ts", "clip.mts", "clip.m2ts"] {
+        assert_eq!(ClipContainer::from_path(Path::new(name)), Some(ClipContainer::MpegTs));
+    }
+    assert_eq!(ClipContainer::from_path(Path::new("clip.avi")), None);
+    assert_eq!(ClipContainer::from_path(Path::new("clip")), None);
+}
+
+#[test]
+fn invalid_range_and_container() {
+    gst::init().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("clip.mp4");
+    let cancellation = ClipExportCancellation::new();
+    for (start, end) in [(-1.0, 2.0), (3.0, 3.0), (5.0, 2.0)] {
+        let settings = settings(start, end, output.clone(), ClipExportMode::StreamCopy);
+        assert!(clip_export::export_clip(&settings, &|_| {}, &cancellation).is_err());
+    }
+    let settings = settings(1.0, 2.0, dir.path().join("clip.avi"), ClipExportMode::StreamCopy);
+    assert!(clip_export::export_clip(&settings, &|_| {}, &cancellation).is_err());
+    assert!(!output.exists());
+}
+
+#[test]
+fn stream_copy_export() {
+    gst::init().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("clip.mkv");
+    export(&settings(2.0, 5.0, output.clone(), ClipExportMode::StreamCopy));
+
+    // A stream copy starts at the keyframe before the start, so the clip can be longer than requested:
+    let duration = duration(&discover(&output));
+    assert!((2.5..=5.5).contains(&duration), "Unexpected clip duration {duration}");
+}
+
+// The muxer of mp4 files writes its header when the seek after the preroll has flushed the pipeline:
+#[test]
+fn stream_copy_export_to_mp4() {
+    gst::init().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("clip.mp4");
+    export(&settings(2.0, 5.0, output.clone(), ClipExportMode::StreamCopy));
+
+    let duration = duration(&discover(&output));
+    assert!((2.5..=5.5).contains(&duration), "Unexpected clip duration {duration}");
+}
+
+#[test]
+fn re_encode_export() {
+    gst::init().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("clip.mp4");
+    export(&settings(2.0, 5.0, output.clone(), ClipExportMode::ReEncode));
+
+    // A re-encode cuts at the requested positions:
+    let info = discover(&output);
+    let duration = duration(&info);
+    assert!((2.8..=3.3).contains(&duration), "Unexpected clip duration {duration}");
+    let caps = info.video_streams()[0].caps().unwrap();
+    assert_eq!(caps.structure(0).unwrap().name(), "video/x-h264");
+}
+
+#[test]
+fn media_id_tags_are_kept() {
+    gst::init().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("recording.mkv");
+    create_tagged_file(&source);
+    let info = discover(&source);
+    assert_eq!(
+        tag_helpers::media_info_get_global_tag(TAG_CURRENT_UUID, &info).as_deref(),
+        Some(CURRENT_UUID)
+    );
+
+    let output = dir.path().join("clip.mkv");
+    export(&ClipExportSettings {
+        uri: uri_helpers::uri_from_pathbuf(&source).unwrap(),
+        ..settings(2.0, 5.0, output.clone(), ClipExportMode::StreamCopy)
+    });
+    let info = discover(&output);
+    assert_eq!(
+        tag_helpers::media_info_get_global_tag(TAG_CURRENT_UUID, &info).as_deref(),
+        Some(CURRENT_UUID)
+    );
+    assert_eq!(
+        tag_helpers::media_info_get_global_tag(TAG_RECORDER_MACHINE_ID, &info).as_deref(),
+        Some(RECORDER_MACHINE_ID)
+    );
+}
+
+#[test]
+fn cancelled_export_removes_output() {
+    gst::init().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("clip.mp4");
+    let cancellation = ClipExportCancellation::new();
+    cancellation.cancel();
+    assert!(
+        clip_export::export_clip(
+            &settings(0.0, 5.0, output.clone(), ClipExportMode::Auto),
+            &|_| {},
+            &cancellation
+        )
+        .is_err()
+    );
+    assert!(!output.exists());
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "pipeline_svg")]
+
+use mxl_player_components::dot_render;
+
+const DOT: &str = r##"digraph pipeline {
+  rankdir=LR;
+  nodesep=.1;
+  ranksep=.2;
+  node [style="filled,rounded", shape=box, fontsize="9", fontname="sans", margin="0.0,0.0"];
+  subgraph cluster_src {
+    label="GstVideoTestSrc\nvideotestsrc0";
+    src_pad [fillcolor="#ffaaaa", label="src\n[>][bfb]", height="0.2", style="filled,solid"];
+  }
+  subgraph cluster_sink {
+    label="GstFakeSink\nfakesink0";
+    sink_pad [fillcolor="#aaaaff", label="sink\n[>][bfb]", height="0.2", style="filled,solid"];
+  }
+  src_pad -> sink_pad [label="video/x-raw\l"]
+}
+"##;
+
+#[test]
+fn render_gstreamer_dot() {
+    let svg = dot_render::dot_to_svg(DOT).unwrap();
+    assert!(svg.contains("<svg"));
+    assert!(svg.contains("video/x-raw"));
+    // The clusters of the elements are flattened:
+    assert!(!svg.contains("GstVideoTestSrc"));
+}
+
+#[test]
+fn render_svg_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let dot_path = dir.path().join("pipeline.dot");
+    let svg_path = dir.path().join("pipeline.svg");
+    std::fs::write(&dot_path, DOT).unwrap();
+    dot_render::render_svg_file(&dot_path, &svg_path).unwrap();
+    assert!(std::fs::read_to_string(svg_path).unwrap().contains("</svg>"));
+}
+
+#[test]
+fn invalid_dot() {
+    assert!(dot_render::dot_to_svg("digraph { a -> }").is_err());
+}
@@ -0,0 +1,93 @@
+use mxl_player_components::{gst, gst_helpers};
+use std::sync::Mutex;
+
+static LOGGED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// Collects the messages, which GStreamer forwards to the application log:
+struct TestLogger;
+
+impl log::Log for TestLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        LOGGED.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn parse_gst_debug() {
+    let entries = gst_helpers::parse_gst_debug("play:6, decodebin*:debug,3").unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            (Some("play".to_string()), gst::DebugLevel::Log),
+            (Some("decodebin*".to_string()), gst::DebugLevel::Debug),
+            (None, gst::DebugLevel::Fixme),
+        ]
+    );
+    assert!(gst_helpers::parse_gst_debug("").unwrap().is_empty());
+    assert!(gst_helpers::parse_gst_debug("play:10").is_err());
+    assert!(gst_helpers::parse_gst_debug(":5").is_err());
+    assert!(gst_helpers::parse_gst_debug("play:loud").is_err());
+}
+
+#[test]
+fn set_gst_debug_with_log_file() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    log::set_logger(&TestLogger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    gst_helpers::init(&tmp_dir.path().join("cache"));
+    gst::init().unwrap();
+    let category = gst::DebugCategory::new("mxltest", gst::DebugColorFlags::empty(), Some("Test category"));
+
+    gst_helpers::set_gst_debug("mxltest:5").unwrap();
+    assert_eq!(gst_helpers::gst_debug().as_deref(), Some("mxltest:5"));
+    assert_eq!(category.threshold(), gst::DebugLevel::Debug);
+    assert!(gst_helpers::set_gst_debug("mxltest:invalid").is_err());
+    assert_eq!(category.threshold(), gst::DebugLevel::Debug);
+
+    let log_file = tmp_dir.path().join("proc").join("gstreamer.log");
+    gst_helpers::set_gst_debug_log_file(Some(&log_file)).unwrap();
+    assert_eq!(gst_helpers::gst_debug_log_file(), Some(log_file.clone()));
+    gst::debug!(category, "Message for the debug log");
+    gst::log!(category, "Message below the threshold");
+    gst::warning!(category, "Warning for both logs");
+    gst_helpers::set_gst_debug_log_file(None).unwrap();
+
+    let content = std::fs::read_to_string(&log_file).unwrap();
+    assert!(content.contains("Message for the debug log"));
+    assert!(!content.contains("Message below the threshold"));
+    assert!(content.contains("Warning for both logs"));
+    // Only the messages of the init() threshold are forwarded to the application log:
+    let logged = LOGGED.lock().unwrap().clone();
+    assert!(logged.iter().any(|message| message == "Warning for both logs"));
+    assert!(!logged.iter().any(|message| message == "Message for the debug log"));
+
+    // Without the log file all messages of the raised thresholds are forwarded:
+    gst::debug!(category, "Message without the debug log");
+    assert!(
+        LOGGED
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|message| message == "Message without the debug log")
+    );
+
+    gst_helpers::reset_gst_debug();
+    assert_eq!(gst_helpers::gst_debug(), None);
+    assert_eq!(category.threshold(), gst::DebugLevel::Warning);
+
+    // The thresholds of the environment are restored:
+    let env_category = gst::DebugCategory::new("mxlenvtest", gst::DebugColorFlags::empty(), Some("Test category"));
+    unsafe { std::env::set_var("GST_DEBUG", "mxlenvtest:6") };
+    gst_helpers::set_gst_debug("mxlenvtest:2").unwrap();
+    assert_eq!(env_category.threshold(), gst::DebugLevel::Warning);
+    gst_helpers::reset_gst_debug();
+    unsafe { std::env::remove_var("GST_DEBUG") };
+    assert_eq!(env_category.threshold(), gst::DebugLevel::Log);
+    assert_eq!(category.threshold(), gst::DebugLevel::Warning);
+}
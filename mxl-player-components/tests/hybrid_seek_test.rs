@@ -0,0 +1,135 @@
+use anyhow::{Context, Result};
+use log::*;
+use mxl_player_components::ui::player::messages::SeekMode;
+use mxl_relm4_components::relm4::{gtk::gio, prelude::*};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+mod player;
+
+use player::{
+    ControllerFeedback,
+    about::APP_ID,
+    app::{App, AppInit, AppMsg},
+    init::init,
+};
+
+// A hybrid seek first seeks to the keyframe before the target and then accurately to the target:
+const SEEK_TARGET: f64 = 7.0;
+
+#[test]
+fn hybrid_seek() -> Result<()> {
+    init()?;
+
+    let mut uris = vec![];
+
+    if let Some(parent) = PathBuf::from(file!()).parent() {
+        if let Some(name) = parent.file_name() {
+            let data_path = PathBuf::from(name).join("data");
+            for file in data_path
+                .read_dir()
+                .with_context(|| format!("Cannot read {data_path:?} dir"))?
+            {
+                match file {
+                    Ok(file) => uris.push(file.path()),
+                    Err(error) => error!("Cannot list file - {error:?}"),
+                }
+            }
+        }
+    }
+
+    let error_channel = Arc::new(Mutex::new(None));
+
+    let adw_app = adw::Application::new(Some(APP_ID), gio::ApplicationFlags::default());
+    let app = RelmApp::from_app(adw_app);
+    app.with_args(vec![]).run::<App>(AppInit {
+        uris,
+        seek_mode: SeekMode::Hybrid,
+        error_channel: Arc::clone(&error_channel),
+        test_controller: |recv, sender| {
+            debug!("Test controller launched...");
+            let mut seek_requested = false;
+            let mut seek_done = false;
+            loop {
+                match recv.recv_timeout(Duration::from_secs(10)) {
+                    Err(error) => {
+                        sender.input(AppMsg::TestError(anyhow::anyhow!(
+                            "Failed to receive from receiver channel: {:?}",
+                            error
+                        )));
+                        break;
+                    }
+                    Ok(feedback) => match feedback {
+                        ControllerFeedback::AppStateChanged(state) => {
+                            debug!("App state changed: {state:?}");
+                        }
+                        ControllerFeedback::PlayerMediaInfoUpdated(info) => {
+                            debug!("Media info updated: {info:?}");
+                        }
+                        ControllerFeedback::PlayerInitialized => {
+                            debug!("Player initialized");
+                            sender.input(AppMsg::TogglePlayPause);
+                        }
+                        ControllerFeedback::PlayerDurationChanged(duration) => {
+                            debug!("Duration changed: {duration:?}");
+                        }
+                        ControllerFeedback::PlayerPositionUpdated(pos) => {
+                            debug!("Position changed: {pos:?}");
+                            // Seek once, the keyframe seek of the first step must not end the seek:
+                            if !seek_requested && (1.0..2.0).contains(&pos) {
+                                seek_requested = true;
+                                sender.input(AppMsg::Seek(SEEK_TARGET));
+                            } else if seek_done && pos < SEEK_TARGET - 0.1 {
+                                sender.input(AppMsg::TestError(anyhow::anyhow!(
+                                    "Not expecting playback at {pos} after the hybrid seek to {SEEK_TARGET}"
+                                )));
+                            }
+                        }
+                        ControllerFeedback::PlayerSeekDone => {
+                            debug!("Seek done");
+                            if seek_done {
+                                sender.input(AppMsg::TestError(anyhow::anyhow!(
+                                    "Hybrid seek reported more than one seek done"
+                                )));
+                            }
+                            seek_done = true;
+                        }
+                        ControllerFeedback::PlayerEndOfStream(uri) => {
+                            debug!("End of stream of uri: {uri:?}");
+                        }
+                        ControllerFeedback::PlaylistChanged(change) => {
+                            debug!("Playlist changed: {change:?}");
+                        }
+                        ControllerFeedback::PlaylistSwitchUri(uri) => {
+                            debug!("Playlist switched to uri: {uri:?}");
+                        }
+                        ControllerFeedback::PlaylistEndOfPlaylist => {
+                            debug!("End of playlist - quit app");
+                            if !seek_done {
+                                sender.input(AppMsg::TestError(anyhow::anyhow!("Hybrid seek was not done")));
+                            }
+                            sender.input(AppMsg::Quit);
+                            break;
+                        }
+                        msg => {
+                            sender.input(AppMsg::TestError(anyhow::anyhow!(
+                                "Unexpected controller feedback from App: {:?}",
+                                msg
+                            )));
+                        }
+                    },
+                }
+            }
+        },
+    });
+
+    let mut error = error_channel.lock().unwrap();
+    if let Some(error) = error.take() {
+        return Err(error);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use mxl_player_components::{
+    gst,
+    keyframe_index::{KeyframeIndex, SeekDirection},
+    uri_helpers,
+};
+use std::path::Path;
+
+fn index() -> KeyframeIndex {
+    KeyframeIndex::new(vec![4.0, 0.0, 2.0, 6.0, 2.0])
+}
+
+#[test]
+fn sorted_and_deduplicated() {
+    assert_eq!(index().keyframes(), &[0.0, 2.0, 4.0, 6.0]);
+    assert!(KeyframeIndex::default().is_empty());
+}
+
+#[test]
+fn at_or_before_and_nearest() {
+    let index = index();
+    assert_eq!(index.at_or_before(-1.0), None);
+    assert_eq!(index.at_or_before(2.0), Some(2.0));
+    assert_eq!(index.at_or_before(3.9), Some(2.0));
+    assert_eq!(index.at_or_before(100.0), Some(6.0));
+
+    assert_eq!(index.nearest(-1.0), Some(0.0));
+    assert_eq!(index.nearest(2.9), Some(2.0));
+    assert_eq!(index.nearest(3.1), Some(4.0));
+    assert_eq!(index.nearest(100.0), Some(6.0));
+    assert_eq!(KeyframeIndex::default().nearest(1.0), None);
+}
+
+#[test]
+fn step() {
+    let index = index();
+    assert_eq!(index.step(2.0, SeekDirection::Forward), Some(4.0));
+    assert_eq!(index.step(2.0, SeekDirection::Backward), Some(0.0));
+    assert_eq!(index.step(3.0, SeekDirection::Backward), Some(2.0));
+    assert_eq!(index.step(6.0, SeekDirection::Forward), None);
+    assert_eq!(index.step(0.0, SeekDirection::Backward), None);
+}
+
+#[test]
+fn build_from_file() {
+    gst::init().unwrap();
+    let uri = uri_helpers::uri_from_pathbuf(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/Big_Buck_Bunny_720_10s_2MB.mp4"),
+    )
+    .unwrap();
+    let index = KeyframeIndex::build(&uri).unwrap();
+    assert!(!index.is_empty());
+    assert_eq!(index.keyframes()[0], 0.0);
+    assert!(index.keyframes().iter().all(|keyframe| *keyframe < 10.5));
+}
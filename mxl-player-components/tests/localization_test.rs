@@ -0,0 +1,6 @@
+use std::path::Path;
+
+#[test]
+fn translations_complete() {
+    mxl_base::translations::check_translations(Path::new(env!("CARGO_MANIFEST_DIR"))).unwrap();
+}
@@ -0,0 +1,170 @@
+use mxl_player_components::markers::{MarkerExportFormat, MarkerList, MarkerMediaInfo, csv_escape};
+use std::{collections::HashSet, path::Path};
+
+fn marker_list() -> MarkerList {
+    let mut markers = MarkerList::new();
+    markers.add("Second", 20.0);
+    markers.add("First", 10.0);
+    markers.add("Third, with \"quotes\"", 30.5);
+    markers
+}
+
+fn media_info() -> MarkerMediaInfo {
+    MarkerMediaInfo {
+        uri: "file:///recordings/video.mp4".to_string(),
+        recorder_machine_id: Some("machine-1".to_string()),
+        current_uuid: None,
+    }
+}
+
+#[test]
+fn add_keeps_order() {
+    let mut markers = marker_list();
+    let names: Vec<_> = markers.markers().iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, ["First", "Second", "Third, with \"quotes\""]);
+
+    assert_eq!(markers.add("Between", 15.0), 1);
+    assert_eq!(markers.add("Last", 100.0), 4);
+    assert_eq!(markers.len(), 5);
+}
+
+#[test]
+fn remove_and_rename_by_id() {
+    let mut markers = marker_list();
+    let first = markers.markers()[0].id;
+    let second = markers.markers()[1].id;
+    assert_ne!(first, second);
+
+    // The ids stay valid when the indices change:
+    markers.add("Start", 0.0);
+    assert_eq!(markers.index_of(first), Some(1));
+    markers.rename(second, "Renamed");
+    assert_eq!(markers.remove(first).map(|m| m.name), Some("First".to_string()));
+    assert_eq!(markers.remove(first), None);
+    assert_eq!(markers.index_of(second), Some(1));
+    assert_eq!(markers.markers()[1].name, "Renamed");
+}
+
+#[test]
+fn next_and_previous_with_tolerance() {
+    let markers = marker_list();
+    assert_eq!(markers.next(0.0).map(|m| m.position), Some(10.0));
+    // A marker at the current position is skipped:
+    assert_eq!(markers.next(9.95).map(|m| m.position), Some(20.0));
+    assert_eq!(markers.next(9.85).map(|m| m.position), Some(10.0));
+    assert_eq!(markers.next(30.5), None);
+
+    assert_eq!(markers.previous(40.0).map(|m| m.position), Some(30.5));
+    assert_eq!(markers.previous(20.05).map(|m| m.position), Some(10.0));
+    assert_eq!(markers.previous(20.15).map(|m| m.position), Some(20.0));
+    assert_eq!(markers.previous(10.0), None);
+
+    assert_eq!(MarkerList::new().next(0.0), None);
+    assert_eq!(MarkerList::new().previous(0.0), None);
+}
+
+#[test]
+fn escape_csv_fields() {
+    assert_eq!(csv_escape("plain"), "plain");
+    assert_eq!(csv_escape(""), "");
+    assert_eq!(csv_escape("a,b"), "\"a,b\"");
+    assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(csv_escape("two\nlines"), "\"two\nlines\"");
+    assert_eq!(csv_escape("cr\r"), "\"cr\r\"");
+}
+
+#[test]
+fn export_csv() {
+    let markers = marker_list();
+    let mut output = Vec::new();
+    markers.write_csv(&mut output, None).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "name,position,time\n\
+         First,10.000,0:00:10.000\n\
+         Second,20.000,0:00:20.000\n\
+         \"Third, with \"\"quotes\"\"\",30.500,0:00:30.500\n"
+    );
+
+    let mut output = Vec::new();
+    markers.write_csv(&mut output, Some(&media_info())).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let mut lines = output.lines();
+    assert_eq!(
+        lines.next(),
+        Some("name,position,time,uri,recorder_machine_id,current_uuid")
+    );
+    assert_eq!(
+        lines.next(),
+        Some("First,10.000,0:00:10.000,file:///recordings/video.mp4,machine-1,")
+    );
+}
+
+#[test]
+fn export_json() {
+    let markers = marker_list();
+    let mut output = Vec::new();
+    markers.write_json(&mut output, Some(&media_info())).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["uri"], "file:///recordings/video.mp4");
+    assert_eq!(json["recorder_machine_id"], "machine-1");
+    // Missing media information is omitted:
+    assert!(json.get("current_uuid").is_none());
+    assert_eq!(json["markers"].as_array().unwrap().len(), 3);
+    assert_eq!(json["markers"][2]["name"], "Third, with \"quotes\"");
+    assert_eq!(json["markers"][2]["position"], 30.5);
+    assert_eq!(json["markers"][2]["time"], "0:00:30.500");
+
+    let mut output = Vec::new();
+    markers.write_json(&mut output, None).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert!(json.get("uri").is_none());
+    assert_eq!(json["markers"][0]["name"], "First");
+}
+
+#[test]
+fn export_format_from_path() {
+    assert_eq!(
+        MarkerExportFormat::from_path(Path::new("markers.CSV")),
+        Some(MarkerExportFormat::Csv)
+    );
+    assert_eq!(
+        MarkerExportFormat::from_path(Path::new("/tmp/markers.json")),
+        Some(MarkerExportFormat::Json)
+    );
+    assert_eq!(MarkerExportFormat::from_path(Path::new("markers.txt")), None);
+    assert_eq!(MarkerExportFormat::from_path(Path::new("markers")), None);
+}
+
+#[test]
+fn save_and_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("video.markers.json");
+    let markers = marker_list();
+    markers.save(&path).unwrap();
+
+    let loaded = MarkerList::load(&path).unwrap();
+    let names: Vec<_> = loaded.markers().iter().map(|m| (m.name.as_str(), m.position)).collect();
+    assert_eq!(
+        names,
+        [("First", 10.0), ("Second", 20.0), ("Third, with \"quotes\"", 30.5)]
+    );
+
+    // Markers added after loading get new ids:
+    let mut loaded = loaded;
+    loaded.add("New", 5.0);
+    let ids: HashSet<_> = loaded.markers().iter().map(|m| m.id).collect();
+    assert_eq!(ids.len(), 4);
+}
+
+#[test]
+fn load_unknown_version() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("video.markers.json");
+    let content = r#"{"version": 2, "markers": [{"name": "First", "position": 1.0, "color": "red"}]}"#;
+    std::fs::write(&path, content).unwrap();
+
+    assert!(MarkerList::load(&path).is_err());
+    // The file of the newer version is not touched:
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+}
@@ -0,0 +1,158 @@
+use anyhow::{Result, anyhow};
+use log::*;
+use mxl_player_components::{
+    ui::{
+        multi_player::{
+            messages::{MultiPlayerComponentInput, MultiPlayerComponentOutput},
+            model::{MultiPlayerComponentInit, MultiPlayerComponentModel},
+        },
+        player::messages::PlaybackState,
+    },
+    uri_helpers,
+};
+use mxl_relm4_components::relm4::{
+    gtk::{gio, glib, prelude::*},
+    prelude::*,
+};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+mod player {
+    pub mod about;
+    pub mod init;
+}
+
+use player::{about::APP_ID, init::init};
+
+// About one frame at 25 fps:
+const TOLERANCE: f64 = 0.04;
+const CHECK_FROM: f64 = 1.0;
+const CHECK_UNTIL: f64 = 5.0;
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+type ResultChannel = Arc<Mutex<Option<Result<()>>>>;
+
+struct SyncTest {
+    multi_player: Controller<MultiPlayerComponentModel>,
+    result: ResultChannel,
+    samples: usize,
+}
+
+#[derive(Debug)]
+enum SyncTestMsg {
+    Player(MultiPlayerComponentOutput),
+    Timeout,
+}
+
+impl Component for SyncTest {
+    type Init = ResultChannel;
+    type Input = SyncTestMsg;
+    type Output = ();
+    type CommandOutput = ();
+    type Root = gtk::Window;
+    type Widgets = ();
+
+    fn init_root() -> Self::Root {
+        gtk::Window::builder().default_width(640).default_height(240).build()
+    }
+
+    fn init(result: Self::Init, root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        // The drift correction is disabled, so that the test checks the synchronized start of the players:
+        let multi_player = MultiPlayerComponentModel::builder()
+            .launch(MultiPlayerComponentInit {
+                players: 2,
+                max_drift: f64::MAX,
+                ..Default::default()
+            })
+            .forward(sender.input_sender(), SyncTestMsg::Player);
+        root.set_child(Some(multi_player.widget()));
+        root.present();
+
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/Big_Buck_Bunny_720_10s_2MB.mp4");
+        let uri = uri_helpers::uri_from_pathbuf(&path).unwrap();
+        for index in 0..2 {
+            multi_player.emit(MultiPlayerComponentInput::SetUri(index, uri.clone()));
+        }
+        multi_player.emit(MultiPlayerComponentInput::ChangeState(PlaybackState::Playing));
+
+        let timeout_sender = sender.input_sender().clone();
+        glib::timeout_add_local_once(TIMEOUT, move || timeout_sender.emit(SyncTestMsg::Timeout));
+
+        let model = SyncTest {
+            multi_player,
+            result,
+            samples: 0,
+        };
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, msg: Self::Input, _sender: ComponentSender<Self>, root: &Self::Root) {
+        match msg {
+            SyncTestMsg::Player(MultiPlayerComponentOutput::PositionUpdated(position)) => {
+                if position < CHECK_FROM {
+                    return;
+                }
+                let model = self.multi_player.model();
+                if let (Some(first), Some(second)) = (model.player_position(0), model.player_position(1)) {
+                    debug!("Positions of the players: {first} and {second}");
+                    self.samples += 1;
+                    if (first - second).abs() > TOLERANCE {
+                        drop(model);
+                        self.finish(Err(anyhow!("Players are not in sync: {first} and {second}")), root);
+                        return;
+                    }
+                }
+                drop(model);
+                if position >= CHECK_UNTIL {
+                    let result = if self.samples > 0 {
+                        Ok(())
+                    } else {
+                        Err(anyhow!("No positions of the players were compared"))
+                    };
+                    self.finish(result, root);
+                }
+            }
+            SyncTestMsg::Player(MultiPlayerComponentOutput::PlayerInitialized(index, Some(error))) => {
+                self.finish(Err(error.context(format!("Cannot initialize player {index}"))), root);
+            }
+            SyncTestMsg::Player(MultiPlayerComponentOutput::Error(index, error)) => {
+                self.finish(Err(error.context(format!("Error of player {index}"))), root);
+            }
+            SyncTestMsg::Player(MultiPlayerComponentOutput::EndOfStream) => {
+                self.finish(Err(anyhow!("Unexpected end of stream")), root);
+            }
+            SyncTestMsg::Player(output) => debug!("Multi player output: {output:?}"),
+            SyncTestMsg::Timeout => self.finish(Err(anyhow!("Timeout after {TIMEOUT:?}")), root),
+        }
+    }
+}
+
+impl SyncTest {
+    fn finish(&mut self, result: Result<()>, root: &gtk::Window) {
+        let mut channel = self.result.lock().unwrap();
+        if channel.is_none() {
+            channel.replace(result);
+            self.multi_player
+                .emit(MultiPlayerComponentInput::ChangeState(PlaybackState::Stopped));
+            root.close();
+        }
+    }
+}
+
+#[test]
+fn players_stay_in_sync() -> Result<()> {
+    init()?;
+
+    let result = Arc::new(Mutex::new(None));
+    let adw_app = adw::Application::new(Some(APP_ID), gio::ApplicationFlags::default());
+    let app = RelmApp::from_app(adw_app);
+    app.with_args(vec![]).run::<SyncTest>(Arc::clone(&result));
+
+    let mut result = result.lock().unwrap();
+    result
+        .take()
+        .unwrap_or_else(|| Err(anyhow!("The test ended without a result")))
+}
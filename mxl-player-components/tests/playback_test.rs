@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use log::*;
+use mxl_player_components::ui::player::messages::SeekMode;
 use mxl_relm4_components::relm4::{gtk::gio, prelude::*};
 use std::{
     path::PathBuf,
@@ -43,6 +44,7 @@ fn playback() -> Result<()> {
     let app = RelmApp::from_app(adw_app);
     app.with_args(vec![]).run::<App>(AppInit {
         uris,
+        seek_mode: SeekMode::Default,
         error_channel: Arc::clone(&error_channel),
         test_controller: |recv, sender| {
             debug!("Test controller launched...");
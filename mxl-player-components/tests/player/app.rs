@@ -3,9 +3,10 @@ use log::*;
 use mxl_player_components::{
     actions::{self, Accelerators},
     gst_play::PlayMediaInfo,
+    gst_video::VideoRectangle,
     ui::{
         player::{
-            messages::{PlaybackState, PlayerComponentInput, PlayerComponentOutput},
+            messages::{PlaybackState, PlayerComponentInput, PlayerComponentOutput, SeekMode},
             model::{PlayerComponentInit, PlayerComponentModel},
         },
         playlist::{
@@ -33,6 +34,8 @@ type TestController = fn(Receiver, ActionSender);
 
 pub struct AppInit {
     pub uris: Vec<PathBuf>,
+    // The keyframe index is built for all seek modes except the default one:
+    pub seek_mode: SeekMode,
     pub error_channel: ErrorChannel,
     pub test_controller: TestController,
 }
@@ -100,11 +103,13 @@ pub enum AppCmd {
     PlayerPositionUpdated(f64),
     PlayerSeekDone,
     PlayerEndOfStream(String),
+    PlayerStartOfStream(String),
     PlayerStateChanged(Option<PlaybackState>, PlaybackState),
     PlayerVolumeChanged(f64),
     PlayerSpeedChanged(f64),
     PlayerAudioVideoOffsetChanged(i64),
     PlayerSubtitleVideoOffsetChanged(i64),
+    PlayerViewChanged(VideoRectangle),
     PlayerWarning(anyhow::Error),
     PlayerError(anyhow::Error),
     PlaylistChanged(PlaylistChange),
@@ -125,6 +130,7 @@ pub enum ControllerFeedback {
     PlayerPositionUpdated(f64),
     PlayerSeekDone,
     PlayerEndOfStream(String),
+    PlayerStartOfStream(String),
     PlayerVolumeChanged(f64),
     PlayerSpeedChanged(f64),
     PlaylistChanged(PlaylistChange),
@@ -217,7 +223,11 @@ impl Component for App {
 
         let player_component = {
             PlayerComponentModel::builder()
-                .launch(PlayerComponentInit::default())
+                .launch(PlayerComponentInit {
+                    seek_mode: app_init.seek_mode,
+                    keyframe_index: app_init.seek_mode != SeekMode::Default,
+                    ..Default::default()
+                })
                 .forward(sender.command_sender(), |msg| match msg {
                     PlayerComponentOutput::PlayerInitialized(x) => AppCmd::PlayerInitialized(x),
                     PlayerComponentOutput::MediaInfoUpdated(x) => AppCmd::PlayerMediaInfoUpdated(x),
@@ -225,11 +235,13 @@ impl Component for App {
                     PlayerComponentOutput::PositionUpdated(x) => AppCmd::PlayerPositionUpdated(x),
                     PlayerComponentOutput::SeekDone => AppCmd::PlayerSeekDone,
                     PlayerComponentOutput::EndOfStream(x) => AppCmd::PlayerEndOfStream(x),
+                    PlayerComponentOutput::StartOfStream(x) => AppCmd::PlayerStartOfStream(x),
                     PlayerComponentOutput::StateChanged(x, y) => AppCmd::PlayerStateChanged(x, y),
                     PlayerComponentOutput::VolumeChanged(x) => AppCmd::PlayerVolumeChanged(x),
                     PlayerComponentOutput::SpeedChanged(x) => AppCmd::PlayerSpeedChanged(x),
                     PlayerComponentOutput::AudioVideoOffsetChanged(x) => AppCmd::PlayerAudioVideoOffsetChanged(x),
                     PlayerComponentOutput::SubtitleVideoOffsetChanged(x) => AppCmd::PlayerSubtitleVideoOffsetChanged(x),
+                    PlayerComponentOutput::ViewChanged(x) => AppCmd::PlayerViewChanged(x),
                     PlayerComponentOutput::Warning(x) => AppCmd::PlayerWarning(x),
                     PlayerComponentOutput::Error(x) => AppCmd::PlayerError(x),
                 })
@@ -665,6 +677,11 @@ impl Component for App {
                     .unwrap_or_default();
                 sender.input(AppMsg::Next)
             }
+            AppCmd::PlayerStartOfStream(a) => {
+                self.controller_feedback
+                    .send(ControllerFeedback::PlayerStartOfStream(a))
+                    .unwrap_or_default();
+            }
             AppCmd::PlayerDurationChanged(duration) => {
                 self.controller_feedback
                     .send(ControllerFeedback::PlayerDurationChanged(duration))
@@ -723,6 +740,7 @@ impl Component for App {
             }
             AppCmd::PlayerAudioVideoOffsetChanged(_offset) => (),
             AppCmd::PlayerSubtitleVideoOffsetChanged(_offset) => (),
+            AppCmd::PlayerViewChanged(_view) => (),
             AppCmd::PlayerWarning(error) => {
                 warn!("Internal player warning: {error:?}");
             }
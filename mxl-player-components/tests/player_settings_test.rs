@@ -0,0 +1,33 @@
+use mxl_base::settings::SettingsStore;
+use mxl_player_components::player::{MaxLateness, PlayerBuilder, PlayerSettings};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn player_settings_round_trip() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let path = tmp_dir.path().join("config").join("player.toml");
+
+    let store = SettingsStore::<PlayerSettings>::open(path.clone()).unwrap();
+    assert_eq!(store.get(), PlayerBuilder::new().to_settings());
+
+    let changed = Arc::new(Mutex::new(None));
+    store.subscribe({
+        let changed = changed.clone();
+        move |settings: &PlayerSettings| *changed.lock().unwrap() = Some(settings.clone())
+    });
+
+    let mut builder = PlayerBuilder::new();
+    builder
+        .seek_accurate(true)
+        .qos(false)
+        .max_lateness(MaxLateness::Custom(40_000_000))
+        .audio_offset(-20_000_000)
+        .subtitle_offset(500_000_000);
+    store.set(builder.to_settings()).unwrap();
+    assert_eq!(changed.lock().unwrap().as_ref(), Some(&builder.to_settings()));
+
+    let reopened = SettingsStore::<PlayerSettings>::open(path).unwrap();
+    let mut restored = PlayerBuilder::new();
+    restored.settings(&reopened.get());
+    assert_eq!(restored.to_settings(), builder.to_settings());
+}
@@ -0,0 +1,126 @@
+use anyhow::{Context, Result};
+use log::*;
+use mxl_player_components::ui::player::messages::SeekMode;
+use mxl_relm4_components::relm4::{gtk::gio, prelude::*};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+mod player;
+
+use player::{
+    ControllerFeedback,
+    about::APP_ID,
+    app::{App, AppInit, AppMsg},
+    init::init,
+};
+
+const REVERSE_SPEED: f64 = 2.0;
+
+#[test]
+fn reverse_playback() -> Result<()> {
+    init()?;
+
+    let mut uris = vec![];
+
+    if let Some(parent) = PathBuf::from(file!()).parent() {
+        if let Some(name) = parent.file_name() {
+            let data_path = PathBuf::from(name).join("data");
+            for file in data_path
+                .read_dir()
+                .with_context(|| format!("Cannot read {data_path:?} dir"))?
+            {
+                match file {
+                    Ok(file) => uris.push(file.path()),
+                    Err(error) => error!("Cannot list file - {error:?}"),
+                }
+            }
+        }
+    }
+
+    let error_channel = Arc::new(Mutex::new(None));
+
+    let adw_app = adw::Application::new(Some(APP_ID), gio::ApplicationFlags::default());
+    let app = RelmApp::from_app(adw_app);
+    app.with_args(vec![]).run::<App>(AppInit {
+        uris,
+        seek_mode: SeekMode::Default,
+        error_channel: Arc::clone(&error_channel),
+        test_controller: |recv, sender| {
+            debug!("Test controller launched...");
+            let mut reverse_requested = false;
+            let mut reverse_applied = false;
+            let mut start_reached = false;
+            loop {
+                match recv.recv_timeout(Duration::from_secs(10)) {
+                    Err(error) => {
+                        sender.input(AppMsg::TestError(anyhow::anyhow!(
+                            "Failed to receive from receiver channel: {:?}",
+                            error
+                        )));
+                        break;
+                    }
+                    Ok(feedback) => match feedback {
+                        ControllerFeedback::AppStateChanged(state) => {
+                            debug!("App state changed: {state:?}");
+                        }
+                        ControllerFeedback::PlayerInitialized => {
+                            debug!("Player initialized");
+                            sender.input(AppMsg::TogglePlayPause);
+                        }
+                        ControllerFeedback::PlayerPositionUpdated(pos) => {
+                            debug!("Position changed: {pos:?}");
+                            if !reverse_requested && pos >= 4.0 {
+                                reverse_requested = true;
+                                sender.input(AppMsg::ChangeSpeed(-REVERSE_SPEED));
+                            }
+                        }
+                        ControllerFeedback::PlayerSpeedChanged(speed) => {
+                            debug!("Speed changed: {speed:?}");
+                            if speed < 0.0 {
+                                reverse_applied = true;
+                            } else if start_reached {
+                                // The speed is kept for the forward playback after reaching the start:
+                                if speed != REVERSE_SPEED {
+                                    sender.input(AppMsg::TestError(anyhow::anyhow!(
+                                        "Expected the speed {REVERSE_SPEED} after reaching the start, got {speed}"
+                                    )));
+                                }
+                                sender.input(AppMsg::Quit);
+                                break;
+                            }
+                        }
+                        ControllerFeedback::PlayerStartOfStream(uri) => {
+                            debug!("Start of stream of uri: {uri:?}");
+                            if !reverse_applied {
+                                sender.input(AppMsg::TestError(anyhow::anyhow!(
+                                    "Reached the start of the stream without an effective negative rate"
+                                )));
+                                sender.input(AppMsg::Quit);
+                                break;
+                            }
+                            start_reached = true;
+                        }
+                        ControllerFeedback::PlayerEndOfStream(uri) => {
+                            sender.input(AppMsg::TestError(anyhow::anyhow!(
+                                "Not expecting the end of stream of {uri:?} during reverse playback"
+                            )));
+                            sender.input(AppMsg::Quit);
+                            break;
+                        }
+                        msg => debug!("Controller feedback: {msg:?}"),
+                    },
+                }
+            }
+        },
+    });
+
+    let mut error = error_channel.lock().unwrap();
+    if let Some(error) = error.take() {
+        return Err(error);
+    }
+
+    Ok(())
+}
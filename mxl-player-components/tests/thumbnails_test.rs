@@ -0,0 +1,83 @@
+use mxl_player_components::{gst, misc, thumbnails::ThumbnailGenerator, uri_helpers};
+use std::{
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+#[test]
+fn slot_and_strip_positions() {
+    let mut generator = ThumbnailGenerator::new("/tmp/thumbnails");
+    generator.set_interval(5.0);
+
+    assert_eq!(generator.slot_position(-1.0), 0.0);
+    assert_eq!(generator.slot_position(4.9), 0.0);
+    assert_eq!(generator.slot_position(12.3), 10.0);
+
+    assert_eq!(generator.strip_positions(0.0), vec![0.0]);
+    assert_eq!(generator.strip_positions(12.0), vec![0.0, 5.0, 10.0]);
+}
+
+#[test]
+fn cache_path() {
+    let generator = ThumbnailGenerator::new("/tmp/thumbnails");
+    let path = generator.cache_path("file:///not/existing/video.mp4", 10.0);
+    assert!(path.starts_with("/tmp/thumbnails"));
+    assert!(path.ends_with(format!("{}-10000.png", generator.width())));
+    assert_ne!(
+        path.parent(),
+        generator.cache_path("file:///not/existing/other.mp4", 10.0).parent()
+    );
+}
+
+#[test]
+fn thumbnail_is_cached() {
+    gst::init().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let generator = ThumbnailGenerator::new(dir.path());
+    let uri = uri_helpers::uri_from_pathbuf(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/Big_Buck_Bunny_720_10s_2MB.mp4"),
+    )
+    .unwrap();
+
+    let path = generator.thumbnail(&uri, 2.0).unwrap();
+    assert!(path.starts_with(dir.path()));
+    assert!(std::fs::read(&path).unwrap().starts_with(b"\x89PNG"));
+
+    // A second call within the same slot returns the cached file without decoding again:
+    std::fs::write(&path, "cached").unwrap();
+    assert_eq!(generator.thumbnail(&uri, 2.5).unwrap(), path);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "cached");
+}
+
+#[test]
+fn remove_unused_cache_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let old_dir = dir.path().join("old");
+    let used_dir = dir.path().join("used");
+    std::fs::create_dir_all(&old_dir).unwrap();
+    std::fs::create_dir_all(&used_dir).unwrap();
+    let old_files = [old_dir.join("0.png"), used_dir.join("0.png")];
+    let used_file = used_dir.join("5000.png");
+    for path in old_files.iter().chain([&used_file]) {
+        std::fs::write(path, "png").unwrap();
+    }
+    for path in &old_files {
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(60 * 60))
+            .unwrap();
+    }
+
+    assert_eq!(
+        misc::remove_unused_cache_files(dir.path(), Duration::from_secs(60)).unwrap(),
+        2
+    );
+    assert!(!old_dir.exists());
+    assert!(used_file.exists() && !old_files[1].exists());
+    assert_eq!(
+        misc::remove_unused_cache_files(&dir.path().join("not_existing"), Duration::ZERO).unwrap(),
+        0
+    );
+}
@@ -0,0 +1,131 @@
+use mxl_player_components::{
+    gst_video::VideoRectangle,
+    ui::player::model::VideoViewData,
+    video_filter::{VideoCrop, VideoOrientation},
+};
+
+const EPSILON: f64 = 1e-6;
+
+const ORIENTATIONS: [VideoOrientation; 8] = [
+    VideoOrientation::Identity,
+    VideoOrientation::Rotate90,
+    VideoOrientation::Rotate180,
+    VideoOrientation::Rotate270,
+    VideoOrientation::FlipHorizontal,
+    VideoOrientation::FlipVertical,
+    VideoOrientation::Transpose,
+    VideoOrientation::AntiTranspose,
+];
+
+fn assert_point_eq(actual: Option<(f64, f64)>, expected: (f64, f64)) {
+    let (x, y) = actual.unwrap_or_else(|| panic!("Expected point {expected:?}, got None"));
+    assert!(
+        (x - expected.0).abs() < EPSILON && (y - expected.1).abs() < EPSILON,
+        "Expected point {expected:?}, got {:?}",
+        (x, y)
+    );
+}
+
+// A 1920x1080 video in a 1280x900 view, which is letterboxed at the top and bottom:
+fn full_hd_view(zoom_factor: f64) -> VideoViewData {
+    let mut view = VideoViewData::default();
+    view.video_dimensions = Some(VideoRectangle::new(0, 0, 1920, 1080));
+    view.view_rect = Some(VideoRectangle::new(0, 0, 1280, 900));
+    view.zoom_factor = zoom_factor;
+    view.fitted_paintable_rect = Some(if zoom_factor == 1.0 {
+        VideoRectangle::new(0, 90, 1280, 720)
+    } else {
+        VideoRectangle::new(0, 0, (1280.0 * zoom_factor) as i32, (720.0 * zoom_factor) as i32)
+    });
+    view
+}
+
+#[test]
+fn without_dimensions() {
+    let view = VideoViewData::default();
+    assert_eq!(view.widget_to_video(10.0, 10.0), None);
+    assert_eq!(view.video_to_widget(10.0, 10.0), None);
+    assert_eq!(view.view_to_video(10.0, 10.0), None);
+    assert_eq!(view.video_to_view(10.0, 10.0), None);
+}
+
+#[test]
+fn letterboxed() {
+    let view = full_hd_view(1.0);
+    assert_point_eq(view.widget_to_video(0.0, 90.0), (0.0, 0.0));
+    assert_point_eq(view.widget_to_video(640.0, 450.0), (960.0, 540.0));
+    assert_point_eq(view.widget_to_video(1280.0, 810.0), (1920.0, 1080.0));
+    assert_point_eq(view.video_to_widget(960.0, 540.0), (640.0, 450.0));
+
+    // Points on the black bars are outside of the video:
+    assert_eq!(view.widget_to_video(640.0, 50.0), None);
+    assert_eq!(view.widget_to_video(640.0, 850.0), None);
+    assert_eq!(view.video_to_widget(-1.0, 0.0), None);
+    assert_eq!(view.video_to_widget(1920.0, 1081.0), None);
+}
+
+#[test]
+fn zoomed_and_scrolled() {
+    let mut view = full_hd_view(2.0);
+    view.scroll_offset = (640.0, 360.0);
+
+    // The drawing overlay covers the whole zoomed content:
+    assert_point_eq(view.widget_to_video(640.0, 360.0), (480.0, 270.0));
+    assert_point_eq(view.video_to_widget(480.0, 270.0), (640.0, 360.0));
+
+    // The view is shifted by the scroll offset:
+    assert_point_eq(view.view_to_video(0.0, 0.0), (480.0, 270.0));
+    assert_point_eq(view.view_to_video(1280.0, 900.0), (1440.0, 945.0));
+    assert_point_eq(view.video_to_view(960.0, 540.0), (640.0, 360.0));
+}
+
+#[test]
+fn rotated_and_cropped() {
+    // A 2000x1200 video cropped to 1800x1100 and rotated clockwise to 1100x1800:
+    let mut view = VideoViewData::default();
+    view.video_dimensions = Some(VideoRectangle::new(0, 0, 1100, 1800));
+    view.view_rect = Some(VideoRectangle::new(0, 0, 1100, 1800));
+    view.fitted_paintable_rect = Some(VideoRectangle::new(0, 0, 1100, 1800));
+    view.zoom_factor = 1.0;
+    view.video_orientation = VideoOrientation::Rotate90;
+    view.video_crop = VideoCrop {
+        left: 100,
+        right: 100,
+        top: 50,
+        bottom: 50,
+    };
+
+    // The top left corner of the view shows the bottom left corner of the cropped video:
+    assert_point_eq(view.widget_to_video(0.0, 0.0), (100.0, 1150.0));
+    // The top right corner of the view shows the top left corner of the cropped video:
+    assert_point_eq(view.widget_to_video(1100.0, 0.0), (100.0, 50.0));
+    assert_point_eq(view.video_to_widget(1900.0, 50.0), (1100.0, 1800.0));
+    // Cropped areas are not visible:
+    assert_eq!(view.video_to_widget(50.0, 50.0), None);
+}
+
+#[test]
+fn round_trip_for_all_orientations() {
+    for orientation in ORIENTATIONS {
+        let mut view = full_hd_view(1.0);
+        if orientation.swaps_dimensions() {
+            view.video_dimensions = Some(VideoRectangle::new(0, 0, 1080, 1920));
+            view.fitted_paintable_rect = Some(VideoRectangle::new(387, 0, 506, 900));
+        }
+        view.video_orientation = orientation;
+        view.video_crop = VideoCrop {
+            left: 16,
+            right: 0,
+            top: 8,
+            bottom: 0,
+        };
+
+        for (x, y) in [(400.0, 100.0), (500.0, 300.0), (640.0, 450.0), (800.0, 700.0)] {
+            if let Some((video_x, video_y)) = view.widget_to_video(x, y) {
+                assert_point_eq(view.video_to_widget(video_x, video_y), (x, y));
+            } else {
+                panic!("Point {:?} must be inside of the video for {orientation:?}", (x, y));
+            }
+        }
+    }
+}
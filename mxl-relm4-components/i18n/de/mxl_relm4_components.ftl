@@ -0,0 +1,17 @@
+third-party-licenses = Lizenzen von Drittanbietern
+    .not-found = Keine Lizenzen von Drittanbietern gefunden
+    .not-found-desc = Das Verzeichnis mit den Lizenzen von Drittanbietern wurde in der Installation nicht gefunden
+log-viewer = Protokoll
+    .level = Maximale Protokollstufe
+    .target = Ziel-Präfix
+    .search = Suchen
+    .copy = Kopieren
+    .copy-desc = Die ausgewählten oder alle sichtbaren Zeilen kopieren
+about-dialog = Info
+    .third-party-licenses = Lizenzen von Drittanbietern
+    .create-report = Berichtsdatei erstellen
+    .git-commit = Git-Commit
+    .build-date = Erstellungsdatum
+    .rustc-version = Rust-Compiler
+    .gstreamer-version = GStreamer
+    .log-file = Protokolldatei
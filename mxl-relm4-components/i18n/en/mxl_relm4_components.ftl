@@ -1,3 +1,17 @@
 third-party-licenses = Third party licenses
     .not-found = No licenses found from third party vendors
     .not-found-desc = The directory containing the third party licenses cannot be found in the installation
+log-viewer = Log
+    .level = Maximum log level
+    .target = Target prefix
+    .search = Search
+    .copy = Copy
+    .copy-desc = Copy the selected or all visible lines
+about-dialog = About
+    .third-party-licenses = Third party licenses
+    .create-report = Create report file
+    .git-commit = Git commit
+    .build-date = Build date
+    .rustc-version = Rust compiler
+    .gstreamer-version = GStreamer
+    .log-file = Log file
@@ -0,0 +1,17 @@
+third-party-licenses = Licences tierces
+    .not-found = Aucune licence tierce trouvée
+    .not-found-desc = Le répertoire contenant les licences tierces est introuvable dans l'installation
+log-viewer = Journal
+    .level = Niveau de journalisation maximal
+    .target = Préfixe de la cible
+    .search = Rechercher
+    .copy = Copier
+    .copy-desc = Copier les lignes sélectionnées ou toutes les lignes visibles
+about-dialog = À propos
+    .third-party-licenses = Licences tierces
+    .create-report = Créer un fichier de rapport
+    .git-commit = Commit Git
+    .build-date = Date de compilation
+    .rustc-version = Compilateur Rust
+    .gstreamer-version = GStreamer
+    .log-file = Fichier journal
@@ -0,0 +1,22 @@
+use relm4::gtk;
+
+#[derive(Debug)]
+pub enum AboutDialogComponentInput {
+    Present(gtk::Widget),
+    PrivateMessage(internal::PrivateMsg),
+}
+
+#[derive(Debug)]
+pub enum AboutDialogComponentOutput {
+    // Show the report dialog of the investigator, the about dialog is already closed:
+    CreateReport,
+}
+
+pub(super) mod internal {
+    #[derive(Debug)]
+    pub enum PrivateMsg {
+        #[cfg(feature = "third_party_licenses_dialog")]
+        ShowThirdPartyLicenses,
+        CreateReport,
+    }
+}
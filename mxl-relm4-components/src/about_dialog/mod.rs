@@ -0,0 +1,5 @@
+pub mod messages;
+pub mod model;
+mod widget;
+
+pub use model::{AboutBuildInfo, AboutDialogComponentInit};
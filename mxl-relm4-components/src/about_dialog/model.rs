@@ -0,0 +1,45 @@
+use relm4::gtk;
+
+#[cfg(feature = "third_party_licenses_dialog")]
+use crate::third_party_licenses_dialog::model::ThirdPartyLicensesComponentModel;
+
+// Versions shown in the troubleshooting section, the git commit, the build date and the rustc version are
+// taken from the build_info of mxl_base::about():
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AboutBuildInfo {
+    // The version of the GStreamer library at runtime, e.g. gst::version_string():
+    pub gstreamer_version: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct AboutDialogComponentInit {
+    pub application_icon: Option<String>,
+    // Defaults to the organization of mxl_base::about():
+    pub developer_name: Option<String>,
+    pub website: Option<String>,
+    pub issue_url: Option<String>,
+    pub license_type: gtk::License,
+    pub build_info: AboutBuildInfo,
+    // Show a link to create a report file, see AboutDialogComponentOutput::CreateReport:
+    pub create_report: bool,
+}
+
+impl Default for AboutDialogComponentInit {
+    fn default() -> Self {
+        Self {
+            application_icon: None,
+            developer_name: None,
+            website: None,
+            issue_url: None,
+            license_type: gtk::License::Unknown,
+            build_info: AboutBuildInfo::default(),
+            create_report: false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AboutDialogComponentModel {
+    #[cfg(feature = "third_party_licenses_dialog")]
+    pub(super) third_party_licenses: Option<relm4::Controller<ThirdPartyLicensesComponentModel>>,
+}
@@ -0,0 +1,139 @@
+use relm4::{adw::prelude::*, prelude::*};
+
+use super::{
+    messages::{AboutDialogComponentInput, AboutDialogComponentOutput, internal::PrivateMsg},
+    model::{AboutBuildInfo, AboutDialogComponentInit, AboutDialogComponentModel},
+};
+use crate::localization::helper::fl;
+
+// Links with these URIs are handled by the component instead of being opened in the browser:
+#[cfg(feature = "third_party_licenses_dialog")]
+const THIRD_PARTY_LICENSES_URI: &str = "about:third-party-licenses";
+const CREATE_REPORT_URI: &str = "about:create-report";
+
+#[relm4::component(pub)]
+impl Component for AboutDialogComponentModel {
+    type Init = AboutDialogComponentInit;
+    type Input = AboutDialogComponentInput;
+    type Output = AboutDialogComponentOutput;
+    type CommandOutput = ();
+
+    view! {
+        // adw::AboutDialog requires libadwaita 1.5, the gnome_44 feature of relm4 only provides libadwaita 1.3:
+        adw::AboutWindow {
+            set_modal: true,
+            set_hide_on_close: true,
+            set_application_name: about.app_name,
+            set_version: about.version,
+            set_developer_name: init.developer_name.as_deref().unwrap_or(about.organization),
+            set_license_type: init.license_type,
+            set_debug_info: &debug_info(&init.build_info),
+            set_debug_info_filename: &format!("{}-debug-info.txt", about.binary_name),
+
+            connect_activate_link[sender] => move |_, uri| {
+                match uri {
+                    #[cfg(feature = "third_party_licenses_dialog")]
+                    THIRD_PARTY_LICENSES_URI => {
+                        sender.input(AboutDialogComponentInput::PrivateMessage(PrivateMsg::ShowThirdPartyLicenses));
+                        true
+                    }
+                    CREATE_REPORT_URI => {
+                        sender.input(AboutDialogComponentInput::PrivateMessage(PrivateMsg::CreateReport));
+                        true
+                    }
+                    _ => false,
+                }
+            },
+        }
+    }
+
+    fn init(init: Self::Init, root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let about = mxl_base::about();
+
+        let model = AboutDialogComponentModel {
+            #[cfg(feature = "third_party_licenses_dialog")]
+            third_party_licenses: None,
+        };
+
+        let widgets = view_output!();
+
+        if let Some(application_icon) = &init.application_icon {
+            root.set_application_icon(application_icon);
+        }
+        if let Some(website) = &init.website {
+            root.set_website(website);
+        }
+        if let Some(issue_url) = &init.issue_url {
+            root.set_issue_url(issue_url);
+        }
+        #[cfg(feature = "third_party_licenses_dialog")]
+        root.add_link(&fl!("about-dialog", "third-party-licenses"), THIRD_PARTY_LICENSES_URI);
+        if init.create_report {
+            root.add_link(&fl!("about-dialog", "create-report"), CREATE_REPORT_URI);
+        }
+        crate::gtk::do_close_on_escape(&root);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>, root: &Self::Root) {
+        match msg {
+            AboutDialogComponentInput::Present(transient_for) => {
+                root.set_transient_for(transient_for.toplevel_window().as_ref());
+                root.present();
+            }
+            AboutDialogComponentInput::PrivateMessage(msg) => match msg {
+                #[cfg(feature = "third_party_licenses_dialog")]
+                PrivateMsg::ShowThirdPartyLicenses => {
+                    let third_party_licenses = self.third_party_licenses.get_or_insert_with(|| {
+                        crate::third_party_licenses_dialog::model::ThirdPartyLicensesComponentModel::builder()
+                            .launch(())
+                            .detach()
+                    });
+                    third_party_licenses.widget().set_transient_for(Some(root));
+                    third_party_licenses.widget().present();
+                }
+                PrivateMsg::CreateReport => {
+                    root.close();
+                    sender
+                        .output(AboutDialogComponentOutput::CreateReport)
+                        .unwrap_or_default();
+                }
+            },
+        }
+    }
+}
+
+// Text of the troubleshooting section, which can be copied or saved by the user:
+fn debug_info(build_info: &AboutBuildInfo) -> String {
+    let about = mxl_base::about();
+    let mut lines = vec![format!("{} {}", about.app_name, about.version)];
+    let mut add = |label: String, value: Option<&String>| {
+        if let Some(value) = value {
+            lines.push(format!("{label}: {value}"));
+        }
+    };
+    add(
+        fl!("about-dialog", "git-commit"),
+        about.build_info.git_version().as_ref(),
+    );
+    add(
+        fl!("about-dialog", "build-date"),
+        about.build_info.timestamp.map(str::to_string).as_ref(),
+    );
+    add(
+        fl!("about-dialog", "rustc-version"),
+        about.build_info.rustc_version.map(str::to_string).as_ref(),
+    );
+    add(
+        fl!("about-dialog", "gstreamer-version"),
+        build_info.gstreamer_version.as_ref(),
+    );
+    add(
+        fl!("about-dialog", "log-file"),
+        mxl_base::logging::try_current_log_file()
+            .map(|path| path.to_string_lossy().to_string())
+            .as_ref(),
+    );
+    lines.join("\n")
+}
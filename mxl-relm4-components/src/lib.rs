@@ -24,5 +24,11 @@ pub fn init() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "about_dialog")]
+pub mod about_dialog;
+
+#[cfg(feature = "log_viewer")]
+pub mod log_viewer;
+
 #[cfg(feature = "third_party_licenses_dialog")]
 pub mod third_party_licenses_dialog;
@@ -0,0 +1,117 @@
+use std::{
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+    str::FromStr,
+};
+
+// Only the end of large log files is loaded when the viewer is opened:
+const INITIAL_READ_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub level: Option<log::Level>,
+    pub target: String,
+    pub text: String,
+}
+
+impl LogLine {
+    // Lines without a level like continuation lines of multi-line messages belong to the previous record:
+    pub fn parse(text: &str, previous: Option<&LogLine>) -> Self {
+        let (level, target) = if text.starts_with('{') {
+            parse_json_line(text)
+        } else {
+            parse_text_line(text)
+        }
+        .unwrap_or_else(|| {
+            previous
+                .map(|previous| (previous.level, previous.target.clone()))
+                .unwrap_or_default()
+        });
+        Self {
+            level,
+            target,
+            text: text.to_string(),
+        }
+    }
+
+    pub fn matches(&self, level: log::LevelFilter, target_prefix: &str, text: &str) -> bool {
+        self.level.is_none_or(|line_level| line_level <= level)
+            // Targets of forwarded GStreamer and GLib messages are prefixed with their level, e.g. "warning|gst::play":
+            && (target_prefix.is_empty() || self.target.split('|').any(|target| target.starts_with(target_prefix)))
+            && (text.is_empty() || self.text.to_lowercase().contains(&text.to_lowercase()))
+    }
+}
+
+pub fn parse_json_line(text: &str) -> Option<(Option<log::Level>, String)> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    let level = value
+        .get("level")?
+        .as_str()
+        .and_then(|level| log::Level::from_str(level).ok());
+    let target = value
+        .get("target")
+        .and_then(|target| target.as_str())
+        .unwrap_or_default();
+    Some((level, target.to_string()))
+}
+
+// Parse lines like "[<timestamp>] <level> [<thread>] [<target>] ...", the target is the last bracket group:
+pub fn parse_text_line(text: &str) -> Option<(Option<log::Level>, String)> {
+    let mut words = text.splitn(3, ' ');
+    let (level, rest) = match (words.next(), words.next(), words.next()) {
+        (Some(first), _, _) if log::Level::from_str(first).is_ok() => {
+            (log::Level::from_str(first).ok(), text.get(first.len()..)?)
+        }
+        (Some(first), Some(second), Some(_)) if log::Level::from_str(second).is_ok() => (
+            log::Level::from_str(second).ok(),
+            text.get(first.len() + second.len() + 1..)?,
+        ),
+        _ => return None,
+    };
+
+    let mut target = String::new();
+    let mut rest = rest.trim_start();
+    while let Some(group) = rest.strip_prefix('[') {
+        let Some(end) = group.find("] ").or_else(|| group.strip_suffix(']').map(str::len)) else {
+            break;
+        };
+        target = group[..end].to_string();
+        rest = group[end + 1..].trim_start();
+    }
+    Some((level, target))
+}
+
+// Read the lines appended to the log file since the last call, a truncated or rotated file is read from the start:
+pub fn read_new_lines(path: &Path, offset: &mut u64, partial_line: &mut String) -> std::io::Result<Vec<String>> {
+    let mut file = std::fs::File::open(path)?;
+    let length = file.metadata()?.len();
+    if length < *offset {
+        *offset = 0;
+        partial_line.clear();
+    }
+    if length == *offset {
+        return Ok(Vec::new());
+    }
+
+    let mut skip_first_line = false;
+    if *offset == 0 && length > INITIAL_READ_BYTES {
+        *offset = length - INITIAL_READ_BYTES;
+        skip_first_line = true;
+    }
+    file.seek(SeekFrom::Start(*offset))?;
+    let mut content = Vec::new();
+    file.read_to_end(&mut content)?;
+    *offset += content.len() as u64;
+
+    partial_line.push_str(&String::from_utf8_lossy(&content));
+    let Some(last_newline) = partial_line.rfind('\n') else {
+        return Ok(Vec::new());
+    };
+    let remainder = partial_line.split_off(last_newline + 1);
+    let complete = std::mem::replace(partial_line, remainder);
+    Ok(complete
+        .lines()
+        .skip(usize::from(skip_first_line))
+        .map(str::to_string)
+        .collect())
+}
@@ -0,0 +1,20 @@
+use super::model::LogViewerSource;
+
+#[derive(Debug)]
+pub enum LogViewerComponentInput {
+    SetSource(LogViewerSource),
+    Clear,
+    PrivateMessage(internal::PrivateMsg),
+}
+
+pub(super) mod internal {
+    #[derive(Debug)]
+    pub enum PrivateMsg {
+        Poll,
+        LanguageChanged,
+        LevelChanged(log::LevelFilter),
+        TargetChanged(String),
+        TextChanged(String),
+        Copy,
+    }
+}
@@ -0,0 +1,7 @@
+pub mod helper;
+pub mod messages;
+pub mod model;
+pub mod ring_buffer;
+mod widget;
+
+pub use model::{LogViewerComponentInit, LogViewerSource};
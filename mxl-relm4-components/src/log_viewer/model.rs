@@ -0,0 +1,57 @@
+use relm4::gtk;
+use std::{collections::VecDeque, path::PathBuf};
+
+use super::{helper::LogLine, ring_buffer};
+
+pub const DEFAULT_MAX_LINES: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogViewerSource {
+    File(PathBuf),
+    // The lines of ring_buffer::dispatch():
+    RingBuffer,
+}
+
+impl LogViewerSource {
+    // The ring buffer if it was added to the logger, otherwise the current log file:
+    pub fn detect() -> Option<Self> {
+        if ring_buffer::ring_buffer().is_some() {
+            Some(Self::RingBuffer)
+        } else {
+            mxl_base::logging::try_current_log_file().map(|path| Self::File(path.clone()))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LogViewerComponentInit {
+    pub source: Option<LogViewerSource>,
+    pub max_lines: usize,
+    pub level: log::LevelFilter,
+}
+
+impl Default for LogViewerComponentInit {
+    fn default() -> Self {
+        Self {
+            source: LogViewerSource::detect(),
+            max_lines: DEFAULT_MAX_LINES,
+            level: log::LevelFilter::Trace,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LogViewerComponentModel {
+    pub(super) source: Option<LogViewerSource>,
+    pub(super) max_lines: usize,
+    pub(super) lines: VecDeque<LogLine>,
+    // Read position in the log file and the incomplete last line:
+    pub(super) file_offset: u64,
+    pub(super) partial_line: String,
+    pub(super) ring_buffer_position: u64,
+    pub(super) level: log::LevelFilter,
+    pub(super) target_prefix: String,
+    pub(super) text: String,
+    pub(super) buffer: gtk::TextBuffer,
+    pub(super) _language_subscription: mxl_base::language::LanguageSubscription,
+}
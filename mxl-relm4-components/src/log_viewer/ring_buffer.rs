@@ -0,0 +1,85 @@
+use mxl_base::fern;
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    sync::{
+        Mutex, OnceLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+static RING_BUFFER: OnceLock<LogRingBuffer> = OnceLock::new();
+
+// Keeps the last formatted log lines in memory, the lines are numbered to read only new lines:
+#[derive(Debug)]
+pub struct LogRingBuffer {
+    capacity: AtomicUsize,
+    // Number of all lines pushed so far and the last lines:
+    lines: Mutex<(u64, VecDeque<String>)>,
+}
+
+impl LogRingBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: AtomicUsize::new(capacity.max(1)),
+            lines: Mutex::new((0, VecDeque::new())),
+        }
+    }
+
+    fn push(&self, line: String) {
+        let capacity = self.capacity.load(Ordering::Relaxed);
+        let mut lines = self.lines.lock().unwrap();
+        while lines.1.len() >= capacity {
+            lines.1.pop_front();
+        }
+        lines.1.push_back(line);
+        lines.0 += 1;
+    }
+
+    // The oldest lines are dropped, when the capacity is reduced:
+    fn set_capacity(&self, capacity: usize) {
+        let capacity = capacity.max(1);
+        self.capacity.store(capacity, Ordering::Relaxed);
+        let mut lines = self.lines.lock().unwrap();
+        while lines.1.len() > capacity {
+            lines.1.pop_front();
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    // Return the lines pushed after the line with the given number and the number to continue with:
+    pub fn lines_after(&self, number: u64) -> (Vec<String>, u64) {
+        let lines = self.lines.lock().unwrap();
+        let (count, buffer) = &*lines;
+        let first = count - buffer.len() as u64;
+        let skip = number.saturating_sub(first) as usize;
+        (buffer.iter().skip(skip).cloned().collect(), *count)
+    }
+}
+
+// Dispatch for mxl_base::logging::Builder::add_dispatch(), which keeps the log in memory for the log viewer.
+// There is only one ring buffer, further calls change its capacity:
+pub fn dispatch(capacity: usize) -> fern::Dispatch {
+    let ring_buffer = RING_BUFFER.get_or_init(|| LogRingBuffer::new(capacity));
+    ring_buffer.set_capacity(capacity);
+    fern::Dispatch::new()
+        .format(|out, message, record| out.finish(format_args!("{} [{}] {}", record.level(), record.target(), message)))
+        .chain(fern::Output::call(move |record| {
+            let line = record.args().to_string();
+            // Personal data is removed like in the log file:
+            let redacted = mxl_base::logging::redactor().and_then(|redactor| match redactor.redact(&line) {
+                Cow::Owned(redacted) => Some(redacted),
+                Cow::Borrowed(_) => None,
+            });
+            ring_buffer.push(redacted.unwrap_or(line))
+        }))
+}
+
+pub fn ring_buffer() -> Option<&'static LogRingBuffer> {
+    RING_BUFFER.get()
+}
@@ -0,0 +1,286 @@
+use log::*;
+use relm4::{
+    gtk::{glib, prelude::*},
+    prelude::*,
+};
+use std::{collections::VecDeque, time::Duration};
+
+use super::{
+    helper::{self, LogLine},
+    messages::{LogViewerComponentInput, internal::PrivateMsg},
+    model::{LogViewerComponentInit, LogViewerComponentModel, LogViewerSource},
+    ring_buffer,
+};
+use crate::localization::helper::fl;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const SPACING: i32 = 6;
+const ERROR_TAG: &str = "error";
+const WARNING_TAG: &str = "warning";
+const END_MARK: &str = "end";
+const LEVELS: [log::LevelFilter; 5] = [
+    log::LevelFilter::Error,
+    log::LevelFilter::Warn,
+    log::LevelFilter::Info,
+    log::LevelFilter::Debug,
+    log::LevelFilter::Trace,
+];
+
+#[relm4::component(pub)]
+impl Component for LogViewerComponentModel {
+    type Init = LogViewerComponentInit;
+    type Input = LogViewerComponentInput;
+    type Output = ();
+    type CommandOutput = ();
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: SPACING,
+            set_hexpand: true,
+            set_vexpand: true,
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Horizontal,
+                set_spacing: SPACING,
+                set_margin_all: SPACING,
+
+                gtk::DropDown {
+                    #[watch]
+                    set_tooltip_text: Some(&fl!("log-viewer", "level")),
+                    set_model: Some(&gtk::StringList::new(&LEVELS.map(|level| level.as_str()))),
+                    set_selected: LEVELS.iter().position(|level| *level == model.level).unwrap_or_default() as u32,
+                    connect_selected_notify[sender] => move |drop_down| {
+                        if let Some(level) = LEVELS.get(drop_down.selected() as usize) {
+                            sender.input(LogViewerComponentInput::PrivateMessage(PrivateMsg::LevelChanged(*level)));
+                        }
+                    },
+                },
+
+                gtk::Entry {
+                    #[watch]
+                    set_placeholder_text: Some(&fl!("log-viewer", "target")),
+                    connect_changed[sender] => move |entry| {
+                        sender.input(LogViewerComponentInput::PrivateMessage(PrivateMsg::TargetChanged(entry.text().to_string())));
+                    },
+                },
+
+                gtk::SearchEntry {
+                    set_hexpand: true,
+                    #[watch]
+                    set_placeholder_text: Some(&fl!("log-viewer", "search")),
+                    connect_search_changed[sender] => move |entry| {
+                        sender.input(LogViewerComponentInput::PrivateMessage(PrivateMsg::TextChanged(entry.text().to_string())));
+                    },
+                },
+
+                #[name(copy_button)]
+                gtk::Button {
+                    #[watch]
+                    set_label: &fl!("log-viewer", "copy"),
+                    #[watch]
+                    set_tooltip_text: Some(&fl!("log-viewer", "copy-desc")),
+                    connect_clicked => LogViewerComponentInput::PrivateMessage(PrivateMsg::Copy),
+                },
+            },
+
+            #[name(scrolled_window)]
+            gtk::ScrolledWindow {
+                set_hexpand: true,
+                set_vexpand: true,
+
+                #[name(text_view)]
+                gtk::TextView {
+                    set_buffer: Some(&model.buffer),
+                    set_editable: false,
+                    set_cursor_visible: false,
+                    set_monospace: true,
+                    set_wrap_mode: gtk::WrapMode::None,
+                },
+            },
+        }
+    }
+
+    // Initialize the component.
+    fn init(init: Self::Init, root: Self::Root, sender: ComponentSender<Self>) -> ComponentParts<Self> {
+        let buffer = gtk::TextBuffer::new(None);
+        buffer.create_tag(Some(ERROR_TAG), &[("foreground", &"#e01b24"), ("weight", &700)]);
+        buffer.create_tag(Some(WARNING_TAG), &[("foreground", &"#c64600")]);
+        buffer.create_mark(Some(END_MARK), &buffer.end_iter(), false);
+
+        let mut model = LogViewerComponentModel {
+            source: init.source,
+            max_lines: init.max_lines.max(1),
+            lines: VecDeque::new(),
+            file_offset: 0,
+            partial_line: String::new(),
+            ring_buffer_position: 0,
+            level: init.level,
+            target_prefix: String::new(),
+            text: String::new(),
+            buffer,
+            _language_subscription: mxl_base::language::subscription({
+                let sender = sender.input_sender().clone();
+                move |_| sender.emit(LogViewerComponentInput::PrivateMessage(PrivateMsg::LanguageChanged))
+            }),
+        };
+
+        let widgets = view_output!();
+
+        model.poll(&widgets);
+
+        let input_sender = sender.input_sender().clone();
+        glib::timeout_add_local(POLL_INTERVAL, move || {
+            match input_sender.send(LogViewerComponentInput::PrivateMessage(PrivateMsg::Poll)) {
+                Ok(()) => glib::ControlFlow::Continue,
+                // The component was destroyed:
+                Err(_) => glib::ControlFlow::Break,
+            }
+        });
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        match msg {
+            LogViewerComponentInput::SetSource(source) => {
+                self.source = Some(source);
+                self.clear();
+                self.poll(widgets);
+            }
+            LogViewerComponentInput::Clear => {
+                self.lines.clear();
+                self.buffer.set_text("");
+            }
+            LogViewerComponentInput::PrivateMessage(msg) => match msg {
+                PrivateMsg::Poll => self.poll(widgets),
+                // The translated labels are watched and updated with the view:
+                PrivateMsg::LanguageChanged => {}
+                PrivateMsg::LevelChanged(level) => {
+                    self.level = level;
+                    self.render();
+                }
+                PrivateMsg::TargetChanged(target_prefix) => {
+                    self.target_prefix = target_prefix;
+                    self.render();
+                }
+                PrivateMsg::TextChanged(text) => {
+                    self.text = text;
+                    self.render();
+                }
+                PrivateMsg::Copy => {
+                    // Copy the selection or all visible lines:
+                    let (start, end) = self.buffer.selection_bounds().unwrap_or_else(|| self.buffer.bounds());
+                    let text = self.buffer.text(&start, &end, false);
+                    widgets.text_view.clipboard().set_text(&text);
+                }
+            },
+        }
+        self.update_view(widgets, sender)
+    }
+}
+
+impl LogViewerComponentModel {
+    fn clear(&mut self) {
+        self.lines.clear();
+        self.file_offset = 0;
+        self.partial_line.clear();
+        self.ring_buffer_position = 0;
+        self.buffer.set_text("");
+    }
+
+    fn read_new_lines(&mut self) -> Vec<String> {
+        match &self.source {
+            Some(LogViewerSource::File(path)) => {
+                helper::read_new_lines(path, &mut self.file_offset, &mut self.partial_line).unwrap_or_else(|error| {
+                    trace!("Cannot read log file {path:?}: {error:?}");
+                    Vec::new()
+                })
+            }
+            Some(LogViewerSource::RingBuffer) => match ring_buffer::ring_buffer() {
+                Some(ring_buffer) => {
+                    let (lines, position) = ring_buffer.lines_after(self.ring_buffer_position);
+                    self.ring_buffer_position = position;
+                    lines
+                }
+                None => Vec::new(),
+            },
+            None => Vec::new(),
+        }
+    }
+
+    fn poll(&mut self, widgets: &LogViewerComponentModelWidgets) {
+        let new_lines = self.read_new_lines();
+        if new_lines.is_empty() {
+            return;
+        }
+
+        // Follow the end of the log only if it is already shown:
+        let adjustment = widgets.scrolled_window.vadjustment();
+        let at_end = adjustment.value() + adjustment.page_size() >= adjustment.upper() - 1_f64;
+
+        let new_line_count = new_lines.len();
+        for text in new_lines {
+            let line = LogLine::parse(&text, self.lines.back());
+            self.lines.push_back(line);
+        }
+
+        let excess = self.lines.len().saturating_sub(self.max_lines);
+        if excess >= self.lines.len() - new_line_count {
+            // Also new lines are dropped, so the remaining lines are shown completely:
+            self.lines.drain(..excess);
+            self.render();
+        } else {
+            // Remove the dropped lines from the view and append the new ones:
+            let removed_visible_lines = self
+                .lines
+                .drain(..excess)
+                .filter(|line| line.matches(self.level, &self.target_prefix, &self.text))
+                .count();
+            if removed_visible_lines > 0 {
+                let mut start = self.buffer.start_iter();
+                let mut end = self
+                    .buffer
+                    .iter_at_line(removed_visible_lines as i32)
+                    .unwrap_or_else(|| self.buffer.end_iter());
+                self.buffer.delete(&mut start, &mut end);
+            }
+            for line in self.lines.range(self.lines.len() - new_line_count..) {
+                if line.matches(self.level, &self.target_prefix, &self.text) {
+                    self.append(line);
+                }
+            }
+        }
+
+        if at_end && let Some(mark) = self.buffer.mark(END_MARK) {
+            widgets.text_view.scroll_mark_onscreen(&mark);
+        }
+    }
+
+    fn render(&self) {
+        self.buffer.set_text("");
+        for line in self
+            .lines
+            .iter()
+            .filter(|line| line.matches(self.level, &self.target_prefix, &self.text))
+        {
+            self.append(line);
+        }
+    }
+
+    fn append(&self, line: &LogLine) {
+        let mut end = self.buffer.end_iter();
+        let text = format!("{}\n", line.text);
+        match line.level {
+            Some(log::Level::Error) => self.buffer.insert_with_tags_by_name(&mut end, &text, &[ERROR_TAG]),
+            Some(log::Level::Warn) => self.buffer.insert_with_tags_by_name(&mut end, &text, &[WARNING_TAG]),
+            _ => self.buffer.insert(&mut end, &text),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "log_viewer")]
+
+use mxl_relm4_components::{
+    log_viewer::{LogViewerComponentInit, model::LogViewerComponentModel},
+    relm4::{
+        gtk::{glib, prelude::*},
+        prelude::*,
+    },
+};
+
+// Process the messages, which were sent to the components:
+fn process_messages() {
+    let context = glib::MainContext::default();
+    while context.iteration(false) {}
+}
+
+#[test]
+fn labels_follow_language_switch() {
+    mxl_relm4_components::init().unwrap();
+    mxl_base::language::set_language(Some("en")).unwrap();
+    let log_viewer = LogViewerComponentModel::builder()
+        .launch(LogViewerComponentInit {
+            source: None,
+            ..Default::default()
+        })
+        .detach();
+    process_messages();
+    assert_eq!(log_viewer.widgets().copy_button.label().as_deref(), Some("Copy"));
+
+    mxl_base::language::set_language(Some("de")).unwrap();
+    process_messages();
+    assert_eq!(log_viewer.widgets().copy_button.label().as_deref(), Some("Kopieren"));
+
+    // The subscription ends with the component:
+    drop(log_viewer);
+    mxl_base::language::set_language(Some("en")).unwrap();
+    process_messages();
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "log_viewer")]
+
+use mxl_relm4_components::log_viewer::{
+    helper::{LogLine, parse_json_line, parse_text_line, read_new_lines},
+    ring_buffer,
+};
+use std::io::Write;
+
+#[test]
+fn parse_text_lines() {
+    assert_eq!(
+        parse_text_line("[2026-10-19T10:00:00.000] INFO [main] [my_app::net] connected"),
+        Some((Some(log::Level::Info), "my_app::net".to_string()))
+    );
+    assert_eq!(
+        parse_text_line("WARN [my_app] disk almost full"),
+        Some((Some(log::Level::Warn), "my_app".to_string()))
+    );
+    // Forwarded GStreamer messages have their level in the target:
+    assert_eq!(
+        parse_text_line("[2026-10-19T10:00:00.000] ERROR [warning|gst::play] failed"),
+        Some((Some(log::Level::Error), "warning|gst::play".to_string()))
+    );
+    assert_eq!(
+        parse_text_line("DEBUG without target"),
+        Some((Some(log::Level::Debug), String::new()))
+    );
+    assert_eq!(parse_text_line("continuation of a message"), None);
+    assert_eq!(parse_text_line(""), None);
+}
+
+#[test]
+fn parse_json_lines() {
+    assert_eq!(
+        parse_json_line(r#"{"level":"WARN","target":"my_app::db","message":"slow query"}"#),
+        Some((Some(log::Level::Warn), "my_app::db".to_string()))
+    );
+    assert_eq!(
+        parse_json_line(r#"{"level":"loud","message":"unknown level"}"#),
+        Some((None, String::new()))
+    );
+    assert_eq!(parse_json_line(r#"{"message":"no level"}"#), None);
+    assert_eq!(parse_json_line("{not json"), None);
+}
+
+#[test]
+fn continuation_lines_belong_to_the_previous_record() {
+    let first = LogLine::parse("ERROR [my_app] multi-line message", None);
+    let second = LogLine::parse("  second line", Some(&first));
+    assert_eq!(second.level, Some(log::Level::Error));
+    assert_eq!(second.target, "my_app");
+    assert!(second.matches(log::LevelFilter::Warn, "my_app", "second"));
+    assert!(!second.matches(log::LevelFilter::Warn, "other", ""));
+    assert!(!LogLine::parse("INFO [my_app] started", None).matches(log::LevelFilter::Warn, "", ""));
+}
+
+#[test]
+fn read_appended_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.log");
+    let mut file = std::fs::File::create(&path).unwrap();
+    let mut offset = 0;
+    let mut partial_line = String::new();
+
+    // A partial line is kept until it is completed:
+    write!(file, "first\nsecond\npar").unwrap();
+    let lines = read_new_lines(&path, &mut offset, &mut partial_line).unwrap();
+    assert_eq!(lines, ["first", "second"]);
+    assert_eq!(partial_line, "par");
+    assert!(
+        read_new_lines(&path, &mut offset, &mut partial_line)
+            .unwrap()
+            .is_empty()
+    );
+
+    write!(file, "tial\nthird\n").unwrap();
+    let lines = read_new_lines(&path, &mut offset, &mut partial_line).unwrap();
+    assert_eq!(lines, ["partial", "third"]);
+    assert!(partial_line.is_empty());
+
+    // A truncated file is read from the start:
+    std::fs::write(&path, "new\n").unwrap();
+    let lines = read_new_lines(&path, &mut offset, &mut partial_line).unwrap();
+    assert_eq!(lines, ["new"]);
+    assert_eq!(offset, 4);
+}
+
+#[test]
+fn ring_buffer_lines_after() {
+    let (_, logger) = ring_buffer::dispatch(3).into_log();
+    for index in 0..5 {
+        logger.log(
+            &log::Record::builder()
+                .args(format_args!("message {index}"))
+                .level(log::Level::Info)
+                .target("test")
+                .build(),
+        );
+    }
+    let ring_buffer = ring_buffer::ring_buffer().unwrap();
+
+    // Only the last lines are kept:
+    let (lines, next) = ring_buffer.lines_after(0);
+    assert_eq!(
+        lines,
+        [
+            "INFO [test] message 2",
+            "INFO [test] message 3",
+            "INFO [test] message 4"
+        ]
+    );
+    assert_eq!(next, 5);
+    assert_eq!(ring_buffer.lines_after(4).0, ["INFO [test] message 4"]);
+    assert!(ring_buffer.lines_after(next).0.is_empty());
+
+    // A further dispatch changes the capacity of the ring buffer:
+    let _ = ring_buffer::dispatch(2);
+    assert_eq!(ring_buffer.capacity(), 2);
+    assert_eq!(
+        ring_buffer.lines_after(0),
+        (
+            vec!["INFO [test] message 3".to_string(), "INFO [test] message 4".to_string()],
+            5
+        )
+    );
+}